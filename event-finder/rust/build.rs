@@ -1,6 +1,14 @@
 use std::io::Result;
 
 fn main() -> Result<()> {
-    prost_build::compile_protos(&["src/proto/types.proto"], &["src/"])?;
+    // The messages can be converted from and to their JSON form
+    prost_build::Config::new()
+        .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        .type_attribute(".", "#[serde(rename_all = \"camelCase\", default)]")
+        .field_attribute(
+            "VehicleQueryResponse.elapsedTimeInMS",
+            "#[serde(rename = \"elapsedTimeInMS\")]",
+        )
+        .compile_protos(&["src/proto/types.proto"], &["src/"])?;
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,304 @@
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+// Bounds the invalidations remembered for the queries still running.
+const MAX_RECENT_INVALIDATIONS: usize = 1024;
+
+/// The rows and final stats of a completed vehicle query, ready to be replayed.
+#[derive(Debug)]
+pub struct CachedVehicleQuery {
+    pub results: Vec<crate::types::VehicleQueryResult>,
+    pub response: crate::types::VehicleQueryResponse,
+    pub from_date: DateTime<Utc>,
+    pub to_date: DateTime<Utc>,
+    pub partitions: Option<HashSet<String>>,
+    pub size_in_bytes: usize,
+    /// When the query started running, to discard it if new data landed in the meantime.
+    pub started_at: Instant,
+}
+
+impl CachedVehicleQuery {
+    /// Checks whether new data in the given geohash partitions and time range may change the results.
+    /// No partitions means that the data may be anywhere.
+    pub fn overlaps(
+        &self,
+        partition_keys: &HashSet<String>,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> bool {
+        let in_area = match &self.partitions {
+            Some(partitions) if !partition_keys.is_empty() => partitions.iter().any(|partition| {
+                partition_keys.iter().any(|key| {
+                    key.starts_with(partition.as_str()) || partition.starts_with(key.as_str())
+                })
+            }),
+            _ => true,
+        };
+        in_area && from_date < self.to_date && to_date > self.from_date
+    }
+}
+
+struct Invalidation {
+    at: Instant,
+    partition_keys: HashSet<String>,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct QueryCacheState {
+    entries: HashMap<u64, Arc<CachedVehicleQuery>>,
+    // least recently used keys first
+    usage: VecDeque<u64>,
+    size_in_bytes: usize,
+    // oldest invalidations first
+    invalidations: VecDeque<Invalidation>,
+    // the queries started before this instant may have missed a forgotten invalidation
+    forgotten_until: Option<Instant>,
+}
+
+impl QueryCacheState {
+    fn touch(&mut self, key: u64) {
+        if let Some(pos) = self.usage.iter().position(|k| *k == key) {
+            self.usage.remove(pos);
+        }
+        self.usage.push_back(key);
+    }
+
+    fn remove(&mut self, key: u64) -> Option<Arc<CachedVehicleQuery>> {
+        let entry = self.entries.remove(&key)?;
+        if let Some(pos) = self.usage.iter().position(|k| *k == key) {
+            self.usage.remove(pos);
+        }
+        self.size_in_bytes -= entry.size_in_bytes;
+        Some(entry)
+    }
+
+    fn is_stale(&self, entry: &CachedVehicleQuery) -> bool {
+        self.forgotten_until
+            .is_some_and(|at| entry.started_at <= at)
+            || self.invalidations.iter().any(|invalidation| {
+                invalidation.at >= entry.started_at
+                    && entry.overlaps(
+                        &invalidation.partition_keys,
+                        invalidation.from_date,
+                        invalidation.to_date,
+                    )
+            })
+    }
+
+    fn record(&mut self, invalidation: Invalidation) {
+        if self.invalidations.len() >= MAX_RECENT_INVALIDATIONS
+            && let Some(oldest) = self.invalidations.pop_front()
+        {
+            self.forgotten_until = Some(oldest.at);
+        }
+        self.invalidations.push_back(invalidation);
+    }
+}
+
+/// A byte-bounded LRU cache of vehicle query results, keyed by a normalized hash of the query.
+pub struct QueryCache {
+    max_size_in_bytes: usize,
+    state: Mutex<QueryCacheState>,
+}
+
+impl QueryCache {
+    pub fn new(max_size_in_bytes: usize) -> Self {
+        Self {
+            max_size_in_bytes,
+            state: Mutex::new(QueryCacheState::default()),
+        }
+    }
+
+    pub fn get(&self, key: u64) -> Option<Arc<CachedVehicleQuery>> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state.entries.get(&key).cloned()?;
+        state.touch(key);
+        Some(entry)
+    }
+
+    pub fn insert(&self, key: u64, entry: CachedVehicleQuery) {
+        if entry.size_in_bytes > self.max_size_in_bytes {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        // New data may have landed while the query was running
+        if state.is_stale(&entry) {
+            return;
+        }
+        state.remove(key);
+        while state.size_in_bytes + entry.size_in_bytes > self.max_size_in_bytes {
+            match state.usage.front().copied() {
+                Some(oldest) => {
+                    state.remove(oldest);
+                }
+                None => break,
+            }
+        }
+        state.size_in_bytes += entry.size_in_bytes;
+        state.entries.insert(key, Arc::new(entry));
+        state.touch(key);
+    }
+
+    /// Drops every entry that covers one of the given geohash partitions and overlaps the given time range,
+    /// and remembers it for the queries still running.
+    pub fn invalidate_partitions(
+        &self,
+        partition_keys: HashSet<String>,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let keys: Vec<u64> = state
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.overlaps(&partition_keys, from_date, to_date))
+            .map(|(key, _)| *key)
+            .collect();
+        for key in keys.iter() {
+            state.remove(*key);
+        }
+        state.record(Invalidation {
+            at: Instant::now(),
+            partition_keys,
+            from_date,
+            to_date,
+        });
+        keys.len()
+    }
+
+    pub fn clear(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let count = state.entries.len();
+        *state = QueryCacheState {
+            forgotten_until: Some(Instant::now()),
+            ..Default::default()
+        };
+        count
+    }
+}
+
+/// Computes the cache key of a query, ignoring the attributes that don't change its results.
+pub fn compute_query_key(query: &crate::types::VehicleQueryRequest) -> anyhow::Result<u64> {
+    let mut normalized = query.clone();
+    normalized.id = String::new();
+    normalized.timeout = None;
+    normalized.ttl = None;
+    normalized.parallelize = None;
+    normalized.use_chunking = None;
+    normalized.from_date = query.from_date.parse::<DateTime<Utc>>()?.to_rfc3339();
+    normalized.to_date = query.to_date.parse::<DateTime<Utc>>()?.to_rfc3339();
+    normalized.vehicle_types.sort();
+    normalized.vehicle_types.dedup();
+    normalized.vehicle_ids.sort();
+    normalized.vehicle_ids.dedup();
+    normalized.directions.sort();
+    normalized.directions.dedup();
+    normalized.limit = Some(query.limit.unwrap_or(crate::types::DEFAULT_QUERY_LIMIT));
+
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_vec(&normalized)?.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+pub fn estimate_result_size(result: &crate::types::VehicleQueryResult) -> usize {
+    std::mem::size_of::<crate::types::VehicleQueryResult>()
+        + result.msg_type.len()
+        + result.query_id.len()
+        + result.timestamp.len()
+        + result.vehicle_id.len()
+        + result.vehicle_type.len()
+        + result.direction.len()
+        + result.geo_hash.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(partitions: Option<&[&str]>, started_at: Instant) -> CachedVehicleQuery {
+        CachedVehicleQuery {
+            results: Vec::new(),
+            response: crate::types::VehicleQueryResponse {
+                msg_type: "vehicle-query-response".to_string(),
+                processed_files_count: 0,
+                processed_bytes: 0,
+                processed_record_count: 0,
+                selected_record_count: 0,
+                distinct_vehicle_count: 0,
+                elapsed_time_in_MS: 0,
+                timeout_expired: false,
+                limit_reached: false,
+                from_cache: false,
+                cursor: None,
+                duplicate_count: 0,
+            },
+            from_date: "2024-01-01T06:00:00Z".parse().unwrap(),
+            to_date: "2024-01-01T07:00:00Z".parse().unwrap(),
+            partitions: partitions.map(|keys| keys.iter().map(|key| key.to_string()).collect()),
+            size_in_bytes: 1,
+            started_at,
+        }
+    }
+
+    fn keys(values: &[&str]) -> HashSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn invalidates_the_entries_covering_the_flushed_partitions() {
+        let cache = QueryCache::new(1024);
+        cache.insert(1, entry(Some(&["u09tu"]), Instant::now()));
+        cache.insert(2, entry(Some(&["gcpvj"]), Instant::now()));
+        cache.insert(3, entry(None, Instant::now()));
+
+        let count = cache.invalidate_partitions(
+            keys(&["u09tu", "u09tv"]),
+            "2024-01-01T06:50:00Z".parse().unwrap(),
+            "2024-01-01T07:00:00Z".parse().unwrap(),
+        );
+
+        assert_eq!(count, 2);
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(2).is_some());
+        assert!(cache.get(3).is_none());
+    }
+
+    #[test]
+    fn keeps_the_entries_outside_the_flushed_period() {
+        let cache = QueryCache::new(1024);
+        cache.insert(1, entry(Some(&["u09tu"]), Instant::now()));
+
+        let count = cache.invalidate_partitions(
+            keys(&["u09tu"]),
+            "2024-01-01T07:00:00Z".parse().unwrap(),
+            "2024-01-01T07:10:00Z".parse().unwrap(),
+        );
+
+        assert_eq!(count, 0);
+        assert!(cache.get(1).is_some());
+    }
+
+    #[test]
+    fn rejects_the_queries_started_before_an_overlapping_invalidation() {
+        let cache = QueryCache::new(1024);
+        let started_at = Instant::now();
+        cache.invalidate_partitions(
+            keys(&["u09tu"]),
+            "2024-01-01T06:50:00Z".parse().unwrap(),
+            "2024-01-01T07:00:00Z".parse().unwrap(),
+        );
+
+        cache.insert(1, entry(Some(&["u09tu"]), started_at));
+        cache.insert(2, entry(Some(&["gcpvj"]), started_at));
+        cache.insert(3, entry(Some(&["u09tu"]), Instant::now()));
+
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(2).is_some());
+        assert!(cache.get(3).is_some());
+    }
+}
@@ -236,6 +236,61 @@ pub struct FinderAzureSqlConnectionOptions {
     pub encrypt: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueryCacheConfig {
+    pub enabled: bool,
+    pub maxSizeInMB: usize,
+}
+
+impl Default for QueryCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            maxSizeInMB: 100,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GeofencesConfig {
+    pub enabled: bool,
+    pub source: String, // 'move' | 'enriched-move'
+    pub defaultLeaseInSecs: u64,
+    pub maxLeaseInSecs: u64,
+    pub maxCells: usize, // geohashes of length 5 covering the area of a geofence
+}
+
+impl Default for GeofencesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: "move".to_string(),
+            defaultLeaseInSecs: 300,
+            maxLeaseInSecs: 3600,
+            maxCells: 10000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HotTierConfig {
+    pub enabled: bool,
+    pub source: String, // 'move' | 'enriched-move'
+    pub retentionInMin: u32,
+    pub maxEventCount: usize,
+}
+
+impl Default for HotTierConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: "move".to_string(),
+            retentionInMin: 30,
+            maxEventCount: 1_000_000,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FinderConfig {
     pub logging: LoggingConfig,
@@ -248,6 +303,64 @@ pub struct FinderConfig {
     pub useChunking: bool,
     pub messageChunkSize: u32,
     pub dataSource: FinderDataSourceConfig,
+    #[serde(default)]
+    pub queryCache: QueryCacheConfig,
+    #[serde(default)]
+    pub geofences: GeofencesConfig,
+    #[serde(default)]
+    pub hotTier: HotTierConfig,
+    #[serde(default)]
+    pub exports: ExportsConfig,
+    #[serde(default)]
+    pub flightSql: FlightSqlConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportsConfig {
+    pub folder: String, // next to the data folder of the collector
+    pub maxRowsPerFile: usize,
+}
+
+impl Default for ExportsConfig {
+    fn default() -> Self {
+        Self {
+            folder: "exports".to_string(),
+            maxRowsPerFile: 1_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlightSqlConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub queryTimeoutInMS: u64,
+    pub maxRows: usize,
+    #[serde(default = "default_prepared_statement_ttl_in_min")]
+    pub preparedStatementTtlInMin: u64,
+    #[serde(default = "default_max_prepared_statements")]
+    pub maxPreparedStatements: usize,
+}
+
+fn default_prepared_statement_ttl_in_min() -> u64 {
+    60
+}
+
+fn default_max_prepared_statements() -> usize {
+    1000
+}
+
+impl Default for FlightSqlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7740,
+            queryTimeoutInMS: 60000,
+            maxRows: 1_000_000,
+            preparedStatementTtlInMin: default_prepared_statement_ttl_in_min(),
+            maxPreparedStatements: default_max_prepared_statements(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
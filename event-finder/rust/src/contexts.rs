@@ -8,6 +8,7 @@ pub struct PrometheusCounters {
 
 pub trait HasNatsClient: Clone {
     fn get_nats_client(&self) -> &async_nats::Client;
+    fn is_proto_buf_enabled(&self) -> bool;
 }
 
 #[derive(Clone)]
@@ -22,12 +23,20 @@ impl HasNatsClient for HandlerContext {
     fn get_nats_client(&self) -> &async_nats::Client {
         &self.nats_client
     }
+
+    fn is_proto_buf_enabled(&self) -> bool {
+        self.enable_proto_buf
+    }
 }
 
 #[derive(Clone)]
 pub struct DataHandlerContext {
     pub parent: HandlerContext,
     pub config: Arc<crate::config::Config>,
+    pub query_cache: Option<Arc<crate::cache::QueryCache>>,
+    pub geofences: Option<Arc<crate::geofences::GeofenceRegistry>>,
+    pub hot_tier: Option<Arc<crate::hot_tier::HotTier>>,
+    pub query_events: Arc<crate::query_events::QueryEventHub>,
     session: Arc<Mutex<datafusion::execution::context::SessionContext>>,
 }
 
@@ -36,10 +45,27 @@ impl DataHandlerContext {
         parent: HandlerContext,
         config: Arc<crate::config::Config>,
         session: datafusion::execution::context::SessionContext,
+        hot_tier: Option<Arc<crate::hot_tier::HotTier>>,
     ) -> Self {
+        let query_cache = if config.finder.queryCache.enabled {
+            Some(Arc::new(crate::cache::QueryCache::new(
+                config.finder.queryCache.maxSizeInMB * 1024 * 1024,
+            )))
+        } else {
+            None
+        };
+        let geofences = if config.finder.geofences.enabled {
+            Some(Arc::new(crate::geofences::GeofenceRegistry::default()))
+        } else {
+            None
+        };
         Self {
             parent,
             config,
+            query_cache,
+            geofences,
+            hot_tier,
+            query_events: Arc::new(crate::query_events::QueryEventHub::default()),
             session: Arc::new(Mutex::new(session)),
         }
     }
@@ -59,4 +85,64 @@ impl HasNatsClient for DataHandlerContext {
     fn get_nats_client(&self) -> &async_nats::Client {
         &self.parent.get_nats_client()
     }
+
+    fn is_proto_buf_enabled(&self) -> bool {
+        self.parent.is_proto_buf_enabled()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn test_config() -> anyhow::Result<crate::config::Config> {
+        crate::config::load_config(concat!(env!("CARGO_MANIFEST_DIR"), "/../../config.yaml"))
+    }
+
+    /// A context over the given session, whose NATS client connects in the background and queues
+    /// the published messages, so that the handlers can run without a NATS server.
+    pub async fn data_handler_context(
+        config: crate::config::Config,
+        session: datafusion::execution::context::SessionContext,
+    ) -> anyhow::Result<DataHandlerContext> {
+        let nats_client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect("127.0.0.1:4222")
+            .await?;
+        let prometheus_counters = PrometheusCounters {
+            prometheus_registry: prometheus::Registry::new(),
+            vehicles_search_processed_events_total_counter: prometheus::IntCounterVec::new(
+                prometheus::opts!("vehicles_search_processed_events_total", "Test counter"),
+                &[],
+            )?,
+        };
+        let parent = HandlerContext {
+            nats_client,
+            prometheus_counters,
+            identity: crate::types::ServiceIdentity {
+                name: "finder".to_string(),
+                instance: 0,
+                runtime: "rust".to_string(),
+            },
+            enable_proto_buf: false,
+        };
+        Ok(DataHandlerContext::new(
+            parent,
+            Arc::new(config),
+            session,
+            None,
+        ))
+    }
+
+    /// A NATS request wrapping the given body, whose results and response are queued by the test client.
+    pub fn request<TBody: crate::types::HasMessageType>(
+        body: serde_json::Value,
+    ) -> anyhow::Result<crate::types::Request<TBody>> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "id": "r1",
+            "type": "request",
+            "replyTo": "replies.test",
+            "body": body,
+        }))?)
+    }
 }
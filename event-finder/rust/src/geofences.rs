@@ -0,0 +1,356 @@
+use crate::utils::moves::VehiclePosition;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+// Same precision as the partitions of the events, which keeps the index small even for large areas.
+const INDEX_PRECISION: usize = 5;
+
+/// A standing query notifying the vehicles that enter and leave an area.
+#[derive(Debug, Clone)]
+pub struct Geofence {
+    pub subscription_id: String,
+    pub reply_to: String,
+    pub area: crate::query::QueryArea,
+    pub vehicle_types: Vec<String>,
+    pub vehicle_ids: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    cells: HashSet<String>,
+}
+
+impl Geofence {
+    pub fn new(
+        subscription_id: String,
+        reply_to: String,
+        area: crate::query::QueryArea,
+        vehicle_types: Vec<String>,
+        vehicle_ids: Vec<String>,
+        expires_at: DateTime<Utc>,
+        max_cells: usize,
+    ) -> anyhow::Result<Self> {
+        let bounds = area.bounds();
+        // Checked before computing the cells, as a continent wide area would have millions of them
+        if crate::utils::geo::geohash_covering_samples(&bounds, INDEX_PRECISION) > max_cells {
+            anyhow::bail!(
+                "The area of the geofence is too large, it must be covered by at most {} geohashes of length {}",
+                max_cells,
+                INDEX_PRECISION
+            );
+        }
+        let cells = crate::utils::geo::geohash_covering(&bounds, INDEX_PRECISION);
+        Ok(Self {
+            subscription_id,
+            reply_to,
+            area,
+            vehicle_types,
+            vehicle_ids,
+            expires_at,
+            cells,
+        })
+    }
+
+    fn contains(&self, position: &VehiclePosition) -> bool {
+        (self.vehicle_types.is_empty() || self.vehicle_types.contains(&position.vehicle_type))
+            && (self.vehicle_ids.is_empty() || self.vehicle_ids.contains(&position.vehicle_id))
+            && self.area.contains(position.lon, position.lat)
+    }
+}
+
+/// An event to publish to the reply subject of a geofence.
+#[derive(Debug)]
+pub struct GeofenceNotification {
+    pub reply_to: String,
+    pub event: crate::types::GeofenceEvent,
+}
+
+#[derive(Default)]
+struct GeofenceState {
+    fences: HashMap<String, Geofence>,
+    // geohash => subscription ids of the fences covering it
+    cells: HashMap<String, HashSet<String>>,
+    // vehicle id => subscription ids of the fences containing the vehicle
+    inside: HashMap<String, HashSet<String>>,
+}
+
+impl GeofenceState {
+    fn remove(&mut self, subscription_id: &str) -> Option<Geofence> {
+        let fence = self.fences.remove(subscription_id)?;
+        for cell in fence.cells.iter() {
+            if let Some(ids) = self.cells.get_mut(cell) {
+                ids.remove(subscription_id);
+                if ids.is_empty() {
+                    self.cells.remove(cell);
+                }
+            }
+        }
+        self.inside.retain(|_, ids| {
+            ids.remove(subscription_id);
+            !ids.is_empty()
+        });
+        Some(fence)
+    }
+}
+
+/// The active geofences, indexed by the geohashes covering their area.
+#[derive(Default)]
+pub struct GeofenceRegistry {
+    state: Mutex<GeofenceState>,
+}
+
+impl GeofenceRegistry {
+    /// Registers the geofence, replacing any geofence with the same subscription id.
+    pub fn insert(&self, fence: Geofence) {
+        let mut state = self.state.lock().unwrap();
+        state.remove(&fence.subscription_id);
+        for cell in fence.cells.iter() {
+            state
+                .cells
+                .entry(cell.clone())
+                .or_default()
+                .insert(fence.subscription_id.clone());
+        }
+        state.fences.insert(fence.subscription_id.clone(), fence);
+    }
+
+    pub fn remove(&self, subscription_id: &str) -> Option<Geofence> {
+        let mut state = self.state.lock().unwrap();
+        state.remove(subscription_id)
+    }
+
+    pub fn remove_expired(&self, now: DateTime<Utc>) -> Vec<Geofence> {
+        let mut state = self.state.lock().unwrap();
+        let expired: Vec<String> = state
+            .fences
+            .values()
+            .filter(|fence| fence.expires_at <= now)
+            .map(|fence| fence.subscription_id.clone())
+            .collect();
+        expired
+            .iter()
+            .filter_map(|subscription_id| state.remove(subscription_id))
+            .collect()
+    }
+
+    /// Returns the enter and exit events caused by the new position of a vehicle.
+    pub fn update(&self, position: &VehiclePosition) -> anyhow::Result<Vec<GeofenceNotification>> {
+        let hash = geohash::encode(
+            geo::coord! { x: position.lon, y: position.lat },
+            INDEX_PRECISION,
+        )?;
+        let mut state = self.state.lock().unwrap();
+        let current: HashSet<String> = match state.cells.get(&hash) {
+            Some(ids) => ids
+                .iter()
+                .filter(|id| {
+                    state
+                        .fences
+                        .get(*id)
+                        .is_some_and(|fence| fence.contains(position))
+                })
+                .cloned()
+                .collect(),
+            None => HashSet::new(),
+        };
+        let previous = state
+            .inside
+            .remove(&position.vehicle_id)
+            .unwrap_or_default();
+        if current.is_empty() && previous.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut notifications = vec![];
+        let events = current
+            .difference(&previous)
+            .map(|id| (id, crate::types::GeofenceEventType::Enter))
+            .chain(
+                previous
+                    .difference(&current)
+                    .map(|id| (id, crate::types::GeofenceEventType::Exit)),
+            );
+        for (subscription_id, event) in events {
+            if let Some(fence) = state.fences.get(subscription_id) {
+                notifications.push(GeofenceNotification {
+                    reply_to: fence.reply_to.clone(),
+                    event: crate::types::GeofenceEvent {
+                        msg_type: "geofence-event".to_string(),
+                        subscription_id: subscription_id.clone(),
+                        event,
+                        timestamp: position.timestamp.clone(),
+                        vehicle_id: position.vehicle_id.clone(),
+                        vehicle_type: position.vehicle_type.clone(),
+                        gps: crate::types::GpsCoordinates {
+                            lat: position.lat,
+                            lon: position.lon,
+                            alt: position.alt,
+                        },
+                        direction: position.direction.clone(),
+                        speed: position.speed,
+                    },
+                });
+            }
+        }
+        if !current.is_empty() {
+            state.inside.insert(position.vehicle_id.clone(), current);
+        }
+        Ok(notifications)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::QueryArea;
+    use crate::types::GeofenceEventType;
+    use chrono::Duration;
+    use geo::Point;
+
+    const MAX_CELLS: usize = 10000;
+
+    fn fence(subscription_id: &str, lon: f64, lat: f64, expires_at: DateTime<Utc>) -> Geofence {
+        Geofence::new(
+            subscription_id.to_string(),
+            format!("inbox.{}", subscription_id),
+            QueryArea::Circle {
+                center: Point::new(lon, lat),
+                radius_in_meters: 500.0,
+            },
+            vec!["car".to_string()],
+            vec![],
+            expires_at,
+            MAX_CELLS,
+        )
+        .unwrap()
+    }
+
+    fn position(vehicle_type: &str, lon: f64, lat: f64) -> VehiclePosition {
+        VehiclePosition {
+            vehicle_id: "v1".to_string(),
+            vehicle_type: vehicle_type.to_string(),
+            timestamp: "2024-01-01T10:00:00Z".to_string(),
+            lat,
+            lon,
+            alt: 0.0,
+            direction: "north".to_string(),
+            speed: 10.0,
+            geo_hash: None,
+            partition_key: None,
+        }
+    }
+
+    fn events(
+        notifications: Vec<GeofenceNotification>,
+    ) -> Vec<(String, String, GeofenceEventType)> {
+        notifications
+            .into_iter()
+            .map(|notification| {
+                (
+                    notification.reply_to,
+                    notification.event.subscription_id,
+                    notification.event.event,
+                )
+            })
+            .collect()
+    }
+
+    fn event(
+        subscription_id: &str,
+        event: GeofenceEventType,
+    ) -> (String, String, GeofenceEventType) {
+        (
+            format!("inbox.{}", subscription_id),
+            subscription_id.to_string(),
+            event,
+        )
+    }
+
+    #[test]
+    fn notifies_the_vehicles_entering_and_exiting_a_geofence() -> anyhow::Result<()> {
+        let registry = GeofenceRegistry::default();
+        registry.insert(fence("a", 4.35, 50.85, Utc::now() + Duration::minutes(5)));
+
+        assert!(registry.update(&position("car", 4.37, 50.85))?.is_empty());
+        assert_eq!(
+            events(registry.update(&position("car", 4.351, 50.85))?),
+            vec![event("a", GeofenceEventType::Enter)]
+        );
+        assert!(registry.update(&position("car", 4.352, 50.851))?.is_empty());
+        assert_eq!(
+            events(registry.update(&position("car", 4.37, 50.85))?),
+            vec![event("a", GeofenceEventType::Exit)]
+        );
+        // the other vehicle types are ignored
+        assert!(
+            registry
+                .update(&position("truck", 4.351, 50.85))?
+                .is_empty()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replaces_the_geofence_with_the_same_subscription_id() -> anyhow::Result<()> {
+        let registry = GeofenceRegistry::default();
+        let expires_at = Utc::now() + Duration::minutes(5);
+        registry.insert(fence("a", 4.35, 50.85, expires_at));
+        assert_eq!(
+            events(registry.update(&position("car", 4.35, 50.85))?),
+            vec![event("a", GeofenceEventType::Enter)]
+        );
+
+        registry.insert(fence("a", 2.35, 48.85, expires_at));
+
+        // the vehicle was only inside the replaced area
+        assert!(registry.update(&position("car", 4.37, 50.85))?.is_empty());
+        assert_eq!(
+            events(registry.update(&position("car", 2.35, 48.85))?),
+            vec![event("a", GeofenceEventType::Enter)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn removes_the_expired_geofences() -> anyhow::Result<()> {
+        let registry = GeofenceRegistry::default();
+        let now = Utc::now();
+        registry.insert(fence("expired", 4.35, 50.85, now - Duration::seconds(1)));
+        registry.insert(fence("active", 4.35, 50.85, now + Duration::minutes(5)));
+
+        let expired = registry.remove_expired(now);
+
+        assert_eq!(
+            expired
+                .iter()
+                .map(|fence| fence.subscription_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["expired"]
+        );
+        assert_eq!(
+            events(registry.update(&position("car", 4.35, 50.85))?),
+            vec![event("active", GeofenceEventType::Enter)]
+        );
+        assert!(registry.remove("expired").is_none());
+        assert!(registry.remove("active").is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_the_geofences_covered_by_too_many_cells() {
+        let world = geo::Rect::new(
+            geo::coord! { x: -180.0, y: -85.0 },
+            geo::coord! { x: 180.0, y: 85.0 },
+        );
+
+        let result = Geofence::new(
+            "world".to_string(),
+            "inbox.world".to_string(),
+            QueryArea::Polygon(geo::Geometry::Polygon(world.to_polygon())),
+            vec![],
+            vec![],
+            Utc::now(),
+            MAX_CELLS,
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,333 @@
+use chrono::{DateTime, Utc};
+use datafusion::arrow::array::{Array, RecordBatch};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::cast::{as_int64_array, as_string_array};
+use datafusion::functions::expr_fn::left;
+use datafusion::functions_aggregate::expr_fn::{count, count_distinct};
+use datafusion::prelude::*;
+use log;
+use std::time::{Duration, Instant};
+
+const DEFAULT_PRECISION: u8 = 6;
+// Keeps each result message well below the max payload of NATS.
+const MAX_FEATURES_PER_MESSAGE: usize = 1000;
+
+/// How the positions are grouped into cells.
+enum DensityCells {
+    Geohash(u8),
+    Grid(crate::utils::geo::MapGrid),
+}
+
+impl DensityCells {
+    fn group_exprs(&self) -> Vec<Expr> {
+        match self {
+            Self::Geohash(precision) => vec![
+                cast(
+                    left(col(r#""geoHash""#), lit(*precision as i64)),
+                    DataType::Utf8,
+                )
+                .alias("cell"),
+            ],
+            Self::Grid(grid) => vec![
+                crate::query::grid_column_expr(grid).alias("cell_column"),
+                crate::query::grid_row_expr(grid).alias("cell_row"),
+            ],
+        }
+    }
+
+    fn feature(&self, batch: &RecordBatch, i: usize) -> anyhow::Result<geojson::Feature> {
+        let (id, polygon) = match self {
+            Self::Geohash(_) => {
+                let cell = as_string_array(column(batch, "cell")?)?.value(i);
+                let bbox = geohash::decode_bbox(cell)?;
+                (cell.to_string(), bbox.to_polygon())
+            }
+            Self::Grid(grid) => {
+                let cell_column = as_int64_array(column(batch, "cell_column")?)?.value(i);
+                let cell_row = as_int64_array(column(batch, "cell_row")?)?.value(i);
+                (
+                    format!("{}:{}", cell_column, cell_row),
+                    grid.cell_polygon(cell_column, cell_row),
+                )
+            }
+        };
+        let mut properties = geojson::JsonObject::new();
+        properties.insert("cell".to_string(), id.clone().into());
+        properties.insert(
+            "eventCount".to_string(),
+            as_int64_array(column(batch, "event_count")?)?
+                .value(i)
+                .into(),
+        );
+        properties.insert(
+            "vehicleCount".to_string(),
+            as_int64_array(column(batch, "vehicle_count")?)?
+                .value(i)
+                .into(),
+        );
+        Ok(geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(geojson::Value::from(&polygon))),
+            id: Some(geojson::feature::Id::String(id)),
+            properties: Some(properties),
+            foreign_members: None,
+        })
+    }
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> anyhow::Result<&'a dyn Array> {
+    batch
+        .column_by_name(name)
+        .map(|column| column.as_ref())
+        .ok_or_else(|| anyhow::format_err!("Missing column '{}'", name))
+}
+
+async fn publish_features(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleDensityRequest>,
+    bucket: Option<i64>,
+    features: Vec<geojson::Feature>,
+) -> anyhow::Result<()> {
+    let result = crate::types::VehicleDensityResult {
+        msg_type: "vehicle-density-result".to_string(),
+        query_id: req.body.id.clone(),
+        bucket_start: bucket
+            .map(crate::utils::time::format_timestamp_millis)
+            .transpose()?,
+        feature_collection: geojson::FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: None,
+        },
+    };
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &result)
+        .await
+}
+
+async fn execute_density_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleDensityRequest>,
+) -> anyhow::Result<crate::types::VehicleDensityResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let cells = match (query.precision, query.grid_cell_size_in_meters) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!("Only one of precision or gridCellSizeInMeters can be specified")
+        }
+        (_, Some(cell_size)) => {
+            if cell_size <= 0.0 {
+                anyhow::bail!("gridCellSizeInMeters must be greater than 0");
+            }
+            let origin = &ctx.config.generator.map.topLeftOrigin;
+            DensityCells::Grid(crate::utils::geo::MapGrid::new(
+                geo::coord! { x: origin.lon, y: origin.lat },
+                cell_size,
+                cell_size,
+            ))
+        }
+        (precision, None) => {
+            let precision = precision.unwrap_or(DEFAULT_PRECISION);
+            let max_precision = ctx.config.collector.geohashLength;
+            if precision == 0 || precision > max_precision {
+                anyhow::bail!("precision must be between 1 and {}", max_precision);
+            }
+            DensityCells::Geohash(precision)
+        }
+    };
+    let from_date: DateTime<Utc> = query.from_date.parse()?;
+    let to_date: DateTime<Utc> = query.to_date.parse()?;
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    let mut filter = crate::query::VehicleFilter::with_range(from_date, to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+
+    let partitions = filter.partitions();
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, partitions.as_ref())?;
+    let time_range = crate::query::time_range_predicate(df.schema(), from_date, to_date)?;
+    df = df.filter(time_range)?;
+    if let Some(predicate) = filter.area_predicate() {
+        df = df.filter(predicate)?;
+    }
+
+    let mut group_exprs = cells.group_exprs();
+    if let Some(interval) = query.bucket_interval_in_min {
+        group_exprs.insert(
+            0,
+            crate::query::time_bucket_expr(df.schema(), interval)?.alias("bucket"),
+        );
+    }
+    let sort_exprs = group_exprs
+        .iter()
+        .map(|expr| col(expr.schema_name().to_string()).sort(true, false))
+        .collect::<Vec<_>>();
+    df = df
+        .aggregate(
+            group_exprs,
+            vec![
+                count(lit(1)).alias("event_count"),
+                count_distinct(col(r#""vehicleId""#)).alias("vehicle_count"),
+            ],
+        )?
+        .sort(sort_exprs)?;
+
+    let start_time = Instant::now();
+    // The aggregation only yields its rows once all the events have been read
+    let (batches, has_timed_out) = match tokio::time::timeout(
+        Duration::from_millis(query_timeout.try_into()?),
+        df.collect(),
+    )
+    .await
+    {
+        Ok(batches) => (batches?, false),
+        Err(_) => (vec![], true),
+    };
+
+    let mut cell_count: usize = 0;
+    let mut bucket_count: usize = 0;
+    let mut event_count: i64 = 0;
+    let mut current_bucket: Option<i64> = None;
+    let mut features: Vec<geojson::Feature> = vec![];
+    for batch in batches.iter() {
+        let buckets = match batch.column_by_name("bucket") {
+            Some(buckets) => Some(as_int64_array(buckets)?),
+            None => None,
+        };
+        let event_counts = as_int64_array(column(batch, "event_count")?)?;
+        for i in 0..batch.num_rows() {
+            let bucket = buckets.map(|buckets| buckets.value(i));
+            let is_new_bucket = cell_count == 0 || bucket != current_bucket;
+            if (is_new_bucket || features.len() >= MAX_FEATURES_PER_MESSAGE) && !features.is_empty()
+            {
+                publish_features(ctx, req, current_bucket, std::mem::take(&mut features)).await?;
+            }
+            if is_new_bucket {
+                bucket_count += 1;
+                current_bucket = bucket;
+            }
+            features.push(cells.feature(batch, i)?);
+            cell_count += 1;
+            event_count += event_counts.value(i);
+        }
+    }
+    if !features.is_empty() {
+        publish_features(ctx, req, current_bucket, features).await?;
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total density cells: {}", cell_count);
+
+    Ok(crate::types::VehicleDensityResponse {
+        msg_type: "vehicle-density-response".to_string(),
+        cell_count,
+        bucket_count,
+        event_count,
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+    })
+}
+
+async fn process_density_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleDensityRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_density_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle density query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_density_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.density".to_string(),
+        process_density_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+
+    fn event(
+        vehicle_id: &'static str,
+        timestamp: &'static str,
+        lon: f64,
+        lat: f64,
+    ) -> TestEvent<'static> {
+        TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp,
+            lon,
+            lat,
+        }
+    }
+
+    async fn density(
+        fields: serde_json::Value,
+    ) -> anyhow::Result<crate::types::VehicleDensityResponse> {
+        let ctx = crate::contexts::tests::data_handler_context(
+            crate::contexts::tests::test_config()?,
+            crate::query::tests::events_session(&[
+                event("a", "2024-01-01T10:00:00Z", 4.35, 50.85),
+                event("a", "2024-01-01T10:20:00Z", 4.35, 50.85),
+                event("b", "2024-01-01T10:25:00Z", 4.35, 50.85),
+                event("b", "2024-01-01T10:30:00Z", 4.45, 50.90),
+                // outside of the time range
+                event("c", "2024-01-01T11:30:00Z", 4.35, 50.85),
+            ])?,
+        )
+        .await?;
+        let mut body = serde_json::json!({
+            "type": "vehicle-density-request",
+            "id": "density-1",
+            "fromDate": "2024-01-01T10:00:00Z",
+            "toDate": "2024-01-01T11:00:00Z",
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        execute_density_query(&ctx, &crate::contexts::tests::request(body)?).await
+    }
+
+    #[tokio::test]
+    async fn counts_the_events_per_cell_and_time_bucket() -> anyhow::Result<()> {
+        let cells = density(serde_json::json!({ "precision": 5 })).await?;
+        let buckets =
+            density(serde_json::json!({ "precision": 5, "bucketIntervalInMin": 30 })).await?;
+
+        assert_eq!(
+            (cells.cell_count, cells.bucket_count, cells.event_count),
+            (2, 1, 4)
+        );
+        assert_eq!(
+            (
+                buckets.cell_count,
+                buckets.bucket_count,
+                buckets.event_count
+            ),
+            (2, 2, 4)
+        );
+        assert!(
+            density(serde_json::json!({ "precision": 5, "gridCellSizeInMeters": 100.0 }))
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+}
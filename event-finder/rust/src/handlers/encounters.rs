@@ -0,0 +1,464 @@
+use chrono::{DateTime, Utc};
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::cast::as_int64_array;
+use datafusion::functions_window::expr_fn::row_number;
+use datafusion::prelude::*;
+use futures_util::StreamExt;
+use geo::{HaversineDistance, point};
+use log;
+use std::collections::HashMap;
+use std::time::Instant;
+
+// The cells are computed from the positions, as the geohash of some rows is missing or shorter
+const MAX_CELL_PRECISION: usize = 12;
+
+/// The position of a vehicle within a sample.
+struct SampleFix {
+    vehicle_id: String,
+    vehicle_type: String,
+    timestamp: i64,
+    lon: f64,
+    lat: f64,
+}
+
+/// Two vehicles that stayed close to each other during consecutive samples.
+struct Encounter {
+    vehicle_id: String,
+    vehicle_type: String,
+    other_vehicle_id: String,
+    other_vehicle_type: String,
+    start_time: i64,
+    end_time: i64,
+    min_distance_in_meters: f64,
+    meeting_point: (f64, f64),
+    last_sample: i64,
+}
+
+impl Encounter {
+    fn new(sample: i64, fix: &SampleFix, other: &SampleFix, distance_in_meters: f64) -> Self {
+        Self {
+            vehicle_id: fix.vehicle_id.clone(),
+            vehicle_type: fix.vehicle_type.clone(),
+            other_vehicle_id: other.vehicle_id.clone(),
+            other_vehicle_type: other.vehicle_type.clone(),
+            start_time: fix.timestamp.min(other.timestamp),
+            end_time: fix.timestamp.max(other.timestamp),
+            min_distance_in_meters: distance_in_meters,
+            meeting_point: ((fix.lon + other.lon) / 2.0, (fix.lat + other.lat) / 2.0),
+            last_sample: sample,
+        }
+    }
+
+    fn push(&mut self, sample: i64, fix: &SampleFix, other: &SampleFix, distance_in_meters: f64) {
+        self.end_time = self.end_time.max(fix.timestamp.max(other.timestamp));
+        if distance_in_meters < self.min_distance_in_meters {
+            self.min_distance_in_meters = distance_in_meters;
+            self.meeting_point = ((fix.lon + other.lon) / 2.0, (fix.lat + other.lat) / 2.0);
+        }
+        self.last_sample = sample;
+    }
+
+    fn duration_in_ms(&self) -> i64 {
+        self.end_time - self.start_time
+    }
+
+    fn to_result(&self, query_id: &str) -> anyhow::Result<crate::types::VehicleEncounterResult> {
+        Ok(crate::types::VehicleEncounterResult {
+            msg_type: "vehicle-encounter-result".to_string(),
+            query_id: query_id.to_string(),
+            vehicle_id: self.vehicle_id.clone(),
+            vehicle_type: self.vehicle_type.clone(),
+            other_vehicle_id: self.other_vehicle_id.clone(),
+            other_vehicle_type: self.other_vehicle_type.clone(),
+            start_time: crate::utils::time::format_timestamp_millis(self.start_time)?,
+            end_time: crate::utils::time::format_timestamp_millis(self.end_time)?,
+            duration_in_secs: self.duration_in_ms() as f64 / 1000.0,
+            min_distance_in_meters: self.min_distance_in_meters,
+            meeting_point: crate::types::GpsCoordinates {
+                lat: self.meeting_point.1,
+                lon: self.meeting_point.0,
+                alt: 0.0,
+            },
+        })
+    }
+}
+
+/// Follows the pairs of vehicles within the distance, sample after sample.
+struct EncounterTracker {
+    distance_in_meters: f64,
+    min_duration_in_ms: i64,
+    sample_interval_in_ms: i64,
+    // (vehicle id, other vehicle id) => encounter
+    encounters: HashMap<(String, String), Encounter>,
+}
+
+impl EncounterTracker {
+    /// Returns the encounters that ended before this sample.
+    fn push_sample(&mut self, sample: i64, fixes: &[SampleFix]) -> anyhow::Result<Vec<Encounter>> {
+        let sample_interval_in_ms = self.sample_interval_in_ms;
+        let mut ended =
+            self.close(|encounter| encounter.last_sample + sample_interval_in_ms < sample);
+        if fixes.is_empty() {
+            return Ok(ended);
+        }
+
+        // Only the fixes in the same or in neighboring cells can be close enough
+        let max_abs_lat = fixes.iter().map(|fix| fix.lat.abs()).fold(0.0, f64::max);
+        let precision = crate::utils::geo::geohash_precision_for_distance(
+            self.distance_in_meters,
+            max_abs_lat,
+            MAX_CELL_PRECISION,
+        );
+        let mut cells: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, fix) in fixes.iter().enumerate() {
+            let cell = geohash::encode(geo::coord! { x: fix.lon, y: fix.lat }, precision)?;
+            cells.entry(cell).or_default().push(i);
+        }
+        for (cell, members) in cells.iter() {
+            for (k, i) in members.iter().enumerate() {
+                for j in members[k + 1..].iter() {
+                    self.compare(sample, &fixes[*i], &fixes[*j]);
+                }
+            }
+            let n = geohash::neighbors(cell)?;
+            for neighbor in [n.n, n.ne, n.e, n.se, n.s, n.sw, n.w, n.nw] {
+                // each pair of cells is only compared once
+                if neighbor <= *cell {
+                    continue;
+                }
+                if let Some(neighbor_members) = cells.get(neighbor.as_str()) {
+                    for i in members.iter() {
+                        for j in neighbor_members.iter() {
+                            self.compare(sample, &fixes[*i], &fixes[*j]);
+                        }
+                    }
+                }
+            }
+        }
+
+        ended.extend(self.close(|encounter| encounter.last_sample < sample));
+        Ok(ended)
+    }
+
+    fn compare(&mut self, sample: i64, fix: &SampleFix, other: &SampleFix) {
+        let distance =
+            point!(x: fix.lon, y: fix.lat).haversine_distance(&point!(x: other.lon, y: other.lat));
+        if distance > self.distance_in_meters || fix.vehicle_id == other.vehicle_id {
+            return;
+        }
+        let (fix, other) = if fix.vehicle_id < other.vehicle_id {
+            (fix, other)
+        } else {
+            (other, fix)
+        };
+        self.encounters
+            .entry((fix.vehicle_id.clone(), other.vehicle_id.clone()))
+            .and_modify(|encounter| encounter.push(sample, fix, other, distance))
+            .or_insert_with(|| Encounter::new(sample, fix, other, distance));
+    }
+
+    /// Removes the encounters matching the predicate, and returns the ones that lasted long enough.
+    fn close(&mut self, predicate: impl Fn(&Encounter) -> bool) -> Vec<Encounter> {
+        let keys: Vec<(String, String)> = self
+            .encounters
+            .iter()
+            .filter(|(_, encounter)| predicate(encounter))
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut ended: Vec<Encounter> = keys
+            .iter()
+            .filter_map(|key| self.encounters.remove(key))
+            .filter(|encounter| encounter.duration_in_ms() >= self.min_duration_in_ms)
+            .collect();
+        ended.sort_by(|a, b| {
+            a.start_time
+                .cmp(&b.start_time)
+                .then_with(|| a.vehicle_id.cmp(&b.vehicle_id))
+                .then_with(|| a.other_vehicle_id.cmp(&b.other_vehicle_id))
+        });
+        ended
+    }
+
+    fn finish(&mut self) -> Vec<Encounter> {
+        self.close(|_| true)
+    }
+}
+
+async fn execute_encounters_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleEncountersRequest>,
+) -> anyhow::Result<crate::types::VehicleEncountersResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let limit: usize = query
+        .limit
+        .unwrap_or(crate::types::DEFAULT_QUERY_LIMIT)
+        .try_into()?;
+    if query.distance_in_meters <= 0.0 {
+        anyhow::bail!("distanceInMeters must be greater than 0");
+    }
+    let sample_interval_in_secs = query
+        .sample_interval_in_secs
+        .unwrap_or(ctx.config.generator.refreshIntervalInSecs);
+    if sample_interval_in_secs == 0 {
+        anyhow::bail!("sampleIntervalInSecs must be greater than 0");
+    }
+    let sample_interval_in_ms = i64::from(sample_interval_in_secs) * 1000;
+    let from_date: DateTime<Utc> = query.from_date.parse()?;
+    let to_date: DateTime<Utc> = query.to_date.parse()?;
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    let mut filter = crate::query::VehicleFilter::with_range(from_date, to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+
+    let partitions = filter.partitions();
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, partitions.as_ref())?;
+    let time_range = crate::query::time_range_predicate(df.schema(), from_date, to_date)?;
+    df = df.filter(time_range)?;
+    if let Some(predicate) = filter.area_predicate() {
+        df = df.filter(predicate)?;
+    }
+    // The vehicles do not report their positions at the same time,
+    // so the first position of each vehicle within each sample interval is compared.
+    let sample = (cast(col("timestamp"), DataType::Int64) / lit(sample_interval_in_ms))
+        * lit(sample_interval_in_ms);
+    let sample_rank = row_number()
+        .partition_by(vec![col(r#""vehicleId""#), col("sample")])
+        .order_by(vec![col("timestamp").sort(true, false)])
+        .build()?
+        .alias("sample_rank");
+    df = df
+        .with_column("sample", sample)?
+        .window(vec![sample_rank])?
+        .filter(col("sample_rank").eq(lit(1u64)))?
+        .sort(vec![col("sample").sort(true, false)])?;
+    let columns = crate::query::VehicleEventColumns::try_new(df.schema())?;
+
+    let start_time = Instant::now();
+    let mut tracker = EncounterTracker {
+        distance_in_meters: query.distance_in_meters,
+        min_duration_in_ms: i64::from(query.min_duration_in_secs) * 1000,
+        sample_interval_in_ms,
+        encounters: HashMap::new(),
+    };
+    let mut response = crate::types::VehicleEncountersResponse {
+        msg_type: "vehicle-encounters-response".to_string(),
+        processed_record_count: 0,
+        sample_count: 0,
+        encounter_count: 0,
+        elapsed_time_in_MS: 0,
+        timeout_expired: false,
+        limit_reached: false,
+    };
+    let mut current_sample: Option<i64> = None;
+    let mut fixes: Vec<SampleFix> = vec![];
+    let mut ended: Vec<Encounter> = vec![];
+    let mut stream = df.execute_stream().await?;
+    'batches: while let Some(batch_result) = stream.next().await {
+        let batch = batch_result?;
+        response.processed_record_count += batch.num_rows();
+        let events = columns.read(&batch)?;
+        let samples = as_int64_array(
+            batch
+                .column_by_name("sample")
+                .ok_or_else(|| anyhow::format_err!("Missing column 'sample'"))?,
+        )?;
+        for i in 0..events.len() {
+            let event = events.event(i);
+            if !event.has_valid_position() {
+                continue;
+            }
+            let sample = samples.value(i);
+            if let Some(previous) = current_sample
+                && previous != sample
+            {
+                ended.extend(tracker.push_sample(previous, &fixes)?);
+                fixes.clear();
+            }
+            if current_sample != Some(sample) {
+                current_sample = Some(sample);
+                response.sample_count += 1;
+            }
+            fixes.push(SampleFix {
+                vehicle_id: event.vehicle_id.to_string(),
+                vehicle_type: event.vehicle_type.to_string(),
+                timestamp: event.timestamp,
+                lon: event.lon,
+                lat: event.lat,
+            });
+        }
+        for encounter in ended.drain(..) {
+            crate::utils::messaging::publish_message(
+                &ctx.parent.nats_client,
+                req.reply_to.clone(),
+                &encounter.to_result(&query.id)?,
+            )
+            .await?;
+            response.encounter_count += 1;
+            if response.encounter_count >= limit {
+                response.limit_reached = true;
+                break 'batches;
+            }
+        }
+        if start_time.elapsed().as_millis() >= query_timeout {
+            response.timeout_expired = true;
+            break;
+        }
+    }
+    if !response.limit_reached && !response.timeout_expired {
+        if let Some(sample) = current_sample {
+            ended.extend(tracker.push_sample(sample, &fixes)?);
+        }
+        ended.extend(tracker.finish());
+        for encounter in ended.drain(..) {
+            if response.encounter_count >= limit {
+                response.limit_reached = true;
+                break;
+            }
+            crate::utils::messaging::publish_message(
+                &ctx.parent.nats_client,
+                req.reply_to.clone(),
+                &encounter.to_result(&query.id)?,
+            )
+            .await?;
+            response.encounter_count += 1;
+        }
+    }
+    response.elapsed_time_in_MS = start_time.elapsed().as_millis();
+    log::info!("Total encounters: {}", response.encounter_count);
+    Ok(response)
+}
+
+async fn process_encounters_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleEncountersRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_encounters_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle encounters query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_encounters_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.encounters".to_string(),
+        process_encounters_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{HaversineDestination, Point};
+
+    const SAMPLE_INTERVAL_IN_MS: i64 = 60_000;
+
+    fn tracker() -> EncounterTracker {
+        EncounterTracker {
+            distance_in_meters: 50.0,
+            min_duration_in_ms: 60_000,
+            sample_interval_in_ms: SAMPLE_INTERVAL_IN_MS,
+            encounters: HashMap::new(),
+        }
+    }
+
+    fn fix(vehicle_id: &str, timestamp: i64, position: Point) -> SampleFix {
+        SampleFix {
+            vehicle_id: vehicle_id.to_string(),
+            vehicle_type: "taxi".to_string(),
+            timestamp,
+            lon: position.x(),
+            lat: position.y(),
+        }
+    }
+
+    /// Pushes the samples of two vehicles at the given distance of each other, returning the ended encounters.
+    fn push_samples(
+        tracker: &mut EncounterTracker,
+        origin: Point,
+        distances_in_meters: &[f64],
+    ) -> anyhow::Result<Vec<Encounter>> {
+        let mut ended = vec![];
+        for (i, distance) in distances_in_meters.iter().enumerate() {
+            let sample = i as i64 * SAMPLE_INTERVAL_IN_MS;
+            ended.extend(tracker.push_sample(
+                sample,
+                &[
+                    fix(
+                        "b",
+                        sample + 1000,
+                        origin.haversine_destination(90.0, *distance),
+                    ),
+                    fix("a", sample, origin),
+                ],
+            )?);
+        }
+        Ok(ended)
+    }
+
+    #[test]
+    fn tracks_the_vehicles_staying_close_to_each_other() -> anyhow::Result<()> {
+        let mut tracker = tracker();
+        let origin = Point::new(4.35, 50.85);
+
+        let ended = push_samples(&mut tracker, origin, &[200.0, 30.0, 10.0, 20.0, 200.0])?;
+
+        assert_eq!(ended.len(), 1);
+        let encounter = &ended[0];
+        assert_eq!(
+            (
+                encounter.vehicle_id.as_str(),
+                encounter.other_vehicle_id.as_str()
+            ),
+            ("a", "b")
+        );
+        assert_eq!(encounter.start_time, SAMPLE_INTERVAL_IN_MS);
+        assert_eq!(encounter.end_time, 3 * SAMPLE_INTERVAL_IN_MS + 1000);
+        assert!((encounter.min_distance_in_meters - 10.0).abs() < 0.1);
+        assert!(tracker.finish().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_the_encounters_shorter_than_the_min_duration() -> anyhow::Result<()> {
+        let mut tracker = tracker();
+
+        let ended = push_samples(&mut tracker, Point::new(4.35, 50.85), &[10.0, 200.0])?;
+
+        assert!(ended.is_empty());
+        assert!(tracker.finish().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn finds_the_vehicles_in_neighboring_cells() -> anyhow::Result<()> {
+        let mut tracker = tracker();
+        let cell = geohash::decode_bbox("u150upk")?;
+        // the vehicles are on both sides of the west border of a cell
+        let border = Point::new(cell.min().x, (cell.min().y + cell.max().y) / 2.0);
+        let origin = border.haversine_destination(270.0, 5.0);
+
+        push_samples(&mut tracker, origin, &[10.0, 10.0])?;
+        let ended = tracker.finish();
+
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].duration_in_ms(), SAMPLE_INTERVAL_IN_MS + 1000);
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::common::cast::as_string_array;
+use datafusion::prelude::*;
+use futures_util::StreamExt;
+use log;
+use object_store::ObjectStore;
+use object_store::buffered::BufWriter;
+use object_store::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::AsyncWriteExt;
+
+const PROGRESS_INTERVAL_IN_MS: u128 = 2000;
+
+/// A file being written in the export store.
+struct ExportPart {
+    path: Path,
+    // set when each vehicle gets its own file
+    vehicle_id: Option<String>,
+    upload: BufWriter,
+    encoder: crate::utils::export::ExportEncoder,
+    row_count: usize,
+    size_in_bytes: usize,
+}
+
+impl ExportPart {
+    fn try_new(
+        store: Arc<dyn ObjectStore>,
+        path: Path,
+        vehicle_id: Option<String>,
+        format: crate::types::ExportFormat,
+        schema: &Schema,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            upload: BufWriter::new(store, path.clone()),
+            path,
+            vehicle_id,
+            encoder: crate::utils::export::ExportEncoder::try_new(format, schema)?,
+            row_count: 0,
+            size_in_bytes: 0,
+        })
+    }
+
+    async fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<()> {
+        self.encoder.write(batch)?;
+        self.row_count += batch.num_rows();
+        let bytes = self.encoder.take_bytes();
+        if !bytes.is_empty() {
+            self.size_in_bytes += bytes.len();
+            self.upload.put(bytes.into()).await?;
+        }
+        Ok(())
+    }
+
+    async fn finish(self, folder: &str) -> anyhow::Result<crate::types::ExportedFile> {
+        let Self {
+            path,
+            vehicle_id: _,
+            mut upload,
+            encoder,
+            row_count,
+            mut size_in_bytes,
+        } = self;
+        let bytes = encoder.finish()?;
+        size_in_bytes += bytes.len();
+        upload.put(bytes.into()).await?;
+        upload.shutdown().await?;
+        Ok(crate::types::ExportedFile {
+            path: format!("{}/{}", folder, path),
+            size_in_bytes,
+            row_count,
+        })
+    }
+}
+
+fn export_progress(
+    export_id: &str,
+    files: &[crate::types::ExportedFile],
+    part: Option<&ExportPart>,
+    start_time: Instant,
+) -> crate::types::VehicleExportProgress {
+    crate::types::VehicleExportProgress {
+        msg_type: "vehicle-export-progress".to_string(),
+        export_id: export_id.to_string(),
+        file_count: files.len() + usize::from(part.is_some()),
+        row_count: files.iter().map(|file| file.row_count).sum::<usize>()
+            + part.map_or(0, |part| part.row_count),
+        written_bytes: files.iter().map(|file| file.size_in_bytes).sum::<usize>()
+            + part.map_or(0, |part| part.size_in_bytes),
+        elapsed_time_in_MS: start_time.elapsed().as_millis(),
+    }
+}
+
+async fn execute_export(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleExportRequest>,
+) -> anyhow::Result<crate::types::VehicleExportResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let max_rows_per_file = query
+        .max_rows_per_file
+        .unwrap_or(ctx.config.finder.exports.maxRowsPerFile);
+    let file_per_vehicle = query.file_per_vehicle.unwrap_or(false);
+    if max_rows_per_file == 0 {
+        anyhow::bail!("maxRowsPerFile must be greater than 0");
+    }
+    let limit = match query.limit {
+        Some(limit) => Some(usize::try_from(limit)?),
+        None => None,
+    };
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    let mut filter = crate::query::VehicleFilter::new(&query.from_date, &query.to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+    filter.min_speed = query.min_speed;
+    filter.max_speed = query.max_speed;
+    filter.directions = query.directions.clone();
+    filter.validate()?;
+
+    // Unlike the queries, all the filters are checked within the plan
+    let deduplicate = query.deduplicate.unwrap_or(false);
+    let partitions = filter.partitions();
+    let df = crate::query::build_vehicle_dataframe(
+        ctx.get_session().table("events").await?,
+        &filter,
+        partitions.as_ref(),
+        &crate::query::VehicleSelection {
+            check_rows: true,
+            sample_interval_in_secs: query.sample_interval_in_secs,
+            sample_method: query.sample_method.unwrap_or_default(),
+            cursor: None,
+            rank_duplicates: deduplicate,
+            order_by: Some(crate::types::VehicleQueryOrder::VehicleIdTimestamp),
+        },
+    )?;
+    let df = crate::query::plain_result_columns(df)?;
+    let result_columns: Vec<usize> = (0..crate::query::RESULT_COLUMN_COUNT).collect();
+    let schema = df.schema().as_arrow().project(&result_columns)?;
+
+    let store = crate::handlers::search::build_export_store(&ctx.config)?;
+    let folder = &ctx.config.finder.exports.folder;
+    let extension = crate::utils::export::file_extension(query.format);
+
+    let start_time = Instant::now();
+    let mut last_progress_time = start_time;
+    let mut row_count: usize = 0;
+    let mut duplicate_count: usize = 0;
+    let mut limit_reached = false;
+    let mut has_timed_out = false;
+    let mut files: Vec<crate::types::ExportedFile> = vec![];
+    let mut part: Option<ExportPart> = None;
+
+    let mut stream = df.execute_stream().await?;
+    while let Some(batch_result) = stream.next().await {
+        let mut batch = batch_result?;
+        if deduplicate {
+            let removed_count;
+            (batch, removed_count) = crate::handlers::search::remove_duplicates(&batch, crate::query::RESULT_COLUMN_COUNT)?;
+            duplicate_count += removed_count;
+        }
+        let mut batch = batch.project(&result_columns)?;
+        if let Some(limit) = limit
+            && row_count + batch.num_rows() > limit
+        {
+            batch = batch.slice(0, limit - row_count);
+            limit_reached = true;
+        }
+        row_count += batch.num_rows();
+        // The rows are split into several files when they do not fit in one,
+        // or when each vehicle gets its own file
+        while batch.num_rows() > 0 {
+            let vehicle_ids = as_string_array(batch.column(1))?;
+            let vehicle_id = file_per_vehicle.then(|| vehicle_ids.value(0).to_string());
+            let count = match &vehicle_id {
+                Some(vehicle_id) => (1..batch.num_rows())
+                    .find(|i| vehicle_ids.value(*i) != vehicle_id)
+                    .unwrap_or(batch.num_rows()),
+                None => batch.num_rows(),
+            };
+            if let Some(current) = &part
+                && (current.vehicle_id != vehicle_id
+                    || (vehicle_id.is_none() && current.row_count >= max_rows_per_file))
+                && let Some(full) = part.take()
+            {
+                files.push(full.finish(folder).await?);
+                let progress = export_progress(&query.id, &files, None, start_time);
+                crate::utils::messaging::publish_message(
+                    &ctx.parent.nats_client,
+                    req.reply_to.clone(),
+                    &progress,
+                )
+                .await?;
+                last_progress_time = Instant::now();
+            }
+            let file_name = match &vehicle_id {
+                Some(vehicle_id) => format!("{}.{}", vehicle_id, extension),
+                None => format!("part-{:05}.{}", files.len(), extension),
+            };
+            let current = match part.as_mut() {
+                Some(current) => current,
+                None => part.insert(ExportPart::try_new(
+                    store.clone(),
+                    Path::from_iter([query.id.clone(), file_name]),
+                    vehicle_id,
+                    query.format,
+                    &schema,
+                )?),
+            };
+            let count = match current.vehicle_id {
+                Some(_) => count,
+                None => count.min(max_rows_per_file - current.row_count),
+            };
+            current.write(&batch.slice(0, count)).await?;
+            batch = batch.slice(count, batch.num_rows() - count);
+        }
+        if last_progress_time.elapsed().as_millis() >= PROGRESS_INTERVAL_IN_MS {
+            let progress = export_progress(&query.id, &files, part.as_ref(), start_time);
+            crate::utils::messaging::publish_message(
+                &ctx.parent.nats_client,
+                req.reply_to.clone(),
+                &progress,
+            )
+            .await?;
+            last_progress_time = Instant::now();
+        }
+        if start_time.elapsed().as_millis() >= query_timeout {
+            has_timed_out = true;
+        }
+        if limit_reached || has_timed_out {
+            break;
+        }
+    }
+    if let Some(last) = part.take() {
+        files.push(last.finish(folder).await?);
+    }
+    let duration = start_time.elapsed();
+    log::info!(
+        "Exported {} rows in {} files for export {}",
+        row_count,
+        files.len(),
+        query.id
+    );
+
+    Ok(crate::types::VehicleExportResponse {
+        msg_type: "vehicle-export-response".to_string(),
+        files,
+        row_count,
+        duplicate_count,
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+        limit_reached,
+    })
+}
+
+async fn process_export_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleExportRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_export(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle export: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_export_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.export".to_string(),
+        process_export_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+
+    fn event<'a>(vehicle_id: &'a str, timestamp: &'a str) -> TestEvent<'a> {
+        TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp,
+            lon: 4.35,
+            lat: 50.85,
+        }
+    }
+
+    /// Runs the export over the given events, into a temporary folder of the local file system.
+    async fn export(
+        events: &[TestEvent<'_>],
+        request: serde_json::Value,
+    ) -> anyhow::Result<(crate::types::VehicleExportResponse, std::path::PathBuf)> {
+        let data_folder =
+            std::env::temp_dir().join(format!("event-finder-{}", uuid::Uuid::new_v4()));
+        let mut config = crate::contexts::tests::test_config()?;
+        config.collector.output.storage = crate::config::StorageConfig::FileStorageConfig {
+            folder: data_folder.to_string_lossy().to_string(),
+        };
+        let export_folder = data_folder.join(&config.finder.exports.folder);
+        let ctx = crate::contexts::tests::data_handler_context(
+            config,
+            crate::query::tests::events_session(events)?,
+        )
+        .await?;
+        let req = serde_json::from_value(serde_json::json!({
+            "id": "request-1",
+            "type": "request",
+            "replyTo": "test.export",
+            "body": request,
+        }))?;
+        Ok((execute_export(&ctx, &req).await?, export_folder))
+    }
+
+    #[tokio::test]
+    async fn splits_the_export_into_files_of_max_rows_per_file() -> anyhow::Result<()> {
+        let (resp, export_folder) = export(
+            &[
+                event("b", "2024-01-01T10:00:00Z"),
+                event("a", "2024-01-01T10:00:01Z"),
+                event("a", "2024-01-01T10:00:00Z"),
+                event("c", "2024-01-01T12:00:00Z"),
+            ],
+            serde_json::json!({
+                "type": "vehicle-export-request",
+                "id": "export-1",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "vehicleIds": ["a", "b", "c"],
+                "format": "geojson",
+                "maxRowsPerFile": 2,
+            }),
+        )
+        .await?;
+        let read_features = |name: &str| -> anyhow::Result<Vec<String>> {
+            let content = std::fs::read_to_string(export_folder.join("export-1").join(name))?;
+            let collection = content.parse::<geojson::FeatureCollection>()?;
+            Ok(collection
+                .features
+                .iter()
+                .filter_map(|feature| feature.property("vehicleId")?.as_str())
+                .map(|vehicle_id| vehicle_id.to_string())
+                .collect())
+        };
+        let first = read_features("part-00000.geojson");
+        let second = read_features("part-00001.geojson");
+        std::fs::remove_dir_all(export_folder.parent().unwrap())?;
+
+        assert_eq!(resp.row_count, 3);
+        assert_eq!(
+            resp.files
+                .iter()
+                .map(|file| (file.path.as_str(), file.row_count))
+                .collect::<Vec<_>>(),
+            vec![
+                ("exports/export-1/part-00000.geojson", 2),
+                ("exports/export-1/part-00001.geojson", 1)
+            ]
+        );
+        assert_eq!(first?, vec!["a", "a"]);
+        assert_eq!(second?, vec!["b"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn writes_one_parquet_file_per_vehicle() -> anyhow::Result<()> {
+        let (resp, export_folder) = export(
+            &[
+                event("b", "2024-01-01T10:00:00Z"),
+                event("a", "2024-01-01T10:00:01Z"),
+                event("a", "2024-01-01T10:00:00Z"),
+                event("a", "2024-01-01T10:00:00Z"),
+            ],
+            serde_json::json!({
+                "type": "vehicle-export-request",
+                "id": "export-2",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "vehicleIds": ["a", "b"],
+                "format": "parquet",
+                "filePerVehicle": true,
+                "deduplicate": true,
+            }),
+        )
+        .await?;
+        let session = SessionContext::new();
+        let count_rows = async |name: &str| -> anyhow::Result<usize> {
+            let path = export_folder.join("export-2").join(name);
+            let df = session
+                .read_parquet(path.to_string_lossy().to_string(), Default::default())
+                .await?;
+            Ok(df.count().await?)
+        };
+        let a_count = count_rows("a.parquet").await;
+        let b_count = count_rows("b.parquet").await;
+        std::fs::remove_dir_all(export_folder.parent().unwrap())?;
+
+        assert_eq!(resp.row_count, 3);
+        assert_eq!(resp.duplicate_count, 1);
+        assert_eq!(resp.files.len(), 2);
+        assert_eq!(a_count?, 2);
+        assert_eq!(b_count?, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,768 @@
+// tonic::Status is the error of every Flight SQL call
+#![allow(clippy::result_large_err)]
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::sql::metadata::{SqlInfoData, SqlInfoDataBuilder};
+use arrow_flight::sql::server::{FlightSqlService, PeekableFlightDataStream};
+use arrow_flight::sql::{
+    ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest,
+    ActionCreatePreparedStatementResult, Any, CommandGetCatalogs, CommandGetDbSchemas,
+    CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables, CommandPreparedStatementQuery,
+    CommandStatementQuery, DoPutPreparedStatementResult, ProstMessageExt, SqlInfo,
+    TicketStatementQuery,
+};
+use arrow_flight::{
+    Action, FlightDescriptor, FlightEndpoint, FlightInfo, HandshakeRequest, HandshakeResponse,
+    IpcMessage, SchemaAsIpc, Ticket,
+};
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::ipc::writer::IpcWriteOptions;
+use datafusion::common::{ParamValues, ScalarValue};
+use datafusion::datasource::TableType;
+use datafusion::execution::context::SQLOptions;
+use datafusion::prelude::*;
+use flight_prost::Message;
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use log;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tonic::transport::Server;
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+
+static SQL_INFO: LazyLock<SqlInfoData> = LazyLock::new(|| {
+    let mut builder = SqlInfoDataBuilder::new();
+    builder.append(SqlInfo::FlightSqlServerName, "event-finder");
+    builder.append(SqlInfo::FlightSqlServerVersion, env!("CARGO_PKG_VERSION"));
+    // the version of the Arrow format
+    builder.append(SqlInfo::FlightSqlServerArrowVersion, "1.3");
+    builder.append(SqlInfo::FlightSqlServerReadOnly, true);
+    builder.append(SqlInfo::SqlIdentifierQuoteChar, "\"");
+    builder.build().unwrap()
+});
+
+type DoGetStream = Pin<Box<dyn Stream<Item = Result<arrow_flight::FlightData, Status>> + Send>>;
+
+/// A statement prepared by a client, with the parameters it bound last.
+#[derive(Clone)]
+struct PreparedStatement {
+    sql: String,
+    parameters: Option<ParamValues>,
+    last_used_at: Instant,
+}
+
+/// The statements prepared by the clients. The clients do not always close them, so the ones unused
+/// for longer than the TTL are dropped, as well as the least recently used ones beyond the capacity.
+struct PreparedStatements {
+    ttl: Duration,
+    capacity: usize,
+    statements: HashMap<String, PreparedStatement>,
+}
+
+impl PreparedStatements {
+    fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            statements: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, handle: String, statement: PreparedStatement) {
+        let ttl = self.ttl;
+        self.statements
+            .retain(|_, statement| statement.last_used_at.elapsed() < ttl);
+        while self.statements.len() >= self.capacity.max(1) {
+            let Some(oldest) = self
+                .statements
+                .iter()
+                .min_by_key(|(_, statement)| statement.last_used_at)
+                .map(|(handle, _)| handle.clone())
+            else {
+                break;
+            };
+            self.statements.remove(&oldest);
+        }
+        self.statements.insert(handle, statement);
+    }
+
+    fn get_mut(&mut self, handle: &str) -> Option<&mut PreparedStatement> {
+        if self
+            .statements
+            .get(handle)
+            .is_some_and(|statement| statement.last_used_at.elapsed() >= self.ttl)
+        {
+            self.statements.remove(handle);
+        }
+        let statement = self.statements.get_mut(handle)?;
+        statement.last_used_at = Instant::now();
+        Some(statement)
+    }
+
+    fn remove(&mut self, handle: &str) {
+        self.statements.remove(handle);
+    }
+}
+
+/// Runs the read-only SQL queries of the Flight SQL clients (DBeaver, ADBC, pandas...) on the session
+/// of the finder, with the `events` table, its partition columns and the spatial UDFs.
+pub struct FinderFlightSqlService {
+    ctx: crate::contexts::DataHandlerContext,
+    query_timeout: Duration,
+    max_rows: usize,
+    prepared_statements: Mutex<PreparedStatements>,
+}
+
+impl FinderFlightSqlService {
+    pub fn new(ctx: crate::contexts::DataHandlerContext) -> Self {
+        let config = &ctx.config.finder.flightSql;
+        Self {
+            query_timeout: Duration::from_millis(config.queryTimeoutInMS),
+            max_rows: config.maxRows,
+            prepared_statements: Mutex::new(PreparedStatements::new(
+                Duration::from_secs(config.preparedStatementTtlInMin * 60),
+                config.maxPreparedStatements,
+            )),
+            ctx,
+        }
+    }
+
+    /// Plans a query, rejecting the statements that would modify the session.
+    async fn plan(&self, sql: &str, parameters: Option<ParamValues>) -> Result<DataFrame, Status> {
+        log::info!("Planning Flight SQL query: {}", sql);
+        let options = SQLOptions::new()
+            .with_allow_ddl(false)
+            .with_allow_dml(false)
+            .with_allow_statements(false);
+        let session = self.ctx.get_session();
+        let df = tokio::time::timeout(self.query_timeout, session.sql_with_options(sql, options))
+            .await
+            .map_err(|_| Status::deadline_exceeded("The query took too long to plan"))?
+            .map_err(invalid_argument)?;
+        let df = match parameters {
+            Some(parameters) => df.with_param_values(parameters).map_err(invalid_argument)?,
+            None => df,
+        };
+        crate::query::with_plain_strings(df).map_err(invalid_argument)
+    }
+
+    /// Streams the results of a query, until the timeout or the row limit is reached.
+    async fn execute(&self, df: DataFrame) -> Result<Response<DoGetStream>, Status> {
+        let schema: SchemaRef = Arc::new(df.schema().as_arrow().clone());
+        let max_rows = self.max_rows;
+        let deadline = tokio::time::Instant::now() + self.query_timeout;
+        // one more row than allowed, to detect the queries exceeding the limit without scanning everything
+        let df = df.limit(0, Some(max_rows + 1)).map_err(internal)?;
+        let stream = tokio::time::timeout_at(deadline, df.execute_stream())
+            .await
+            .map_err(|_| Status::deadline_exceeded("The query timed out"))?
+            .map_err(internal)?;
+        let batches = futures_util::stream::unfold(Some((stream, 0)), move |state| async move {
+            let (mut stream, row_count) = state?;
+            match tokio::time::timeout_at(deadline, stream.next()).await {
+                Err(_) => Some((
+                    Err(Status::deadline_exceeded("The query timed out").into()),
+                    None,
+                )),
+                Ok(None) => None,
+                Ok(Some(Err(e))) => Some((Err(FlightError::ExternalError(Box::new(e))), None)),
+                Ok(Some(Ok(batch))) => {
+                    let row_count = row_count + batch.num_rows();
+                    if row_count > max_rows {
+                        let message = format!(
+                            "The query returns more than {} rows, add a LIMIT clause or a narrower filter",
+                            max_rows
+                        );
+                        Some((Err(Status::resource_exhausted(message).into()), None))
+                    } else {
+                        Some((Ok(batch), Some((stream, row_count))))
+                    }
+                }
+            }
+        });
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(batches)
+            .map_err(Status::from);
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    fn get_prepared_statement(&self, handle: &[u8]) -> Result<PreparedStatement, Status> {
+        let handle = std::str::from_utf8(handle).map_err(invalid_argument)?;
+        self.prepared_statements
+            .lock()
+            .unwrap()
+            .get_mut(handle)
+            .map(|statement| statement.clone())
+            .ok_or_else(|| Status::not_found(format!("Unknown prepared statement {}", handle)))
+    }
+}
+
+#[tonic::async_trait]
+impl FlightSqlService for FinderFlightSqlService {
+    type FlightService = FinderFlightSqlService;
+
+    /// The clients are not authenticated, but some of them always start with a handshake.
+    async fn do_handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<
+        Response<Pin<Box<dyn Stream<Item = Result<HandshakeResponse, Status>> + Send>>>,
+        Status,
+    > {
+        let response = HandshakeResponse {
+            protocol_version: 0,
+            payload: Default::default(),
+        };
+        Ok(Response::new(Box::pin(futures_util::stream::once(
+            async move { Ok(response) },
+        ))))
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let df = self.plan(&query.query, None).await?;
+        // the query is executed again by DoGet, possibly on another connection
+        let ticket = TicketStatementQuery {
+            statement_handle: query.query.into(),
+        };
+        flight_info(
+            df.schema().as_arrow(),
+            ticket.as_any(),
+            request.into_inner(),
+        )
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: TicketStatementQuery,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let sql = std::str::from_utf8(&ticket.statement_handle).map_err(invalid_argument)?;
+        let df = self.plan(sql, None).await?;
+        self.execute(df).await
+    }
+
+    async fn do_action_create_prepared_statement(
+        &self,
+        query: ActionCreatePreparedStatementRequest,
+        _request: Request<Action>,
+    ) -> Result<ActionCreatePreparedStatementResult, Status> {
+        let df = self.plan(&query.query, None).await?;
+        let mut parameters = df
+            .logical_plan()
+            .get_parameter_types()
+            .map_err(invalid_argument)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        // $1, $2... in their order of binding
+        parameters.sort_by_key(|(id, _)| id[1..].parse::<usize>().unwrap_or(usize::MAX));
+        let parameter_schema = Schema::new(
+            parameters
+                .into_iter()
+                .map(|(id, data_type)| {
+                    let data_type = data_type.unwrap_or(DataType::Null);
+                    Field::new(
+                        id,
+                        crate::query::plain_type(&data_type).unwrap_or(data_type),
+                        true,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        let handle = Uuid::new_v4().to_string();
+        self.prepared_statements.lock().unwrap().insert(
+            handle.clone(),
+            PreparedStatement {
+                sql: query.query,
+                parameters: None,
+                last_used_at: Instant::now(),
+            },
+        );
+        Ok(ActionCreatePreparedStatementResult {
+            prepared_statement_handle: handle.into(),
+            dataset_schema: encode_schema(df.schema().as_arrow())?.0,
+            parameter_schema: encode_schema(&parameter_schema)?.0,
+        })
+    }
+
+    async fn do_put_prepared_statement_query(
+        &self,
+        query: CommandPreparedStatementQuery,
+        request: Request<PeekableFlightDataStream>,
+    ) -> Result<DoPutPreparedStatementResult, Status> {
+        let handle = std::str::from_utf8(&query.prepared_statement_handle)
+            .map_err(invalid_argument)?
+            .to_string();
+        let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(
+            request.into_inner().map_err(FlightError::from),
+        )
+        .try_collect()
+        .await
+        .map_err(Status::from)?;
+        let parameters = match batches.iter().map(RecordBatch::num_rows).sum::<usize>() {
+            0 => None,
+            1 => {
+                let batch = batches.iter().find(|batch| batch.num_rows() == 1).unwrap();
+                let values = batch
+                    .columns()
+                    .iter()
+                    .map(|column| ScalarValue::try_from_array(column, 0))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(invalid_argument)?;
+                Some(ParamValues::List(values))
+            }
+            _ => {
+                return Err(Status::invalid_argument(
+                    "Only a single set of parameters can be bound",
+                ));
+            }
+        };
+        match self.prepared_statements.lock().unwrap().get_mut(&handle) {
+            Some(statement) => statement.parameters = parameters,
+            None => {
+                return Err(Status::not_found(format!(
+                    "Unknown prepared statement {}",
+                    handle
+                )));
+            }
+        }
+        Ok(DoPutPreparedStatementResult {
+            prepared_statement_handle: Some(handle.into()),
+        })
+    }
+
+    async fn get_flight_info_prepared_statement(
+        &self,
+        query: CommandPreparedStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let statement = self.get_prepared_statement(&query.prepared_statement_handle)?;
+        let df = self.plan(&statement.sql, statement.parameters).await?;
+        flight_info(df.schema().as_arrow(), query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_prepared_statement(
+        &self,
+        query: CommandPreparedStatementQuery,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let statement = self.get_prepared_statement(&query.prepared_statement_handle)?;
+        let df = self.plan(&statement.sql, statement.parameters).await?;
+        self.execute(df).await
+    }
+
+    async fn do_action_close_prepared_statement(
+        &self,
+        query: ActionClosePreparedStatementRequest,
+        _request: Request<Action>,
+    ) -> Result<(), Status> {
+        let handle =
+            std::str::from_utf8(&query.prepared_statement_handle).map_err(invalid_argument)?;
+        self.prepared_statements.lock().unwrap().remove(handle);
+        Ok(())
+    }
+
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.into_builder().schema();
+        flight_info(&schema, query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let mut builder = query.into_builder();
+        for catalog_name in self.ctx.get_session().catalog_names() {
+            builder.append(catalog_name);
+        }
+        Ok(single_batch(builder.schema(), builder.build()))
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+        flight_info(&schema, query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let session = self.ctx.get_session();
+        let mut builder = query.into_builder();
+        for catalog_name in session.catalog_names() {
+            if let Some(catalog) = session.catalog(&catalog_name) {
+                for schema_name in catalog.schema_names() {
+                    builder.append(&catalog_name, schema_name);
+                }
+            }
+        }
+        Ok(single_batch(builder.schema(), builder.build()))
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+        flight_info(&schema, query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_tables(
+        &self,
+        query: CommandGetTables,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let session = self.ctx.get_session();
+        let mut builder = query.into_builder();
+        for catalog_name in session.catalog_names() {
+            let Some(catalog) = session.catalog(&catalog_name) else {
+                continue;
+            };
+            for schema_name in catalog.schema_names() {
+                let Some(schema) = catalog.schema(&schema_name) else {
+                    continue;
+                };
+                for table_name in schema.table_names() {
+                    let Some(table) = schema.table(&table_name).await.map_err(internal)? else {
+                        continue;
+                    };
+                    builder
+                        .append(
+                            &catalog_name,
+                            &schema_name,
+                            table_name,
+                            table_type_name(table.table_type()),
+                            &table.schema(),
+                        )
+                        .map_err(internal)?;
+                }
+            }
+        }
+        Ok(single_batch(builder.schema(), builder.build()))
+    }
+
+    async fn get_flight_info_table_types(
+        &self,
+        query: CommandGetTableTypes,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.into_builder().schema();
+        flight_info(&schema, query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_table_types(
+        &self,
+        query: CommandGetTableTypes,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let mut builder = query.into_builder();
+        for table_type in [TableType::Base, TableType::View, TableType::Temporary] {
+            builder.append(table_type_name(table_type));
+        }
+        Ok(single_batch(builder.schema(), builder.build()))
+    }
+
+    async fn get_flight_info_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder(&SQL_INFO).schema();
+        flight_info(&schema, query.as_any(), request.into_inner())
+    }
+
+    async fn do_get_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let builder = query.into_builder(&SQL_INFO);
+        Ok(single_batch(builder.schema(), builder.build()))
+    }
+
+    async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
+}
+
+/// Describes the single endpoint of a query, served by this instance.
+fn flight_info(
+    schema: &Schema,
+    ticket: Any,
+    descriptor: FlightDescriptor,
+) -> Result<Response<FlightInfo>, Status> {
+    let endpoint = FlightEndpoint::new().with_ticket(Ticket::new(ticket.encode_to_vec()));
+    let info = FlightInfo::new()
+        .try_with_schema(schema)
+        .map_err(internal)?
+        .with_endpoint(endpoint)
+        .with_descriptor(descriptor);
+    Ok(Response::new(info))
+}
+
+fn single_batch(
+    schema: SchemaRef,
+    batch: Result<RecordBatch, FlightError>,
+) -> Response<DoGetStream> {
+    let stream = FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .build(futures_util::stream::once(async move { batch }))
+        .map_err(Status::from);
+    Response::new(Box::pin(stream))
+}
+
+fn encode_schema(schema: &Schema) -> Result<IpcMessage, Status> {
+    SchemaAsIpc::new(schema, &IpcWriteOptions::default())
+        .try_into()
+        .map_err(internal)
+}
+
+fn table_type_name(table_type: TableType) -> &'static str {
+    match table_type {
+        TableType::Base => "TABLE",
+        TableType::View => "VIEW",
+        TableType::Temporary => "LOCAL TEMPORARY",
+    }
+}
+
+fn invalid_argument(e: impl std::fmt::Display) -> Status {
+    Status::invalid_argument(e.to_string())
+}
+
+fn internal(e: impl std::fmt::Display) -> Status {
+    Status::internal(e.to_string())
+}
+
+/// Starts the Flight SQL server in the background, next to the web server.
+pub fn start_flight_sql_server(ctx: crate::contexts::DataHandlerContext) -> anyhow::Result<()> {
+    let port: u16 = std::env::var("FLIGHT_SQL_PORT")
+        .unwrap_or(ctx.config.finder.flightSql.port.to_string())
+        .parse()?;
+    let service = FlightServiceServer::new(FinderFlightSqlService::new(ctx));
+    log::info!("Flight SQL listening on: grpc://127.0.0.1:{}", port);
+    tokio::spawn(async move {
+        let address = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+        if let Err(e) = Server::builder().add_service(service).serve(address).await {
+            log::error!("Flight SQL server stopped: {}", e);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+    use arrow_flight::sql::client::FlightSqlServiceClient;
+    use datafusion::arrow::array::StringArray;
+    use datafusion::common::cast::{as_int64_array, as_string_array};
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::{Channel, Endpoint};
+
+    fn statement(sql: &str) -> PreparedStatement {
+        PreparedStatement {
+            sql: sql.to_string(),
+            parameters: None,
+            last_used_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn drops_the_least_recently_used_statements_beyond_the_capacity() {
+        let mut statements = PreparedStatements::new(Duration::from_secs(60), 2);
+        statements.insert("a".to_string(), statement("SELECT 1"));
+        statements.insert("b".to_string(), statement("SELECT 2"));
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(statements.get_mut("a").is_some());
+
+        statements.insert("c".to_string(), statement("SELECT 3"));
+
+        assert!(statements.get_mut("a").is_some());
+        assert!(statements.get_mut("b").is_none());
+        assert!(statements.get_mut("c").is_some());
+    }
+
+    #[test]
+    fn drops_the_expired_statements() {
+        let mut statements = PreparedStatements::new(Duration::from_millis(1), 10);
+        statements.insert("a".to_string(), statement("SELECT 1"));
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert!(statements.get_mut("a").is_none());
+    }
+
+    fn event(vehicle_id: &'static str, timestamp: &'static str) -> TestEvent<'static> {
+        TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp,
+            lon: 4.35,
+            lat: 50.85,
+        }
+    }
+
+    /// Starts the server on an ephemeral port, over an in-memory `events` table.
+    async fn connect(max_rows: usize) -> anyhow::Result<FlightSqlServiceClient<Channel>> {
+        let mut config = crate::contexts::tests::test_config()?;
+        config.finder.flightSql.maxRows = max_rows;
+        let session = crate::query::tests::events_session(&[
+            event("a", "2024-01-01T10:00:00Z"),
+            event("a", "2024-01-01T10:00:01Z"),
+            event("b", "2024-01-01T10:00:00Z"),
+        ])?;
+        let ctx = crate::contexts::tests::data_handler_context(config, session).await?;
+        let incoming = TcpIncoming::bind(std::net::SocketAddr::from(([127, 0, 0, 1], 0)))?;
+        let address = incoming.local_addr()?;
+        tokio::spawn(
+            Server::builder()
+                .add_service(FlightServiceServer::new(FinderFlightSqlService::new(ctx)))
+                .serve_with_incoming(incoming),
+        );
+        let channel = Endpoint::from_shared(format!("http://{}", address))?
+            .connect()
+            .await?;
+        Ok(FlightSqlServiceClient::new(channel))
+    }
+
+    /// Fetches the results of all the endpoints, like the clients do with DoGet.
+    async fn fetch(
+        client: &mut FlightSqlServiceClient<Channel>,
+        info: FlightInfo,
+    ) -> anyhow::Result<Vec<RecordBatch>> {
+        let mut batches = vec![];
+        for endpoint in info.endpoint {
+            let ticket = endpoint
+                .ticket
+                .ok_or_else(|| anyhow::format_err!("Missing ticket"))?;
+            let stream = client.do_get(ticket).await?;
+            batches.extend(stream.try_collect::<Vec<_>>().await?);
+        }
+        Ok(batches)
+    }
+
+    #[tokio::test]
+    async fn runs_the_statements_with_plain_strings() -> anyhow::Result<()> {
+        let mut client = connect(100).await?;
+
+        let info = client
+            .execute(
+                r#"SELECT "vehicleId", count(*) AS n FROM events GROUP BY 1 ORDER BY 1"#
+                    .to_string(),
+                None,
+            )
+            .await?;
+        let batches = fetch(&mut client, info).await?;
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0].schema().field(0).data_type(),
+            &DataType::Utf8,
+            "the string views are cast for the clients"
+        );
+        assert_eq!(
+            as_string_array(batches[0].column(0))?,
+            &StringArray::from(vec!["a", "b"])
+        );
+        assert_eq!(as_int64_array(batches[0].column(1))?.values(), &[2, 1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn binds_the_parameters_of_the_prepared_statements() -> anyhow::Result<()> {
+        let mut client = connect(100).await?;
+
+        let mut statement = client
+            .prepare(
+                r#"SELECT count(*) AS n FROM events WHERE "vehicleId" = $1"#.to_string(),
+                None,
+            )
+            .await?;
+        let parameter_schema = Arc::new(statement.parameter_schema()?.clone());
+        assert_eq!(parameter_schema.field(0).name(), "$1");
+        assert_eq!(parameter_schema.field(0).data_type(), &DataType::Utf8);
+        statement.set_parameters(RecordBatch::try_new(
+            parameter_schema,
+            vec![Arc::new(StringArray::from(vec!["a"]))],
+        )?)?;
+        let info = statement.execute().await?;
+        let batches = fetch(&mut client, info).await?;
+
+        assert_eq!(as_int64_array(batches[0].column(0))?.values(), &[2]);
+        statement.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lists_the_events_table() -> anyhow::Result<()> {
+        let mut client = connect(100).await?;
+
+        let info = client
+            .get_tables(CommandGetTables {
+                catalog: None,
+                db_schema_filter_pattern: None,
+                table_name_filter_pattern: Some("event%".to_string()),
+                table_types: vec![],
+                include_schema: false,
+            })
+            .await?;
+        let batches = fetch(&mut client, info).await?;
+
+        let table_names = batches
+            .iter()
+            .map(|batch| -> anyhow::Result<Vec<String>> {
+                let column = batch.column(batch.schema().index_of("table_name")?);
+                Ok(as_string_array(column)?
+                    .iter()
+                    .flatten()
+                    .map(|name| name.to_string())
+                    .collect())
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .concat();
+        assert_eq!(table_names, vec!["events"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_the_queries_returning_more_than_max_rows() -> anyhow::Result<()> {
+        let mut client = connect(2).await?;
+
+        let info = client
+            .execute("SELECT * FROM events".to_string(), None)
+            .await?;
+        let error = fetch(&mut client, info).await.unwrap_err();
+        assert!(
+            error.to_string().contains("more than 2 rows"),
+            "unexpected error: {}",
+            error
+        );
+
+        let info = client
+            .execute("SELECT * FROM events LIMIT 2".to_string(), None)
+            .await?;
+        let batches = fetch(&mut client, info).await?;
+        assert_eq!(
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+            2
+        );
+        Ok(())
+    }
+}
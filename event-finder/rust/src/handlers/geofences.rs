@@ -0,0 +1,164 @@
+use crate::geofences::Geofence;
+use chrono::{Duration, Utc};
+use futures_util::StreamExt;
+use log;
+
+// A single finder instance owns each geofence, as the live positions are received by all the instances.
+const SUBSCRIBE_QUEUE_GROUP: &str = "finder-geofences";
+const EXPIRATION_CHECK_INTERVAL_IN_SECS: u64 = 1;
+
+async fn process_move(
+    ctx: &crate::contexts::DataHandlerContext,
+    geofences: &crate::geofences::GeofenceRegistry,
+    msg: &async_nats::Message,
+) -> anyhow::Result<()> {
+    let Some(position) = crate::utils::moves::decode_position(msg)? else {
+        return anyhow::Ok(());
+    };
+    for notification in geofences.update(&position)? {
+        crate::utils::messaging::publish_message(
+            &ctx.parent.nats_client,
+            notification.reply_to,
+            &notification.event,
+        )
+        .await?;
+    }
+    anyhow::Ok(())
+}
+
+fn execute_subscribe(
+    ctx: &crate::contexts::DataHandlerContext,
+    geofences: &crate::geofences::GeofenceRegistry,
+    req: &crate::types::Request<crate::types::GeofenceSubscribeRequest>,
+) -> anyhow::Result<crate::types::GeofenceSubscribeResponse> {
+    let query = &req.body;
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?
+    .ok_or_else(|| anyhow::format_err!("A geometry, circle or corridor is required"))?;
+    let config = &ctx.config.finder.geofences;
+    let lease_in_secs = query.lease_in_secs.unwrap_or(config.defaultLeaseInSecs);
+    if lease_in_secs == 0 || lease_in_secs > config.maxLeaseInSecs {
+        anyhow::bail!(
+            "leaseInSecs must be between 1 and {}",
+            config.maxLeaseInSecs
+        );
+    }
+    let expires_at = Utc::now() + Duration::seconds(lease_in_secs.try_into()?);
+    geofences.insert(Geofence::new(
+        query.id.clone(),
+        req.reply_to.clone(),
+        area,
+        query.vehicle_types.clone(),
+        query.vehicle_ids.clone(),
+        expires_at,
+        config.maxCells,
+    )?);
+    Ok(crate::types::GeofenceSubscribeResponse {
+        msg_type: "geofence-subscribe-response".to_string(),
+        subscription_id: query.id.clone(),
+        expires_at: expires_at.to_rfc3339(),
+    })
+}
+
+async fn process_subscribe_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::GeofenceSubscribeRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let Some(geofences) = ctx.geofences.clone() else {
+        return anyhow::Ok(());
+    };
+    let result = execute_subscribe(&ctx, &geofences, &req);
+    if let Err(e) = &result {
+        log::error!("Error executing geofence subscription: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+async fn process_unsubscribe_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::GeofenceUnsubscribeRequest>,
+) -> anyhow::Result<()> {
+    let Some(geofences) = ctx.geofences.clone() else {
+        return anyhow::Ok(());
+    };
+    // Only the instance owning the geofence replies
+    if geofences.remove(&req.body.subscription_id).is_none() {
+        return anyhow::Ok(());
+    }
+    log::info!("Removed geofence {}", req.body.subscription_id);
+    let resp = crate::utils::messaging::create_response(
+        &req.id,
+        Ok(crate::types::GeofenceUnsubscribeResponse {
+            msg_type: "geofence-unsubscribe-response".to_string(),
+            subscription_id: req.body.subscription_id.clone(),
+        }),
+    );
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_geofence_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let Some(geofences) = ctx.geofences.clone() else {
+        return anyhow::Ok(());
+    };
+    let subject = crate::utils::moves::move_subject(&ctx.config.finder.geofences.source)?;
+
+    let _ = crate::utils::messaging::queue_message_loop(
+        ctx.clone(),
+        "requests.vehicles.geofences.subscribe".to_string(),
+        SUBSCRIBE_QUEUE_GROUP.to_string(),
+        process_subscribe_request,
+    );
+    let _ = crate::utils::messaging::message_loop(
+        ctx.clone(),
+        "requests.vehicles.geofences.unsubscribe".to_string(),
+        process_unsubscribe_request,
+    );
+
+    let move_ctx = ctx.clone();
+    let move_geofences = geofences.clone();
+    tokio::task::spawn(async move {
+        let mut sub = move_ctx.parent.nats_client.subscribe(subject).await?;
+        while let Some(msg) = sub.next().await {
+            if let Err(e) = process_move(&move_ctx, &move_geofences, &msg).await {
+                log::error!("Error processing move command: {}", e);
+            }
+        }
+        anyhow::Ok(())
+    });
+
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            EXPIRATION_CHECK_INTERVAL_IN_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            for fence in geofences.remove_expired(Utc::now()) {
+                log::info!("Geofence {} expired", fence.subscription_id);
+                let expired = crate::types::GeofenceExpired {
+                    msg_type: "geofence-expired".to_string(),
+                    subscription_id: fence.subscription_id,
+                };
+                if let Err(e) = crate::utils::messaging::publish_message(
+                    &ctx.parent.nats_client,
+                    fence.reply_to,
+                    &expired,
+                )
+                .await
+                {
+                    log::error!("Error publishing geofence expiration: {}", e);
+                }
+            }
+        }
+    });
+    anyhow::Ok(())
+}
@@ -0,0 +1,79 @@
+use futures_util::StreamExt;
+use log;
+use std::collections::HashSet;
+
+const RETENTION_CHECK_INTERVAL_IN_SECS: u64 = 60;
+
+async fn process_aggregate_period_event(
+    ctx: crate::contexts::DataHandlerContext,
+    event: crate::types::AggregatePeriodCreated,
+) -> anyhow::Result<()> {
+    // The moves received after the flush notification were not part of the flush
+    let flushed_at = std::time::Instant::now();
+    let Some(hot_tier) = ctx.hot_tier.clone() else {
+        return anyhow::Ok(());
+    };
+    let partition_keys: HashSet<String> = event
+        .partitions
+        .iter()
+        .map(|partition| partition.partition_key.clone())
+        .collect();
+    let count = hot_tier.evict_flushed(
+        crate::utils::time::parse_datetime(&event.from_time)?,
+        crate::utils::time::parse_datetime(&event.to_time)?,
+        &partition_keys,
+        flushed_at,
+    );
+    log::debug!(
+        "Evicted {} recent events flushed in period {}",
+        count,
+        event.partition_key
+    );
+    anyhow::Ok(())
+}
+
+pub fn subscribe_to_recent_events(ctx: crate::contexts::DataHandlerContext) -> anyhow::Result<()> {
+    let Some(hot_tier) = ctx.hot_tier.clone() else {
+        return anyhow::Ok(());
+    };
+    let subject = crate::utils::moves::move_subject(&ctx.config.finder.hotTier.source)?;
+
+    let _ = crate::utils::messaging::message_loop(
+        ctx.clone(),
+        "events.vehicles.aggregate-period.created".to_string(),
+        process_aggregate_period_event,
+    );
+
+    let move_hot_tier = hot_tier.clone();
+    tokio::task::spawn(async move {
+        let mut sub = ctx.parent.nats_client.subscribe(subject).await?;
+        while let Some(msg) = sub.next().await {
+            let result =
+                crate::utils::moves::decode_position(&msg).and_then(|position| match position {
+                    Some(position) => move_hot_tier.push(position),
+                    None => anyhow::Ok(()),
+                });
+            if let Err(e) = result {
+                log::error!("Error processing move command: {}", e);
+            }
+        }
+        anyhow::Ok(())
+    });
+
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            RETENTION_CHECK_INTERVAL_IN_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            let count = hot_tier.evict_expired();
+            if count > 0 {
+                log::warn!(
+                    "Evicted {} recent events that were not flushed in time",
+                    count
+                );
+            }
+        }
+    });
+    anyhow::Ok(())
+}
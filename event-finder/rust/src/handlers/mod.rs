@@ -1,2 +1,15 @@
 pub mod search;
+pub mod density;
+pub mod encounters;
+pub mod export;
+pub mod flight_sql;
+pub mod geofences;
+pub mod hot_tier;
+pub mod nearest;
+pub mod od_matrix;
+pub mod snapshot;
+pub mod stats;
+pub mod stops;
 pub mod ping;
+pub mod rest;
+pub mod trajectory;
@@ -0,0 +1,310 @@
+use crate::query::{VehicleFix, collect_fixes, first_fix_per_vehicle};
+use chrono::{DateTime, Utc};
+use datafusion::prelude::*;
+use geo::{HaversineDistance, Point, point};
+use log;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+// Partitions are geohashes of 5 chars (about 4.9km x 4.9km), which caps the search to about 100km.
+const PARTITION_PRECISION: usize = 5;
+const MAX_RING_COUNT: usize = 20;
+
+fn distance_in_meters(origin: &Point, fix: &VehicleFix) -> f64 {
+    origin.haversine_distance(&point!(x: fix.lon, y: fix.lat))
+}
+
+/// Shortest distance between the origin and the border of the scanned cells.
+/// Any vehicle that was not found yet is at least that far.
+fn scanned_radius(origin: &Point, scanned: &HashSet<String>) -> anyhow::Result<f64> {
+    let mut min_x = f64::MAX;
+    let mut min_y = f64::MAX;
+    let mut max_x = f64::MIN;
+    let mut max_y = f64::MIN;
+    for hash in scanned.iter() {
+        let bbox = geohash::decode_bbox(hash)?;
+        min_x = min_x.min(bbox.min().x);
+        min_y = min_y.min(bbox.min().y);
+        max_x = max_x.max(bbox.max().x);
+        max_y = max_y.max(bbox.max().y);
+    }
+    let borders = [
+        point!(x: min_x, y: origin.y()),
+        point!(x: max_x, y: origin.y()),
+        point!(x: origin.x(), y: min_y),
+        point!(x: origin.x(), y: max_y),
+    ];
+    Ok(borders
+        .iter()
+        .map(|border| origin.haversine_distance(border))
+        .fold(f64::MAX, f64::min))
+}
+
+/// Returns the vehicles sorted by their distance to the origin.
+fn rank_fixes(
+    origin: &Point,
+    fixes: HashMap<String, VehicleFix>,
+) -> Vec<(String, VehicleFix, f64)> {
+    let mut ranked: Vec<(String, VehicleFix, f64)> = fixes
+        .into_iter()
+        .map(|(vehicle_id, fix)| {
+            let distance = distance_in_meters(origin, &fix);
+            (vehicle_id, fix, distance)
+        })
+        .collect();
+    ranked.sort_by(|a, b| a.2.total_cmp(&b.2).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Keeps the latest position of each vehicle, which can be found in several partitions.
+fn merge_fixes(fixes: &mut HashMap<String, VehicleFix>, other: HashMap<String, VehicleFix>) {
+    for (vehicle_id, fix) in other.into_iter() {
+        match fixes.get(&vehicle_id) {
+            Some(existing) if existing.timestamp >= fix.timestamp => {}
+            _ => {
+                fixes.insert(vehicle_id, fix);
+            }
+        }
+    }
+}
+
+/// Selects the latest position of each vehicle within the requested time range.
+fn latest_fixes(
+    df: DataFrame,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+) -> anyhow::Result<DataFrame> {
+    let from_time = crate::query::timestamp_literal(df.schema(), from_date)?;
+    let to_time = crate::query::timestamp_literal(df.schema(), to_date)?;
+    let df = df
+        .filter(col("timestamp").gt_eq(from_time))?
+        .filter(col("timestamp").lt(to_time))?;
+    first_fix_per_vehicle(df, true)
+}
+
+async fn execute_nearest_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleNearestRequest>,
+) -> anyhow::Result<crate::types::VehicleNearestResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    if query.k == 0 {
+        anyhow::bail!("k must be greater than 0");
+    }
+    let origin = match geo::Geometry::try_from(&query.point)? {
+        geo::Geometry::Point(origin) => origin,
+        _ => anyhow::bail!("The point must be a GeoJSON Point"),
+    };
+    let from_date: DateTime<Utc> = query.from_date.parse()?;
+    let to_date: DateTime<Utc> = query.to_date.parse()?;
+    let mut filter = crate::query::VehicleFilter::with_range(from_date, to_date, None)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    let events = ctx.get_session().table("events").await?;
+
+    // Scan the partitions ring by ring, starting from the one containing the point,
+    // until the k-th closest vehicle is nearer than any unscanned partition.
+    let start_time = Instant::now();
+    let mut ring: HashSet<String> =
+        HashSet::from([geohash::encode(origin.0, PARTITION_PRECISION)?]);
+    let mut scanned: HashSet<String> = HashSet::new();
+    let mut fixes: HashMap<String, VehicleFix> = HashMap::new();
+    let mut ring_count: usize = 0;
+    let mut has_timed_out = false;
+    loop {
+        let df = latest_fixes(
+            filter.apply(events.clone(), Some(&ring))?,
+            from_date,
+            to_date,
+        )?;
+        let (ring_fixes, timed_out) = collect_fixes(df, &start_time, query_timeout).await?;
+        merge_fixes(&mut fixes, ring_fixes);
+        scanned.extend(ring.iter().cloned());
+        ring_count += 1;
+        if timed_out {
+            has_timed_out = true;
+            break;
+        }
+        if ring_count >= MAX_RING_COUNT {
+            break;
+        }
+        if fixes.len() >= query.k {
+            let kth_distance = rank_fixes(&origin, fixes.clone())[query.k - 1].2;
+            if kth_distance <= scanned_radius(&origin, &scanned)? {
+                break;
+            }
+        }
+        ring = crate::utils::geo::geohash_ring(&ring, &scanned)?;
+    }
+    log::debug!(
+        "Scanned {} partitions in {} rings",
+        scanned.len(),
+        ring_count
+    );
+
+    // A candidate might have moved away into a partition that was not scanned,
+    // so the latest positions of all the candidates are fetched from all the partitions
+    // before keeping the k closest ones.
+    let mut ranked = rank_fixes(&origin, fixes);
+    if !ranked.is_empty() && !has_timed_out {
+        filter.vehicle_ids = ranked
+            .iter()
+            .map(|(vehicle_id, _, _)| vehicle_id.clone())
+            .collect();
+        let df = latest_fixes(filter.apply(events, None)?, from_date, to_date)?;
+        let (latest, timed_out) = collect_fixes(df, &start_time, query_timeout).await?;
+        has_timed_out = timed_out;
+        let mut refreshed: HashMap<String, VehicleFix> = ranked
+            .into_iter()
+            .map(|(vehicle_id, fix, _)| (vehicle_id, fix))
+            .collect();
+        merge_fixes(&mut refreshed, latest);
+        ranked = rank_fixes(&origin, refreshed);
+    }
+    ranked.truncate(query.k);
+
+    let vehicle_count = ranked.len();
+    for (i, (vehicle_id, fix, distance)) in ranked.into_iter().enumerate() {
+        let result = crate::types::VehicleNearestResult {
+            msg_type: "vehicle-nearest-result".to_string(),
+            query_id: query.id.clone(),
+            rank: i + 1,
+            distance_in_meters: distance,
+            timestamp: crate::utils::time::format_timestamp_millis(fix.timestamp)?,
+            vehicle_id,
+            vehicle_type: fix.vehicle_type,
+            gps: crate::types::GpsCoordinates {
+                lat: fix.lat,
+                lon: fix.lon,
+                alt: 0.0,
+            },
+            direction: fix.direction,
+            speed: fix.speed,
+        };
+        crate::utils::messaging::publish_message(
+            &ctx.parent.nats_client,
+            req.reply_to.clone(),
+            &result,
+        )
+        .await?;
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total nearest vehicles: {}", vehicle_count);
+
+    Ok(crate::types::VehicleNearestResponse {
+        msg_type: "vehicle-nearest-response".to_string(),
+        vehicle_count,
+        ring_count,
+        partition_count: scanned.len(),
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+    })
+}
+
+async fn process_nearest_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleNearestRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_nearest_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle nearest query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_nearest_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.nearest".to_string(),
+        process_nearest_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+
+    fn fix(timestamp: i64, lat: f64) -> VehicleFix {
+        VehicleFix {
+            timestamp,
+            lat,
+            lon: 4.35,
+            vehicle_type: "taxi".to_string(),
+            direction: "north".to_string(),
+            speed: 30.0,
+        }
+    }
+
+    #[test]
+    fn ranks_the_latest_positions_by_distance() {
+        let origin = point!(x: 4.35, y: 50.85);
+        let mut fixes = HashMap::from([("a".to_string(), fix(1000, 50.851))]);
+        merge_fixes(
+            &mut fixes,
+            HashMap::from([
+                // a moved away, and an older position of b is ignored
+                ("a".to_string(), fix(2000, 50.86)),
+                ("b".to_string(), fix(1000, 50.852)),
+            ]),
+        );
+        merge_fixes(
+            &mut fixes,
+            HashMap::from([("b".to_string(), fix(500, 50.85))]),
+        );
+
+        let ranked = rank_fixes(&origin, fixes)
+            .into_iter()
+            .map(|(vehicle_id, fix, _)| (vehicle_id, fix.timestamp))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranked,
+            vec![("b".to_string(), 1000), ("a".to_string(), 2000)]
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_the_k_nearest_vehicles() -> anyhow::Result<()> {
+        let event = |vehicle_id, lat| TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp: "2024-01-01T10:00:00Z",
+            lon: 4.35,
+            lat,
+        };
+        let ctx = crate::contexts::tests::data_handler_context(
+            crate::contexts::tests::test_config()?,
+            crate::query::tests::events_session(&[
+                event("a", 50.851),
+                event("b", 50.86),
+                event("c", 50.95),
+            ])?,
+        )
+        .await?;
+        let nearest = async |k: usize| {
+            let req = crate::contexts::tests::request(serde_json::json!({
+                "type": "vehicle-nearest-request",
+                "id": "nearest-1",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "point": { "type": "Point", "coordinates": [4.35, 50.85] },
+                "k": k,
+            }))?;
+            execute_nearest_query(&ctx, &req).await
+        };
+
+        assert_eq!(nearest(2).await?.vehicle_count, 2);
+        assert_eq!(nearest(5).await?.vehicle_count, 3);
+        assert!(nearest(0).await.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+use crate::utils::stops::{Stop, StopCriteria, StopDetector};
+use chrono::{DateTime, Utc};
+use datafusion::arrow::array::{Array, RecordBatch, StringArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::cast::as_string_array;
+use futures_util::StreamExt;
+use log;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+// Bounds the size of the messages, the matrices having zone_count² cells
+const MAX_MATRIX_CELLS_PER_MESSAGE: usize = 100_000;
+
+/// A place where a trip starts or ends.
+#[derive(Debug, Clone, Copy)]
+struct Place {
+    start_time: i64,
+    zone: Option<i64>,
+}
+
+impl Place {
+    fn from_stop(stop: &Stop, zone: Option<i64>) -> Self {
+        Self {
+            start_time: stop.start_time,
+            zone,
+        }
+    }
+}
+
+/// The zones of the generator given with the moves, when the files have them.
+struct ZoneIds {
+    zone_ids: Option<StringArray>,
+}
+
+impl ZoneIds {
+    fn read(batch: &RecordBatch) -> anyhow::Result<Self> {
+        let zone_ids = match batch.column_by_name("zoneId") {
+            Some(column) => Some(as_string_array(&cast(column, &DataType::Utf8)?)?.clone()),
+            None => None,
+        };
+        Ok(Self { zone_ids })
+    }
+
+    /// Returns the zone of the move, or the zone containing its position when the move has none.
+    fn zone(
+        &self,
+        zones: &crate::utils::geo::MapZones,
+        i: usize,
+        event: &crate::query::VehicleEvent,
+    ) -> Option<i64> {
+        match &self.zone_ids {
+            Some(zone_ids) if zone_ids.is_valid(i) => zone_ids
+                .value(i)
+                .parse()
+                .ok()
+                .filter(|zone| (1..=zones.zone_count()).contains(zone)),
+            _ => zones.zone_at(event.lon, event.lat),
+        }
+    }
+}
+
+/// Splits the positions of a single vehicle into trips separated by stops.
+struct TripBuilder {
+    vehicle_id: String,
+    vehicle_type: String,
+    detector: StopDetector,
+    places: Vec<Place>,
+    last_position: Place,
+}
+
+impl TripBuilder {
+    fn new(event: &crate::query::VehicleEvent, zone: Option<i64>, criteria: StopCriteria) -> Self {
+        let place = Place {
+            start_time: event.timestamp,
+            zone,
+        };
+        let mut builder = Self {
+            vehicle_id: event.vehicle_id.to_string(),
+            vehicle_type: event.vehicle_type.to_string(),
+            detector: StopDetector::new(criteria),
+            places: vec![place],
+            last_position: place,
+        };
+        builder
+            .detector
+            .push(event.timestamp, event.lon, event.lat, event.speed);
+        builder
+    }
+
+    fn push(&mut self, event: &crate::query::VehicleEvent, zone: Option<i64>) {
+        if let Some(stop) = self
+            .detector
+            .push(event.timestamp, event.lon, event.lat, event.speed)
+        {
+            // the stop ended with the previous position
+            self.push_stop(&stop, self.last_position.zone);
+        }
+        self.last_position = Place {
+            start_time: event.timestamp,
+            zone,
+        };
+    }
+
+    fn push_stop(&mut self, stop: &Stop, zone: Option<i64>) {
+        // a vehicle that was already stopped at the start of the range starts its first trip from the stop
+        if self.places.len() == 1 && self.places[0].start_time == stop.start_time {
+            self.places[0] = Place::from_stop(stop, zone);
+        } else {
+            self.places.push(Place::from_stop(stop, zone));
+        }
+    }
+
+    /// Returns the origin and destination of each trip.
+    fn into_trips(mut self) -> Vec<(Place, Place)> {
+        match self.detector.finish() {
+            Some(stop) => self.push_stop(&stop, self.last_position.zone),
+            None => {
+                if self.last_position.start_time > self.places[self.places.len() - 1].start_time {
+                    self.places.push(self.last_position);
+                }
+            }
+        }
+        self.places
+            .windows(2)
+            .map(|places| (places[0], places[1]))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct TripCounter {
+    // (vehicle type, origin zone, destination zone) => trip count
+    counts: HashMap<(Option<String>, i64, i64), u64>,
+    trip_count: u64,
+    outside_trip_count: u64,
+}
+
+impl TripCounter {
+    fn add(&mut self, vehicle_type: Option<String>, builder: TripBuilder) {
+        for (origin, destination) in builder.into_trips() {
+            match (origin.zone, destination.zone) {
+                (Some(origin_zone), Some(destination_zone)) => {
+                    *self
+                        .counts
+                        .entry((vehicle_type.clone(), origin_zone, destination_zone))
+                        .or_default() += 1;
+                    self.trip_count += 1;
+                }
+                _ => self.outside_trip_count += 1,
+            }
+        }
+    }
+
+    /// Returns the rows of the matrices for the origin zones in the given range, starting at 1.
+    fn matrix_rows(
+        &self,
+        zone_count: i64,
+        origin_zones: std::ops::Range<i64>,
+    ) -> Vec<crate::types::OdMatrix> {
+        let size = zone_count as usize;
+        let mut matrices: BTreeMap<Option<String>, Vec<Vec<u64>>> = BTreeMap::new();
+        for ((vehicle_type, origin_zone, destination_zone), count) in self.counts.iter() {
+            let matrix = matrices
+                .entry(vehicle_type.clone())
+                .or_insert_with(|| vec![vec![0; size]; origin_zones.clone().count()]);
+            if origin_zones.contains(origin_zone) {
+                matrix[(origin_zone - origin_zones.start) as usize]
+                    [(destination_zone - 1) as usize] += count;
+            }
+        }
+        matrices
+            .into_iter()
+            .map(|(vehicle_type, trip_counts)| crate::types::OdMatrix {
+                vehicle_type,
+                trip_counts,
+            })
+            .collect()
+    }
+}
+
+fn zone_features(zones: &crate::utils::geo::MapZones) -> geojson::FeatureCollection {
+    let features = (1..=zones.zone_count())
+        .map(|zone| {
+            let mut properties = geojson::JsonObject::new();
+            properties.insert("zoneId".to_string(), zone.to_string().into());
+            geojson::Feature {
+                bbox: None,
+                geometry: Some(geojson::Geometry::new(geojson::Value::from(
+                    &zones.zone_polygon(zone),
+                ))),
+                id: Some(geojson::feature::Id::String(zone.to_string())),
+                properties: Some(properties),
+                foreign_members: None,
+            }
+        })
+        .collect();
+    geojson::FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    }
+}
+
+async fn execute_od_matrix_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleOdMatrixRequest>,
+) -> anyhow::Result<crate::types::VehicleOdMatrixResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let group_by_vehicle_type = query.group_by_vehicle_type.unwrap_or(false);
+    let criteria = StopCriteria::new(
+        query.stop_max_speed,
+        query.stop_radius_in_meters,
+        query.stop_min_duration_in_min,
+    )?;
+    let zones = crate::query::map_zones(&ctx.config.generator)?;
+    // the matrices are chunked by origin rows
+    if zones.zone_count() <= 0 {
+        anyhow::bail!("The map of the generator has no zones");
+    }
+    let from_date: DateTime<Utc> = query.from_date.parse()?;
+    let to_date: DateTime<Utc> = query.to_date.parse()?;
+    let mut filter = crate::query::VehicleFilter::with_range(from_date, to_date, None)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, None)?;
+    let time_range = crate::query::time_range_predicate(df.schema(), from_date, to_date)?;
+    df = df
+        .filter(time_range)?
+        .sort(crate::utils::pagination::sort_exprs(
+            crate::types::VehicleQueryOrder::VehicleIdTimestamp,
+        ))?;
+    let columns = crate::query::VehicleEventColumns::try_new(df.schema())?;
+
+    let start_time = Instant::now();
+    let mut processed_row_count: usize = 0;
+    let mut vehicle_count: usize = 0;
+    let mut has_timed_out = false;
+    let mut counter = TripCounter::default();
+    let mut current: Option<TripBuilder> = None;
+
+    let mut stream = df.execute_stream().await?;
+    while let Some(batch_result) = stream.next().await {
+        let batch = batch_result?;
+        processed_row_count += batch.num_rows();
+        let events = columns.read(&batch)?;
+        let zone_ids = ZoneIds::read(&batch)?;
+        for i in 0..events.len() {
+            let event = events.event(i);
+            if !event.has_valid_position() {
+                continue;
+            }
+            let zone = zone_ids.zone(&zones, i, &event);
+            match current.as_mut() {
+                Some(builder) if builder.vehicle_id == event.vehicle_id => {
+                    builder.push(&event, zone)
+                }
+                _ => {
+                    if let Some(builder) = current.take() {
+                        let vehicle_type =
+                            group_by_vehicle_type.then(|| builder.vehicle_type.clone());
+                        counter.add(vehicle_type, builder);
+                    }
+                    current = Some(TripBuilder::new(&event, zone, criteria));
+                    vehicle_count += 1;
+                }
+            }
+        }
+        if start_time.elapsed().as_millis() >= query_timeout {
+            has_timed_out = true;
+            break;
+        }
+    }
+    if let Some(builder) = current.take() {
+        let vehicle_type = group_by_vehicle_type.then(|| builder.vehicle_type.clone());
+        counter.add(vehicle_type, builder);
+    }
+    let trip_count = counter.trip_count;
+    let outside_trip_count = counter.outside_trip_count;
+
+    // The matrices are sent by chunks of origin rows, the zones with the first chunk
+    let zone_count = zones.zone_count();
+    let rows_per_message = (MAX_MATRIX_CELLS_PER_MESSAGE / zone_count as usize).max(1) as i64;
+    let mut origin_zone = 1;
+    while origin_zone <= zone_count {
+        let origin_zones = origin_zone..(origin_zone + rows_per_message).min(zone_count + 1);
+        let is_first = origin_zone == 1;
+        let result = crate::types::VehicleOdMatrixResult {
+            msg_type: "vehicle-od-matrix-result".to_string(),
+            query_id: query.id.clone(),
+            origin_offset: (origin_zones.start - 1) as usize,
+            zone_ids: match is_first {
+                true => (1..=zone_count).map(|zone| zone.to_string()).collect(),
+                false => vec![],
+            },
+            zones: is_first.then(|| zone_features(&zones)),
+            matrices: counter.matrix_rows(zone_count, origin_zones.clone()),
+        };
+        crate::utils::messaging::publish_message(
+            &ctx.parent.nats_client,
+            req.reply_to.clone(),
+            &result,
+        )
+        .await?;
+        origin_zone = origin_zones.end;
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total trips: {}", trip_count);
+
+    Ok(crate::types::VehicleOdMatrixResponse {
+        msg_type: "vehicle-od-matrix-response".to_string(),
+        processed_record_count: processed_row_count,
+        vehicle_count,
+        trip_count,
+        outside_trip_count,
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+    })
+}
+
+async fn process_od_matrix_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleOdMatrixRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_od_matrix_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle OD matrix query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_od_matrix_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.od-matrix".to_string(),
+        process_od_matrix_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_matrix_rows_of_the_origin_zones() {
+        let mut counter = TripCounter::default();
+        counter.counts.insert((None, 1, 3), 2);
+        counter.counts.insert((None, 3, 1), 5);
+
+        let first_rows = counter.matrix_rows(3, 1..3);
+        let last_rows = counter.matrix_rows(3, 3..4);
+
+        assert_eq!(first_rows[0].trip_counts, vec![vec![0, 0, 2], vec![0, 0, 0]]);
+        assert_eq!(last_rows[0].trip_counts, vec![vec![5, 0, 0]]);
+    }
+
+    #[test]
+    fn rejects_the_maps_without_zones() -> anyhow::Result<()> {
+        let mut generator = crate::contexts::tests::test_config()?.generator;
+        assert!(crate::query::map_zones(&generator)?.zone_count() > 0);
+
+        generator.map.widthInKm = 0.0;
+
+        assert!(crate::query::map_zones(&generator).is_err());
+        Ok(())
+    }
+}
@@ -23,11 +23,8 @@ pub async fn process_ping_request(
             identity: ctx.identity.clone(),
         },
     };
-    let resp_json = serde_json::to_vec(&resp)?;
     log::info!("Sending NATS response: {:?}", resp);
-    ctx.nats_client
-        .publish(req.reply_to.clone(), resp_json.into())
-        .await?;
+    crate::utils::messaging::publish_message(&ctx.nats_client, req.reply_to.clone(), &resp).await?;
     anyhow::Ok(())
 }
 
@@ -0,0 +1,484 @@
+use crate::handlers::search::{VehicleQueryOutput, VehicleQuerySink};
+use crate::query_events::{QueryEventHub, VehicleQueryEvent};
+use actix_web::http::header;
+use actix_web::web::{Bytes, Data, Json, Path};
+use actix_web::{HttpRequest, HttpResponse, get, post};
+use log;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+const GEOJSON_CONTENT_TYPE: &str = "application/geo+json";
+// the results waiting to be sent to a slow client, before the query is paused
+const RESULT_BUFFER_SIZE: usize = 1024;
+// detects the disconnected clients of the queries that take a while to start or to progress
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+// the stream of a query that never starts, like an unknown one, is closed after this delay
+const START_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How the results of a vehicle query are streamed to an HTTP client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResultEncoding {
+    /// One JSON object per line, ending with the response
+    NdJson,
+    /// A FeatureCollection with one Point feature per result, and the response as a foreign member
+    GeoJson,
+}
+
+impl ResultEncoding {
+    fn from_request(req: &HttpRequest) -> Self {
+        let accept = req
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if accept.contains(GEOJSON_CONTENT_TYPE) {
+            Self::GeoJson
+        } else {
+            Self::NdJson
+        }
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Self::NdJson => NDJSON_CONTENT_TYPE,
+            Self::GeoJson => GEOJSON_CONTENT_TYPE,
+        }
+    }
+
+    fn encode(&self, output: &VehicleQueryOutput, is_first: bool) -> anyhow::Result<Vec<u8>> {
+        let mut buffer = vec![];
+        match (self, output) {
+            (Self::NdJson, VehicleQueryOutput::Result(result)) => {
+                serde_json::to_writer(&mut buffer, result)?;
+                buffer.push(b'\n');
+            }
+            (Self::NdJson, VehicleQueryOutput::Response(resp)) => {
+                serde_json::to_writer(&mut buffer, resp)?;
+                buffer.push(b'\n');
+            }
+            (Self::GeoJson, VehicleQueryOutput::Result(result)) => {
+                buffer.extend_from_slice(match is_first {
+                    true => br#"{"type":"FeatureCollection","features":["#,
+                    false => b",",
+                });
+                let feature = serde_json::json!({
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "Point",
+                        "coordinates": [result.gps.lon, result.gps.lat, result.gps.alt],
+                    },
+                    "properties": {
+                        "timestamp": result.timestamp,
+                        "vehicleId": result.vehicle_id,
+                        "vehicleType": result.vehicle_type,
+                        "speed": result.speed,
+                        "direction": result.direction,
+                        "geoHash": result.geo_hash,
+                    },
+                });
+                serde_json::to_writer(&mut buffer, &feature)?;
+            }
+            (Self::GeoJson, VehicleQueryOutput::Response(resp)) => {
+                if is_first {
+                    buffer.extend_from_slice(br#"{"type":"FeatureCollection","features":["#);
+                }
+                buffer.extend_from_slice(br#"],"response":"#);
+                serde_json::to_writer(&mut buffer, resp)?;
+                buffer.push(b'}');
+            }
+        }
+        Ok(buffer)
+    }
+}
+
+/// Runs a vehicle query with the same engine, timeout and limit as the NATS requests,
+/// and streams its results as NDJSON or GeoJSON, depending on the `Accept` header.
+/// The query is cancelled when the client disconnects.
+#[post("/api/v1/vehicles/query")]
+pub async fn query_vehicles(
+    ctx: Data<crate::contexts::DataHandlerContext>,
+    http_req: HttpRequest,
+    body: Json<crate::types::VehicleQueryRequest>,
+) -> HttpResponse {
+    let query = body.into_inner();
+    log::info!("Received HTTP request: {:?}", query);
+    if let Err(e) = crate::query::VehicleFilter::from_query(&query) {
+        return HttpResponse::BadRequest().body(e.to_string());
+    }
+    if query.format == Some(crate::types::TableFormat::Arrow) {
+        return HttpResponse::BadRequest()
+            .body("The arrow format is only supported by the NATS requests");
+    }
+    let req = crate::types::Request {
+        id: Uuid::new_v4().to_string(),
+        msg_type: "request".to_string(),
+        reply_to: String::new(),
+        parent_id: None,
+        expires_at: None,
+        timeout: None,
+        body: query,
+    };
+    let encoding = ResultEncoding::from_request(&http_req);
+    let (sender, receiver) = tokio::sync::mpsc::channel(RESULT_BUFFER_SIZE);
+    let ctx = ctx.get_ref().clone();
+    tokio::spawn(async move {
+        if let Err(e) =
+            crate::handlers::search::run_vehicle_query(ctx, req, VehicleQuerySink::Channel(sender))
+                .await
+        {
+            log::error!("Error running HTTP vehicle query: {}", e);
+        }
+    });
+
+    // the receiver is dropped with the body when the client disconnects, which stops the query
+    let body = futures_util::stream::unfold(
+        (receiver, true),
+        move |(mut receiver, is_first)| async move {
+            let output = receiver.recv().await?;
+            let chunk = encoding
+                .encode(&output, is_first)
+                .map(Bytes::from)
+                .map_err(actix_web::error::ErrorInternalServerError);
+            Some((chunk, (receiver, false)))
+        },
+    );
+    HttpResponse::Ok()
+        .content_type(encoding.content_type())
+        .streaming(body)
+}
+
+/// The subscription of an SSE client to the events of a query.
+struct QueryEventSubscription {
+    hub: Arc<QueryEventHub>,
+    query_id: String,
+    replay: VecDeque<VehicleQueryEvent>,
+    // dropped after the vehicle-query-stopped event
+    receiver: Option<broadcast::Receiver<VehicleQueryEvent>>,
+    // until the vehicle-query-started event
+    start_deadline: Option<Instant>,
+}
+
+impl QueryEventSubscription {
+    fn new(hub: Arc<QueryEventHub>, query_id: String) -> Self {
+        let (replay, receiver) = hub.subscribe(&query_id);
+        let start_deadline = match replay.is_empty() {
+            true => Some(Instant::now() + START_TIMEOUT),
+            false => None,
+        };
+        Self {
+            hub,
+            query_id,
+            replay: replay.into(),
+            receiver: Some(receiver),
+            start_deadline,
+        }
+    }
+
+    fn on_event(&mut self, event: &VehicleQueryEvent) {
+        match event {
+            VehicleQueryEvent::Stopped(_) => self.receiver = None,
+            _ => self.start_deadline = None,
+        }
+    }
+
+    async fn next_chunk(&mut self) -> Option<String> {
+        if let Some(event) = self.replay.pop_front() {
+            self.on_event(&event);
+            return Some(encode_server_sent_event(&event));
+        }
+        let receiver = self.receiver.as_mut()?;
+        let keep_alive_time = match self.start_deadline {
+            Some(deadline) => deadline.min(Instant::now() + KEEP_ALIVE_INTERVAL),
+            None => Instant::now() + KEEP_ALIVE_INTERVAL,
+        };
+        tokio::select! {
+            result = receiver.recv() => match result {
+                Ok(event) => {
+                    self.on_event(&event);
+                    Some(encode_server_sent_event(&event))
+                }
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    Some(format!(": skipped {} events\n\n", count))
+                }
+                Err(broadcast::error::RecvError::Closed) => None,
+            },
+            _ = tokio::time::sleep_until(keep_alive_time) => {
+                if self.start_deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+                    return None;
+                }
+                Some(": keep-alive\n\n".to_string())
+            }
+        }
+    }
+}
+
+impl Drop for QueryEventSubscription {
+    fn drop(&mut self) {
+        self.receiver = None;
+        self.hub.unsubscribe(&self.query_id);
+    }
+}
+
+fn encode_server_sent_event(event: &VehicleQueryEvent) -> String {
+    match event.to_json() {
+        Ok(data) => format!("event: {}\ndata: {}\n\n", event.msg_type(), data),
+        Err(e) => format!(": invalid {} event: {}\n\n", event.msg_type(), e),
+    }
+}
+
+/// Streams the lifecycle events of a vehicle query submitted over HTTP or NATS, as Server-Sent Events:
+/// vehicle-query-started, vehicle-query-progress, vehicle-query-result-chunk and vehicle-query-stopped.
+/// The clients can subscribe before submitting the query, and the stream ends when the query stops,
+/// or when it does not start in time.
+#[get("/api/v1/queries/{id}/events")]
+pub async fn query_events(
+    ctx: Data<crate::contexts::DataHandlerContext>,
+    query_id: Path<String>,
+) -> HttpResponse {
+    let query_id = query_id.into_inner();
+    log::info!("Subscribing to the events of query {}", query_id);
+    let subscription = QueryEventSubscription::new(ctx.query_events.clone(), query_id);
+    let body = futures_util::stream::unfold(subscription, |mut subscription| async move {
+        let chunk = subscription.next_chunk().await?;
+        Some((Ok::<_, actix_web::Error>(Bytes::from(chunk)), subscription))
+    });
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+    use crate::query_events::tests::{started, stopped};
+    use actix_web::{App, test};
+
+    async fn context(
+        events: &[TestEvent<'_>],
+    ) -> anyhow::Result<crate::contexts::DataHandlerContext> {
+        crate::contexts::tests::data_handler_context(
+            crate::contexts::tests::test_config()?,
+            crate::query::tests::events_session(events)?,
+        )
+        .await
+    }
+
+    fn event(timestamp: &str) -> TestEvent<'_> {
+        TestEvent {
+            vehicle_id: "a",
+            vehicle_type: "taxi",
+            timestamp,
+            lon: 4.35,
+            lat: 50.85,
+        }
+    }
+
+    fn query(vehicle_ids: &[&str]) -> serde_json::Value {
+        serde_json::json!({
+            "type": "vehicle-query-request",
+            "id": "q1",
+            "fromDate": "2024-01-01T10:00:00Z",
+            "toDate": "2024-01-01T11:00:00Z",
+            "vehicleIds": vehicle_ids,
+            "orderBy": "timestampAsc",
+        })
+    }
+
+    /// Posts the vehicle query and returns the content type and the body of the response.
+    async fn post_query(
+        ctx: crate::contexts::DataHandlerContext,
+        accept: Option<&str>,
+        query: serde_json::Value,
+    ) -> anyhow::Result<(String, String)> {
+        let app =
+            test::init_service(App::new().app_data(Data::new(ctx)).service(query_vehicles)).await;
+        let mut req = test::TestRequest::post()
+            .uri("/api/v1/vehicles/query")
+            .set_json(query);
+        if let Some(accept) = accept {
+            req = req.insert_header((header::ACCEPT, accept));
+        }
+        let resp = test::call_service(&app, req.to_request()).await;
+        assert!(resp.status().is_success());
+        let content_type = resp
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = String::from_utf8(test::read_body(resp).await.to_vec())?;
+        Ok((content_type, body))
+    }
+
+    #[actix_web::test]
+    async fn streams_the_results_as_ndjson_by_default() -> anyhow::Result<()> {
+        let ctx = context(&[event("2024-01-01T10:00:01Z"), event("2024-01-01T10:00:00Z")]).await?;
+
+        let (content_type, body) = post_query(ctx, None, query(&["a"])).await?;
+
+        assert_eq!(content_type, NDJSON_CONTENT_TYPE);
+        let lines = body
+            .lines()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "vehicle-query-result");
+        assert_eq!(lines[0]["timestamp"], "2024-01-01T10:00:00+00:00");
+        assert_eq!(lines[2]["type"], "response-success");
+        assert_eq!(lines[2]["body"]["selectedRecordCount"], 2);
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn streams_the_results_as_geojson_when_accepted() -> anyhow::Result<()> {
+        let ctx = context(&[event("2024-01-01T10:00:01Z"), event("2024-01-01T10:00:00Z")]).await?;
+
+        let (content_type, body) = post_query(
+            ctx,
+            Some("application/json, application/geo+json"),
+            query(&["a"]),
+        )
+        .await?;
+
+        assert_eq!(content_type, GEOJSON_CONTENT_TYPE);
+        let collection = body.parse::<geojson::FeatureCollection>()?;
+        assert_eq!(collection.features.len(), 2);
+        assert_eq!(
+            collection.features[0]
+                .geometry
+                .as_ref()
+                .map(|geometry| &geometry.value),
+            Some(&geojson::Value::Point(vec![4.35, 50.85, 0.0]))
+        );
+        let response = &collection.foreign_members.as_ref().unwrap()["response"];
+        assert_eq!(response["type"], "response-success");
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn writes_an_empty_geojson_collection_without_results() -> anyhow::Result<()> {
+        let ctx = context(&[event("2024-01-01T10:00:00Z")]).await?;
+
+        let (_, body) = post_query(ctx, Some(GEOJSON_CONTENT_TYPE), query(&["b"])).await?;
+
+        let collection = serde_json::from_str::<serde_json::Value>(&body)?;
+        assert_eq!(collection["type"], "FeatureCollection");
+        assert_eq!(collection["features"], serde_json::json!([]));
+        assert_eq!(collection["response"]["type"], "response-success");
+        assert_eq!(collection["response"]["body"]["selectedRecordCount"], 0);
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn ends_the_geojson_collection_with_the_error_response() -> anyhow::Result<()> {
+        let ctx = context(&[event("2024-01-01T10:00:00Z")]).await?;
+        let mut query = query(&["a"]);
+        query["cursor"] = serde_json::json!("not-a-cursor");
+
+        let (_, body) = post_query(ctx, Some(GEOJSON_CONTENT_TYPE), query).await?;
+
+        let collection = serde_json::from_str::<serde_json::Value>(&body)?;
+        assert_eq!(collection["features"], serde_json::json!([]));
+        assert_eq!(collection["response"]["type"], "response-error");
+        assert!(collection["response"]["error"].is_string());
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn cancels_the_query_when_the_client_disconnects() -> anyhow::Result<()> {
+        // more results than the buffer of the response, so that the query waits for the client
+        let timestamps = (0..2 * RESULT_BUFFER_SIZE)
+            .map(|i| format!("2024-01-01T10:{:02}:{:02}Z", i / 60, i % 60))
+            .collect::<Vec<_>>();
+        let events = timestamps
+            .iter()
+            .map(|timestamp| event(timestamp))
+            .collect::<Vec<_>>();
+        let ctx = context(&events).await?;
+        let (_, mut receiver) = ctx.query_events.subscribe("q1");
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(ctx.clone()))
+                .service(query_vehicles),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/vehicles/query")
+            .set_json(query(&["a"]))
+            .to_request();
+        drop(test::call_service(&app, req).await);
+
+        let stopped = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let VehicleQueryEvent::Stopped(event) = receiver.recv().await? {
+                    return anyhow::Ok(event);
+                }
+            }
+        })
+        .await??;
+        assert!(!stopped.is_success);
+        assert_eq!(
+            stopped.error.as_deref(),
+            Some("The client disconnected before the end of the query")
+        );
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn encodes_the_server_sent_events_on_a_single_data_line() {
+        let chunk = encode_server_sent_event(&VehicleQueryEvent::Started(started("q1")));
+
+        assert!(chunk.starts_with("event: vehicle-query-started\ndata: {"));
+        assert!(chunk.ends_with("}\n\n"));
+        assert_eq!(chunk.lines().count(), 3);
+    }
+
+    #[actix_web::test]
+    async fn replays_and_closes_the_stream_of_a_recently_stopped_query() -> anyhow::Result<()> {
+        let ctx = context(&[]).await?;
+        ctx.query_events.start(started("q1"));
+        ctx.query_events.stop(stopped("q1"));
+        let app =
+            test::init_service(App::new().app_data(Data::new(ctx)).service(query_events)).await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/queries/q1/events")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        let body = String::from_utf8(test::read_body(resp).await.to_vec())?;
+
+        let events = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("event: "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec!["vehicle-query-started", "vehicle-query-stopped"]
+        );
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn closes_the_stream_of_a_query_that_does_not_start_in_time() -> anyhow::Result<()> {
+        let ctx = context(&[]).await?;
+        let mut subscription = QueryEventSubscription::new(ctx.query_events.clone(), "q1".into());
+        assert!(subscription.start_deadline.is_some());
+
+        subscription.start_deadline = Some(Instant::now());
+
+        assert_eq!(subscription.next_chunk().await, None);
+        Ok(())
+    }
+}
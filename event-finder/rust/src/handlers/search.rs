@@ -1,7 +1,6 @@
-use async_nats::HeaderMap;
-use chrono::{Utc, prelude::*};
-use datafusion::arrow::array::{Array, Float64Array, StringViewArray, TimestampMillisecondArray};
 use datafusion::arrow::datatypes::DataType;
+use datafusion::catalog::TableProvider;
+use datafusion::common::cast::{as_string_array, as_timestamp_millisecond_array, as_uint64_array};
 use datafusion::datasource::file_format::arrow::ArrowFormat;
 use datafusion::datasource::file_format::csv::CsvFormat;
 use datafusion::datasource::file_format::json::JsonFormat;
@@ -10,12 +9,10 @@ use datafusion::datasource::listing::{
     ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
 };
 use datafusion::prelude::*;
-use futures_util::StreamExt;
-use geo::{Contains, Geometry, point};
+use futures_util::{StreamExt, TryStreamExt, future};
 use log;
-use object_store::ObjectStore;
 use object_store::azure::MicrosoftAzureBuilder;
-use prost::Message;
+use object_store::{ObjectMeta, ObjectStore};
 use std::collections::{HashMap, HashSet};
 use std::env;
 use std::path::PathBuf;
@@ -24,9 +21,57 @@ use std::sync::Arc;
 use std::time::Instant;
 use uuid::Uuid;
 
+/// Where the results and the response of a vehicle query are sent.
+pub enum VehicleQuerySink {
+    /// The reply subject of a NATS request
+    Nats(String),
+    /// The stream of an HTTP response, which is closed when the client disconnects
+    Channel(tokio::sync::mpsc::Sender<VehicleQueryOutput>),
+}
+
+pub enum VehicleQueryOutput {
+    Result(crate::types::VehicleQueryResult),
+    Response(crate::types::Response<crate::types::VehicleQueryResponse>),
+}
+
+impl VehicleQuerySink {
+    fn is_closed(&self) -> bool {
+        match self {
+            Self::Nats(_) => false,
+            Self::Channel(sender) => sender.is_closed(),
+        }
+    }
+
+    async fn send_response(
+        &self,
+        ctx: &crate::contexts::DataHandlerContext,
+        resp: &crate::types::Response<crate::types::VehicleQueryResponse>,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::Nats(reply_to) => {
+                crate::utils::messaging::publish_message(
+                    &ctx.parent.nats_client,
+                    reply_to.clone(),
+                    resp,
+                )
+                .await
+            }
+            Self::Channel(sender) => {
+                // the client may be gone already
+                let _ = sender
+                    .send(VehicleQueryOutput::Response(resp.clone()))
+                    .await;
+                anyhow::Ok(())
+            }
+        }
+    }
+}
+
 async fn execute_vehicle_query(
     ctx: &crate::contexts::DataHandlerContext,
     req: &crate::types::Request<crate::types::VehicleQueryRequest>,
+    sink: &VehicleQuerySink,
+    reporter: &mut crate::query_events::VehicleQueryReporter,
 ) -> anyhow::Result<crate::types::VehicleQueryResponse> {
     let labels: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
     let query = &req.body;
@@ -34,54 +79,65 @@ async fn execute_vehicle_query(
         Some(timeout) => timeout,
         None => ctx.config.finder.defaultTimeoutInMS,
     };
-    let (from_date1, _) =
-        crate::utils::time::round_datetime_modulo_minutes(query.from_date.parse()?, 10);
-    let (_, to_date2) =
-        crate::utils::time::round_datetime_modulo_minutes(query.to_date.parse()?, 10);
-
-    let from_date = from_date1.format("%Y-%m-%d-%H-%M").to_string();
-    let to_date = to_date2.format("%Y-%m-%d-%H-%M").to_string();
-    assert!(from_date < to_date, "fromDate must be before toDate");
-    let limit: usize = query.limit.unwrap_or(100).try_into()?;
-
-    let geom: Geometry = (&query.geometry).try_into()?;
-    let geohash_set = crate::utils::geo::geohash_covering(&geom, 5);
-    let partitions: Vec<Expr> = geohash_set.iter().map(|h| lit(h)).collect();
-    log::debug!("Partitions: {:?}", partitions);
-
-    // execute_query(ctx, "SELECT * FROM events where \"vehicleType\" = 'Mini_van' and pk in ('f25kv', 'f25s0') and start >= '2024-01-01-06-50' and start < '2024-01-01-07-10' limit 10").await?;
-    // execute_query(&ctx, "SELECT * FROM events limit 1000").await?;
-    // ORDER BY
-    //     period, partition
-
-    let mut df = ctx.get_session().table("events").await?;
-
-    df = df.filter(col("start").gt_eq(lit(from_date)))?;
-    df = df.filter(col("start").lt(lit(to_date)))?;
-    df = df.filter(col("pk").in_list(partitions, false))?;
-    if !query.vehicle_types.is_empty() {
-        let vehicle_types = query
-            .vehicle_types
-            .iter()
-            .map(|v| lit(v))
-            .collect::<Vec<Expr>>();
+    let filter = crate::query::VehicleFilter::from_query(query)?;
+    let limit: usize = query
+        .limit
+        .unwrap_or(crate::types::DEFAULT_QUERY_LIMIT)
+        .try_into()?;
+    let cursor = match &query.cursor {
+        Some(value) => Some(crate::utils::pagination::decode_cursor(value)?),
+        None => None,
+    };
+    let order_by = crate::utils::pagination::resolve_order(query.order_by, cursor.as_ref())?;
+    let format = query.format.unwrap_or(crate::types::TableFormat::Json);
 
-        df = df.filter(col(r#""vehicleType""#).in_list(vehicle_types, false))?;
+    let started_at = std::time::Instant::now();
+    // The recent events change on every move, so the results covering them cannot be cached
+    let hot = match &ctx.hot_tier {
+        Some(hot_tier) => hot_tier.overlaps(query.from_date.parse()?, query.to_date.parse()?),
+        None => false,
+    };
+    let cache_key = match ctx.query_cache {
+        Some(_) if !hot && format == crate::types::TableFormat::Json => {
+            Some(crate::cache::compute_query_key(query)?)
+        }
+        _ => None,
+    };
+    if let Some(cached) = cache_key.and_then(|key| ctx.query_cache.as_ref()?.get(key)) {
+        log::info!("Serving query {} from the cache", query.id);
+        return replay_cached_query(ctx, req, sink, reporter, &cached).await;
     }
-    // df.clone().show_limit(20).await?;
 
-    let schema = df.schema();
+    // The same event can be written in several files by partial flushes.
+    // When the results are ordered and published row by row, the duplicates are adjacent and
+    // skipped while streaming, otherwise they are ranked by DataFusion.
+    let deduplicate = query.deduplicate.unwrap_or(false);
+    let partitions = filter.partitions();
+    let df = crate::query::build_vehicle_dataframe(
+        ctx.get_session().table("events").await?,
+        &filter,
+        partitions.as_ref(),
+        &crate::query::VehicleSelection {
+            // The rows are checked within the plan when they are not checked one by one while streaming
+            check_rows: format == crate::types::TableFormat::Arrow
+                || query.sample_interval_in_secs.is_some(),
+            sample_interval_in_secs: query.sample_interval_in_secs,
+            sample_method: query.sample_method.unwrap_or_default(),
+            cursor: cursor.as_ref(),
+            rank_duplicates: deduplicate
+                && (order_by.is_none() || format == crate::types::TableFormat::Arrow),
+            order_by,
+        },
+    )?;
+    // df.clone().show_limit(20).await?;
+    if format == crate::types::TableFormat::Arrow {
+        if !matches!(sink, VehicleQuerySink::Nats(_)) {
+            anyhow::bail!("The arrow format is only supported by the NATS requests");
+        }
+        return stream_arrow_results(ctx, req, reporter, df, limit, query_timeout, order_by).await;
+    }
 
-    let idx_timestamp = schema.index_of_column(&Column::from_qualified_name("timestamp"))?;
-    let idx_lat = schema.index_of_column(&Column::from_qualified_name("gps_lat"))?;
-    let idx_lon = schema.index_of_column(&Column::from_qualified_name("gps_lon"))?;
-    let idx_alt = schema.index_of_column(&Column::from_qualified_name("gps_alt"))?;
-    let idx_vehicle_id = schema.index_of_column(&Column::from_qualified_name(r#""vehicleId""#))?;
-    let idx_vehicle_type =
-        schema.index_of_column(&Column::from_qualified_name(r#""vehicleType""#))?;
-    let idx_direction = schema.index_of_column(&Column::from_qualified_name("direction"))?;
-    let idx_speed = schema.index_of_column(&Column::from_qualified_name("speed"))?;
-    let idx_geohash = schema.index_of_column(&Column::from_qualified_name(r#""geoHash""#))?;
+    let columns = crate::query::VehicleEventColumns::try_new(df.schema())?;
 
     let start_time = Instant::now();
     let mut processed_row_count: usize = 0;
@@ -91,6 +147,17 @@ async fn execute_vehicle_query(
     let mut limit_reached = false;
     let mut has_timed_out = false;
     let mut vehicle_ids = HashSet::new();
+    let mut last_row_key: Option<(i64, String)> = None;
+    let mut duplicate_count: usize = 0;
+    let cache_budget = ctx
+        .config
+        .finder
+        .queryCache
+        .maxSizeInMB
+        .saturating_mul(1024 * 1024);
+    let mut cached_size: usize = 0;
+    let mut cached_results: Option<Vec<crate::types::VehicleQueryResult>> =
+        ctx.query_cache.as_ref().map(|_| Vec::new());
 
     let mut stream = df.execute_stream().await?;
     while let Some(batch_result) = stream.next().await {
@@ -99,168 +166,94 @@ async fn execute_vehicle_query(
         processed_row_count += batch.num_rows();
         total_bytes += batch.get_array_memory_size();
 
-        let col_timestamp = batch
-            .column(idx_timestamp)
-            .as_any()
-            .downcast_ref::<TimestampMillisecondArray>()
-            .ok_or_else(|| {
-                anyhow::format_err!(
-                    "Unable to cast column 'timestamp' into TimestampMillisecondArray"
-                )
-            })?;
-        let col_lat = batch
-            .column(idx_lat)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'gps_lat' into Float64Array")
-            })?;
-        let col_lon = batch
-            .column(idx_lon)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'gps_lon' into Float64Array")
-            })?;
-        let col_alt = batch
-            .column(idx_alt)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'gps_alt' into Float64Array")
-            })?;
-        let col_vehicle_id = batch
-            .column(idx_vehicle_id)
-            .as_any()
-            .downcast_ref::<StringViewArray>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'vehicleId' into StringViewArray")
-            })?;
-        let col_vehicle_type = batch
-            .column(idx_vehicle_type)
-            .as_any()
-            .downcast_ref::<StringViewArray>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'vehicleType' into StringViewArray")
-            })?;
-        let col_direction = batch
-            .column(idx_direction)
-            .as_any()
-            .downcast_ref::<StringViewArray>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'direction' into StringViewArray")
-            })?;
-        let col_speed = batch
-            .column(idx_speed)
-            .as_any()
-            .downcast_ref::<Float64Array>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'speed' into Float64Array")
-            })?;
-        let col_geohash = batch
-            .column(idx_geohash)
-            .as_any()
-            .downcast_ref::<StringViewArray>()
-            .ok_or_else(|| {
-                anyhow::format_err!("Unable to cast column 'geoHash' into StringViewArray")
-            })?;
-
-        for i in 0..batch.num_rows() {
-            let vehicle_timestamp = col_timestamp.value(i);
-            let vehicle_lat = col_lat.value(i);
-            let vehicle_lon = col_lon.value(i);
-            let vehicle_alt = col_alt.value(i);
-            let vehicle_id = col_vehicle_id.value(i);
-            let vehicle_type = col_vehicle_type.value(i);
-            let direction = col_direction.value(i);
-            let speed = col_speed.value(i);
-            let geo_hash = col_geohash.value(i);
-            if vehicle_lat.is_nan() || vehicle_lon.is_nan() || vehicle_alt.is_nan() {
+        let events = columns.read(&batch)?;
+        let duplicate_ranks = match batch.column_by_name("duplicate_rank") {
+            Some(column) => Some(as_uint64_array(column)?),
+            None => None,
+        };
+        for i in 0..events.len() {
+            let event = events.event(i);
+            if !event.has_valid_position() {
                 continue;
             }
-            let datetime = Utc
-                .timestamp_millis_opt(vehicle_timestamp)
-                .single()
-                .unwrap();
-            if (datetime < from_date1) || (datetime >= to_date2) {
+            let datetime = event.datetime()?;
+            if !filter.contains_timestamp(datetime) {
                 continue;
             }
-
-            let p = point!(x: vehicle_lon, y: vehicle_lat);
-            if !geom.contains(&p) {
+            if !filter.contains_position(event.lon, event.lat) {
                 continue;
             }
+            if deduplicate {
+                let is_duplicate = match duplicate_ranks {
+                    Some(ranks) => ranks.value(i) > 1,
+                    None => last_row_key
+                        .as_ref()
+                        .is_some_and(|(timestamp, vehicle_id)| {
+                            *timestamp == event.timestamp && vehicle_id == event.vehicle_id
+                        }),
+                };
+                if is_duplicate {
+                    duplicate_count += 1;
+                    continue;
+                }
+            }
+            if selected_row_count >= limit {
+                limit_reached = true;
+                break;
+            }
             selected_row_count += 1;
-            vehicle_ids.insert(vehicle_id.to_string());
+            if order_by.is_some() {
+                last_row_key = Some((event.timestamp, event.vehicle_id.to_string()));
+            }
+            vehicle_ids.insert(event.vehicle_id.to_string());
             ctx.parent
                 .prometheus_counters
                 .vehicles_search_processed_events_total_counter
                 .with(&labels)
                 .inc();
 
-            if ctx.parent.enable_proto_buf {
-                let gps = crate::types_proto::GpsCoordinates {
-                    lat: vehicle_lat,
-                    lon: vehicle_lon,
-                    alt: 0.0,
-                };
-                let result = crate::types_proto::VehicleQueryResult {
-                    query_id: query.id.clone(),
-                    timestamp: datetime.to_rfc3339(),
-                    vehicle_id: vehicle_id.to_string(),
-                    vehicle_type: vehicle_type.to_string(),
-                    gps: Some(gps),
-                    direction: direction.to_string(),
-                    speed,
-                    geo_hash: geo_hash.to_string(),
-                };
-                let mut buf = Vec::new();
-                result.encode(&mut buf)?;
-                let mut headers = HeaderMap::new();
-                headers.insert("proto/type", "vehicle-query-result");
-                ctx.parent
-                    .nats_client
-                    .publish_with_headers(req.reply_to.clone(), headers, buf.into())
-                    .await?;
-            } else {
-                let gps = crate::types::GpsCoordinates {
-                    lat: vehicle_lat,
-                    lon: vehicle_lon,
-                    alt: 0.0,
-                };
-                let result = crate::types::VehicleQueryResult {
-                    msg_type: "vehicle-query-result".to_string(),
-                    query_id: query.id.clone(),
-                    timestamp: datetime.to_rfc3339(),
-                    vehicle_id: vehicle_id.to_string(),
-                    vehicle_type: vehicle_type.to_string(),
-                    gps,
-                    direction: direction.to_string(),
-                    speed,
-                    geo_hash: geo_hash.to_string(),
-                };
-                let result_json = serde_json::to_vec(&result)?;
-                ctx.parent
-                    .nats_client
-                    .publish(req.reply_to.clone(), result_json.into())
-                    .await?;
+            let result = event.to_result(&query.id)?;
+            reporter.add_result(&result);
+            publish_vehicle_query_result(ctx, sink, result.clone()).await?;
+
+            if let Some(results) = cached_results.as_mut() {
+                cached_size += crate::cache::estimate_result_size(&result);
+                if cached_size <= cache_budget {
+                    results.push(result);
+                } else {
+                    cached_results = None;
+                }
             }
         }
-        if selected_row_count >= limit {
-            limit_reached = true;
-            break;
-        }
+        reporter.report_progress(batch_count, processed_row_count, selected_row_count);
         if start_time.elapsed().as_millis() >= query_timeout {
             has_timed_out = true;
         }
         if limit_reached || has_timed_out {
             break;
         }
+        if sink.is_closed() {
+            anyhow::bail!("The client disconnected before the end of the query");
+        }
     }
     let duration = start_time.elapsed();
     log::info!("Total rows processed: {}", processed_row_count);
     log::info!("Total rows selected: {}", selected_row_count);
 
+    let next_cursor = match (order_by, last_row_key) {
+        (Some(order_by), Some((timestamp, vehicle_id))) if limit_reached || has_timed_out => {
+            Some(crate::utils::pagination::encode_cursor(
+                &crate::utils::pagination::VehicleQueryCursor {
+                    order_by,
+                    timestamp,
+                    vehicle_id,
+                },
+            )?)
+        }
+        (Some(_), None) if has_timed_out => query.cursor.clone(),
+        _ => None,
+    };
+
     let respBody = crate::types::VehicleQueryResponse {
         msg_type: "vehicle-query-response".to_string(),
         processed_files_count: batch_count,
@@ -269,31 +262,259 @@ async fn execute_vehicle_query(
         selected_record_count: selected_row_count,
         distinct_vehicle_count: vehicle_ids.len(),
         elapsed_time_in_MS: duration.as_millis(),
-        timeout_expired: false,
+        timeout_expired: has_timed_out,
         limit_reached,
+        from_cache: false,
+        cursor: next_cursor,
+        duplicate_count,
     };
 
+    if let (Some(cache), Some(key), Some(results)) = (&ctx.query_cache, cache_key, cached_results)
+        && !has_timed_out
+    {
+        cache.insert(
+            key,
+            crate::cache::CachedVehicleQuery {
+                results,
+                response: respBody.clone(),
+                from_date: filter.from_date,
+                to_date: filter.to_date,
+                partitions,
+                size_in_bytes: cached_size,
+                started_at,
+            },
+        );
+    }
+
     Ok(respBody)
 }
 
+/// Publishes the selected rows as an Arrow IPC stream, one message per record batch,
+/// instead of one message per row.
+async fn stream_arrow_results(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleQueryRequest>,
+    reporter: &mut crate::query_events::VehicleQueryReporter,
+    df: DataFrame,
+    limit: usize,
+    query_timeout: u128,
+    order_by: Option<crate::types::VehicleQueryOrder>,
+) -> anyhow::Result<crate::types::VehicleQueryResponse> {
+    let labels: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
+    let query = &req.body;
+    let has_duplicate_rank = df
+        .schema()
+        .has_column_with_unqualified_name("duplicate_rank");
+    let df = crate::query::plain_result_columns(df)?;
+    let result_columns: Vec<usize> = (0..crate::query::RESULT_COLUMN_COUNT).collect();
+    let schema = df.schema().as_arrow().project(&result_columns)?;
+
+    let start_time = Instant::now();
+    let mut processed_row_count: usize = 0;
+    let mut selected_row_count: usize = 0;
+    let mut batch_count: usize = 0;
+    let mut total_bytes: usize = 0;
+    let mut limit_reached = false;
+    let mut has_timed_out = false;
+    let mut duplicate_count: usize = 0;
+    let mut vehicle_ids = HashSet::new();
+    let mut last_row_key: Option<(i64, String)> = None;
+
+    let mut publisher = crate::utils::arrow_stream::ArrowStreamPublisher::try_new(
+        &ctx.parent.nats_client,
+        &req.reply_to,
+        &schema,
+    )
+    .await?;
+    // The end-of-stream marker is sent on failure too, so that the readers do not wait for it
+    let streamed = async {
+        let mut stream = df.execute_stream().await?;
+        while let Some(batch_result) = stream.next().await {
+            let mut batch = batch_result?;
+            batch_count += 1;
+            processed_row_count += batch.num_rows();
+            total_bytes += batch.get_array_memory_size();
+
+            if has_duplicate_rank {
+                let removed_count;
+                (batch, removed_count) =
+                    remove_duplicates(&batch, crate::query::RESULT_COLUMN_COUNT)?;
+                duplicate_count += removed_count;
+            }
+            let mut batch = batch.project(&result_columns)?;
+            if selected_row_count + batch.num_rows() > limit {
+                batch = batch.slice(0, limit - selected_row_count);
+                limit_reached = true;
+            }
+            if batch.num_rows() > 0 {
+                selected_row_count += batch.num_rows();
+                let ids = as_string_array(batch.column(1))?;
+                vehicle_ids.extend(ids.iter().flatten().map(|id| id.to_string()));
+                if order_by.is_some() {
+                    let last = batch.num_rows() - 1;
+                    let timestamps = as_timestamp_millisecond_array(batch.column(0))?;
+                    last_row_key = Some((timestamps.value(last), ids.value(last).to_string()));
+                }
+                ctx.parent
+                    .prometheus_counters
+                    .vehicles_search_processed_events_total_counter
+                    .with(&labels)
+                    .inc_by(batch.num_rows() as u64);
+                publisher.write(&batch).await?;
+            }
+            reporter.report_progress(batch_count, processed_row_count, selected_row_count);
+            if start_time.elapsed().as_millis() >= query_timeout {
+                has_timed_out = true;
+            }
+            if limit_reached || has_timed_out {
+                break;
+            }
+        }
+        anyhow::Ok(())
+    }
+    .await;
+    match streamed {
+        Ok(()) => publisher.finish().await?,
+        Err(e) => {
+            if let Err(publish_error) = publisher.abort(&e).await {
+                log::warn!("Failed to end the Arrow stream: {}", publish_error);
+            }
+            return Err(e);
+        }
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total rows processed: {}", processed_row_count);
+    log::info!("Total rows selected: {}", selected_row_count);
+
+    let next_cursor = match (order_by, last_row_key) {
+        (Some(order_by), Some((timestamp, vehicle_id))) if limit_reached || has_timed_out => {
+            Some(crate::utils::pagination::encode_cursor(
+                &crate::utils::pagination::VehicleQueryCursor {
+                    order_by,
+                    timestamp,
+                    vehicle_id,
+                },
+            )?)
+        }
+        (Some(_), None) if has_timed_out => query.cursor.clone(),
+        _ => None,
+    };
+
+    Ok(crate::types::VehicleQueryResponse {
+        msg_type: "vehicle-query-response".to_string(),
+        processed_files_count: batch_count,
+        processed_bytes: total_bytes,
+        processed_record_count: processed_row_count,
+        selected_record_count: selected_row_count,
+        distinct_vehicle_count: vehicle_ids.len(),
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+        limit_reached,
+        from_cache: false,
+        cursor: next_cursor,
+        duplicate_count,
+    })
+}
+
+/// Keeps the rows ranked first by the `duplicate_rank` window, returning the number of removed rows.
+pub fn remove_duplicates(
+    batch: &datafusion::arrow::array::RecordBatch,
+    rank_column: usize,
+) -> anyhow::Result<(datafusion::arrow::array::RecordBatch, usize)> {
+    let ranks = as_uint64_array(batch.column(rank_column))?;
+    let first = datafusion::arrow::compute::kernels::cmp::eq(
+        ranks,
+        &datafusion::arrow::array::UInt64Array::new_scalar(1),
+    )?;
+    let removed_count = batch.num_rows() - first.true_count();
+    Ok((
+        datafusion::arrow::compute::filter_record_batch(batch, &first)?,
+        removed_count,
+    ))
+}
+
+async fn replay_cached_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleQueryRequest>,
+    sink: &VehicleQuerySink,
+    reporter: &mut crate::query_events::VehicleQueryReporter,
+    cached: &crate::cache::CachedVehicleQuery,
+) -> anyhow::Result<crate::types::VehicleQueryResponse> {
+    let start_time = Instant::now();
+    for cached_result in cached.results.iter() {
+        let mut result = cached_result.clone();
+        result.query_id = req.body.id.clone();
+        reporter.add_result(&result);
+        publish_vehicle_query_result(ctx, sink, result).await?;
+    }
+    reporter.flush_results();
+    let mut respBody = cached.response.clone();
+    respBody.elapsed_time_in_MS = start_time.elapsed().as_millis();
+    respBody.from_cache = true;
+    Ok(respBody)
+}
+
+async fn publish_vehicle_query_result(
+    ctx: &crate::contexts::DataHandlerContext,
+    sink: &VehicleQuerySink,
+    result: crate::types::VehicleQueryResult,
+) -> anyhow::Result<()> {
+    match sink {
+        VehicleQuerySink::Nats(reply_to) => {
+            // the results are always encoded with protobuf when it is enabled, as all the clients support it
+            let (headers, payload) =
+                crate::utils::messaging::encode_message(&result, ctx.parent.enable_proto_buf)?;
+            crate::utils::messaging::publish_payload(
+                &ctx.parent.nats_client,
+                reply_to.to_string(),
+                headers,
+                payload,
+            )
+            .await?;
+        }
+        VehicleQuerySink::Channel(sender) => {
+            if sender
+                .send(VehicleQueryOutput::Result(result))
+                .await
+                .is_err()
+            {
+                anyhow::bail!("The client disconnected before the end of the query");
+            }
+        }
+    }
+    anyhow::Ok(())
+}
+
 async fn process_search_request(
     ctx: crate::contexts::DataHandlerContext,
     req: crate::types::Request<crate::types::VehicleQueryRequest>,
 ) -> anyhow::Result<()> {
     log::info!("Received NATS request: {:?}", req);
+    let sink = VehicleQuerySink::Nats(req.reply_to.clone());
+    run_vehicle_query(ctx, req, sink).await
+}
 
+/// Runs a vehicle query received over NATS or HTTP, publishing its lifecycle events.
+pub async fn run_vehicle_query(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleQueryRequest>,
+    sink: VehicleQuerySink,
+) -> anyhow::Result<()> {
     let start = crate::types::VehicleQueryStartedEvent {
         msg_type: "vehicle-query-started".to_string(),
         query: req.clone(),
     };
-    let msg_json = serde_json::to_vec(&start)?;
-    ctx.parent
-        .nats_client
-        .publish("events.vehicles.query.started", msg_json.into())
-        .await?;
+    crate::utils::messaging::publish_event(
+        &ctx.parent.nats_client,
+        "events.vehicles.query.started".to_string(),
+        &start,
+    )
+    .await?;
+    let mut reporter =
+        crate::query_events::VehicleQueryReporter::new(ctx.query_events.start(start), &req.body.id);
 
     let resp: crate::types::Response<crate::types::VehicleQueryResponse> =
-        match execute_vehicle_query(&ctx, &req).await {
+        match execute_vehicle_query(&ctx, &req, &sink, &mut reporter).await {
             Ok(respBody) => {
                 log::info!("Vehicle query executed successfully");
                 crate::types::Response::Success {
@@ -314,12 +535,8 @@ async fn process_search_request(
             }
         };
 
-    let resp_json = serde_json::to_vec(&resp)?;
-    log::info!("Sending NATS response: {:?}", resp);
-    ctx.parent
-        .nats_client
-        .publish(req.reply_to.clone(), resp_json.into())
-        .await?;
+    log::info!("Sending vehicle query response: {:?}", resp);
+    let sent = sink.send_response(&ctx, &resp).await;
 
     let stop = match resp {
         crate::types::Response::Success { body, .. } => crate::types::VehicleQueryStoppedEvent {
@@ -337,11 +554,15 @@ async fn process_search_request(
             error,
         },
     };
-    let msg_json = serde_json::to_vec(&stop)?;
-    ctx.parent
-        .nats_client
-        .publish("events.vehicles.query.stopped", msg_json.into())
-        .await?;
+    // the subscribers are released even when the response could not be sent
+    ctx.query_events.stop(stop.clone());
+    sent?;
+    crate::utils::messaging::publish_event(
+        &ctx.parent.nats_client,
+        "events.vehicles.query.stopped".to_string(),
+        &stop,
+    )
+    .await?;
     Ok(())
 }
 
@@ -371,21 +592,79 @@ pub async fn process_generation_requests(
     mut ctx: crate::contexts::DataHandlerContext,
     _req: crate::types::VehicleGenerationStopped,
 ) -> anyhow::Result<()> {
-    let session = create_session_context(&ctx.config).await?;
+    let session = create_session_context(&ctx.config, ctx.hot_tier.clone()).await?;
     ctx.set_session(session);
     log::warn!("Created a brand new session context after the new generation completed.");
+    if let Some(cache) = &ctx.query_cache {
+        let count = cache.clear();
+        log::info!(
+            "Cleared {} cached queries after the new generation completed.",
+            count
+        );
+    }
+    anyhow::Ok(())
+}
+
+pub fn subscribe_to_aggregate_period_events(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    if ctx.query_cache.is_none() {
+        return anyhow::Ok(());
+    }
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "events.vehicles.aggregate-period.created".to_string(),
+        process_aggregate_period_events,
+    );
+    anyhow::Ok(())
+}
+
+pub async fn process_aggregate_period_events(
+    ctx: crate::contexts::DataHandlerContext,
+    event: crate::types::AggregatePeriodCreated,
+) -> anyhow::Result<()> {
+    if let Some(cache) = &ctx.query_cache {
+        // The partition key of the event is its time window, the geohash cells are in the partition stats
+        let partition_keys: std::collections::HashSet<String> = event
+            .partitions
+            .iter()
+            .map(|partition| partition.partition_key.clone())
+            .collect();
+        let count = cache.invalidate_partitions(
+            partition_keys,
+            crate::utils::time::parse_datetime(&event.from_time)?,
+            crate::utils::time::parse_datetime(&event.to_time)?,
+        );
+        if count > 0 {
+            log::debug!(
+                "Invalidated {} cached queries after new data landed in period {}",
+                count,
+                event.partition_key
+            );
+        }
+    }
     anyhow::Ok(())
 }
 
 pub async fn create_session_context(
     config: &Arc<crate::config::Config>,
+    hot_tier: Option<Arc<crate::hot_tier::HotTier>>,
 ) -> anyhow::Result<SessionContext> {
     let prefix = url::Url::parse("events:///").unwrap();
     let (format, store) = build_object_store(config)?;
+    // The exported files may be stored within the data folder, see build_export_store
+    let exports_folder = object_store::path::Path::from(config.finder.exports.folder.as_str());
+
+    let geo_metadata = match format.as_str() {
+        "parquet" => crate::utils::geoparquet::read_geo_metadata(&store, &exports_folder).await?,
+        _ => None,
+    };
 
     let ctx = SessionContext::new();
+    ctx.register_udf(crate::query::within_geometry_udf());
+    ctx.register_udf(crate::query::distance_udf());
     let session_state = ctx.state();
-    ctx.register_object_store(&prefix, store);
+    ctx.register_object_store(&prefix, store.clone());
 
     log::info!("Using prefix: {}", prefix);
     let table_path = ListingTableUrl::parse(prefix)?;
@@ -419,15 +698,56 @@ pub async fn create_session_context(
             ("pk".to_string(), DataType::Utf8),
         ]);
 
+    let files: Vec<ObjectMeta> = table_path
+        .list_all_files(
+            &session_state,
+            store.as_ref(),
+            &listing_options.file_extension,
+        )
+        .await?
+        // Empty files cannot affect the schema but may fail to be read
+        .try_filter(|file| {
+            future::ready(file.size > 0 && !file.location.prefix_matches(&exports_folder))
+        })
+        .try_collect()
+        .await?;
     let resolved_schema = listing_options
-        .infer_schema(&session_state, &table_path)
+        .format
+        .infer_schema(&session_state, &store, &files)
         .await?;
     let config = ListingTableConfig::new(table_path)
         .with_listing_options(listing_options)
         .with_schema(resolved_schema);
 
-    let provider = Arc::new(ListingTable::try_new(config)?);
-    ctx.register_table("events", provider)?;
+    let mut provider: Arc<dyn TableProvider> = Arc::new(ListingTable::try_new(config)?);
+    if let Some(geo_metadata) = geo_metadata {
+        // The GeoParquet files are exposed with the same columns as the other files
+        ctx.register_table("geoparquet_events", provider)?;
+        provider = crate::utils::geoparquet::with_point_columns(
+            ctx.table("geoparquet_events").await?,
+            &geo_metadata,
+        )?
+        .into_view();
+    }
+    match hot_tier {
+        Some(hot_tier) => {
+            // The events not flushed yet by the collectors are merged with the files
+            let schema = provider.schema();
+            ctx.register_table("flushed_events", provider)?;
+            ctx.register_table(
+                "recent_events",
+                Arc::new(crate::hot_tier::HotTierTable::new(hot_tier, schema)),
+            )?;
+            let events = ctx
+                .table("flushed_events")
+                .await?
+                .union(ctx.table("recent_events").await?)?;
+            ctx.register_table("events", events.into_view())?;
+        }
+        None => {
+            ctx.register_table("events", provider)?;
+        }
+    }
 
     Ok(ctx)
 }
@@ -493,6 +813,30 @@ pub fn build_object_store(
     }
 }
 
+/// Builds the store where the exported files are written, in the storage of the collector,
+/// next to the data folder.
+pub fn build_export_store(
+    config: &Arc<crate::config::Config>,
+) -> anyhow::Result<Arc<dyn ObjectStore>> {
+    let export_folder = &config.finder.exports.folder;
+    match &config.collector.output.storage {
+        crate::config::StorageConfig::FileStorageConfig { folder } => {
+            let data_folder = build_data_folder_path(folder, "DATA_FOLDER")?.join(export_folder);
+            std::fs::create_dir_all(&data_folder)?;
+            log::info!("Using export folder: {}", data_folder.display());
+            let local_store = object_store::local::LocalFileSystem::new_with_prefix(data_folder)?;
+            Ok(Arc::new(local_store))
+        }
+        _ => {
+            let (_, store) = build_object_store(config)?;
+            Ok(Arc::new(object_store::prefix::PrefixStore::new(
+                store,
+                export_folder.as_str(),
+            )))
+        }
+    }
+}
+
 pub fn build_data_folder_path(folder: &String, env_var_name: &str) -> anyhow::Result<PathBuf> {
     let data_folder = match env::var(env_var_name) {
         Ok(s) => PathBuf::from(s),
@@ -535,3 +879,228 @@ pub fn parse_key_value_string(input: &str) -> anyhow::Result<HashMap<String, Str
 
     Ok(map)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+
+    fn event(vehicle_id: &'static str, timestamp: &'static str) -> TestEvent<'static> {
+        TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp,
+            lon: 4.35,
+            lat: 50.85,
+        }
+    }
+
+    /// Runs the vehicle query over the events, and returns its results and its response.
+    async fn run_query(
+        events: &[TestEvent<'_>],
+        query: serde_json::Value,
+    ) -> anyhow::Result<(
+        Vec<crate::types::VehicleQueryResult>,
+        crate::types::VehicleQueryResponse,
+    )> {
+        let ctx = crate::contexts::tests::data_handler_context(
+            crate::contexts::tests::test_config()?,
+            crate::query::tests::events_session(events)?,
+        )
+        .await?;
+        let req = crate::types::Request {
+            id: "r1".to_string(),
+            msg_type: "request".to_string(),
+            reply_to: String::new(),
+            parent_id: None,
+            expires_at: None,
+            timeout: None,
+            body: serde_json::from_value(query)?,
+        };
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(100);
+        run_vehicle_query(ctx, req, VehicleQuerySink::Channel(sender)).await?;
+        let mut results = vec![];
+        while let Some(output) = receiver.recv().await {
+            match output {
+                VehicleQueryOutput::Result(result) => results.push(result),
+                VehicleQueryOutput::Response(crate::types::Response::Success { body, .. }) => {
+                    return Ok((results, body));
+                }
+                VehicleQueryOutput::Response(crate::types::Response::Error { error, .. }) => {
+                    anyhow::bail!("The query failed: {:?}", error);
+                }
+            }
+        }
+        anyhow::bail!("The query sent no response")
+    }
+
+    fn timestamps(results: &[crate::types::VehicleQueryResult]) -> Vec<(&str, &str)> {
+        results
+            .iter()
+            .map(|result| (result.vehicle_id.as_str(), result.timestamp.as_str()))
+            .collect()
+    }
+
+    fn events_with_duplicates() -> Vec<TestEvent<'static>> {
+        vec![
+            event("a", "2024-01-01T10:00:00Z"),
+            event("b", "2024-01-01T10:00:00Z"),
+            event("a", "2024-01-01T10:00:01Z"),
+            event("a", "2024-01-01T10:00:00Z"),
+            event("a", "2024-01-01T10:00:01Z"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn skips_the_adjacent_duplicates_of_the_ordered_results() -> anyhow::Result<()> {
+        let (results, resp) = run_query(
+            &events_with_duplicates(),
+            serde_json::json!({
+                "type": "vehicle-query-request",
+                "id": "q1",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "vehicleIds": ["a", "b"],
+                "orderBy": "vehicleIdTimestamp",
+                "deduplicate": true,
+            }),
+        )
+        .await?;
+
+        assert_eq!(
+            timestamps(&results),
+            vec![
+                ("a", "2024-01-01T10:00:00+00:00"),
+                ("a", "2024-01-01T10:00:01+00:00"),
+                ("b", "2024-01-01T10:00:00+00:00"),
+            ]
+        );
+        assert_eq!(resp.selected_record_count, 3);
+        assert_eq!(resp.duplicate_count, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn removes_the_ranked_duplicates_of_the_unordered_results() -> anyhow::Result<()> {
+        let (results, resp) = run_query(
+            &events_with_duplicates(),
+            serde_json::json!({
+                "type": "vehicle-query-request",
+                "id": "q1",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "vehicleIds": ["a", "b"],
+                "deduplicate": true,
+            }),
+        )
+        .await?;
+
+        let mut selected = timestamps(&results);
+        selected.sort();
+        assert_eq!(
+            selected,
+            vec![
+                ("a", "2024-01-01T10:00:00+00:00"),
+                ("a", "2024-01-01T10:00:01+00:00"),
+                ("b", "2024-01-01T10:00:00+00:00"),
+            ]
+        );
+        assert_eq!(resp.duplicate_count, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_the_rows_ranked_first() -> anyhow::Result<()> {
+        use datafusion::arrow::array::{RecordBatch, StringArray, UInt64Array};
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "vehicleId",
+                Arc::new(StringArray::from(vec!["a", "a", "b", "a"])) as _,
+            ),
+            (
+                "duplicate_rank",
+                Arc::new(UInt64Array::from(vec![1, 2, 1, 3])) as _,
+            ),
+        ])?;
+
+        let (batch, removed_count) = remove_duplicates(&batch, 1)?;
+
+        assert_eq!(removed_count, 2);
+        assert_eq!(
+            as_string_array(batch.column(0))?.iter().collect::<Vec<_>>(),
+            vec![Some("a"), Some("b")]
+        );
+        Ok(())
+    }
+
+    /// Samples the moves of a vehicle, at 10:00:00, 10:00:20 and 10:01:10, with the given method.
+    async fn sample(method: &str) -> anyhow::Result<Vec<(String, f64)>> {
+        let moves = [
+            ("2024-01-01T10:00:00Z", 50.8),
+            ("2024-01-01T10:00:20Z", 50.9),
+            ("2024-01-01T10:01:10Z", 51.0),
+        ];
+        let events = moves
+            .iter()
+            .map(|(timestamp, lat)| TestEvent {
+                lat: *lat,
+                ..event("a", timestamp)
+            })
+            .collect::<Vec<_>>();
+        let (results, _) = run_query(
+            &events,
+            serde_json::json!({
+                "type": "vehicle-query-request",
+                "id": "q1",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "vehicleIds": ["a"],
+                "orderBy": "timestampAsc",
+                "sampleIntervalInSecs": 60,
+                "sampleMethod": method,
+            }),
+        )
+        .await?;
+        Ok(results
+            .into_iter()
+            .map(|result| (result.timestamp, (result.gps.lat * 1000.0).round() / 1000.0))
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn samples_the_first_move_of_each_interval() -> anyhow::Result<()> {
+        assert_eq!(
+            sample("first").await?,
+            vec![
+                ("2024-01-01T10:00:00+00:00".to_string(), 50.8),
+                ("2024-01-01T10:01:10+00:00".to_string(), 51.0),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn samples_the_last_move_of_each_interval() -> anyhow::Result<()> {
+        assert_eq!(
+            sample("last").await?,
+            vec![
+                ("2024-01-01T10:00:20+00:00".to_string(), 50.9),
+                ("2024-01-01T10:01:10+00:00".to_string(), 51.0),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn samples_the_average_position_of_each_interval() -> anyhow::Result<()> {
+        assert_eq!(
+            sample("average").await?,
+            vec![
+                ("2024-01-01T10:00:00+00:00".to_string(), 50.85),
+                ("2024-01-01T10:01:10+00:00".to_string(), 51.0),
+            ]
+        );
+        Ok(())
+    }
+}
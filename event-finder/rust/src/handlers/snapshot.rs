@@ -0,0 +1,204 @@
+use crate::query::{VehicleFix, collect_fixes, first_fix_per_vehicle};
+use chrono::{DateTime, Duration, Utc};
+use datafusion::prelude::*;
+use log;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+async fn execute_snapshot_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleSnapshotRequest>,
+) -> anyhow::Result<crate::types::VehicleSnapshotResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let timestamp: DateTime<Utc> = query.timestamp.parse()?;
+    let look_back = Duration::minutes(
+        query
+            .look_back_in_min
+            .unwrap_or(ctx.config.partitioning.timePartition.aggregationPeriodInMin)
+            .into(),
+    );
+    let interpolate = query.interpolate.unwrap_or(false);
+    let to_date = if interpolate {
+        timestamp + look_back
+    } else {
+        timestamp + Duration::milliseconds(1)
+    };
+
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    // Only the partitions surrounding the snapshot time are scanned
+    let mut filter = crate::query::VehicleFilter::with_range(timestamp - look_back, to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+    let partitions = filter.partitions();
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, partitions.as_ref())?;
+    let snapshot_time = crate::query::timestamp_literal(df.schema(), timestamp)?;
+    let look_back_time = crate::query::timestamp_literal(df.schema(), timestamp - look_back)?;
+    let look_ahead_time = crate::query::timestamp_literal(df.schema(), timestamp + look_back)?;
+
+    let start_time = Instant::now();
+    let before_df = df
+        .clone()
+        .filter(col("timestamp").lt_eq(snapshot_time.clone()))?
+        .filter(col("timestamp").gt_eq(look_back_time))?;
+    let (before, mut has_timed_out) = collect_fixes(
+        first_fix_per_vehicle(before_df, true)?,
+        &start_time,
+        query_timeout,
+    )
+    .await?;
+    let after = if interpolate && !has_timed_out {
+        let after_df = df
+            .filter(col("timestamp").gt(snapshot_time))?
+            .filter(col("timestamp").lt_eq(look_ahead_time))?;
+        let (after, after_timed_out) = collect_fixes(
+            first_fix_per_vehicle(after_df, false)?,
+            &start_time,
+            query_timeout,
+        )
+        .await?;
+        has_timed_out = after_timed_out;
+        after
+    } else {
+        HashMap::new()
+    };
+
+    let mut vehicle_count: usize = 0;
+    let mut interpolated_vehicle_count: usize = 0;
+    let ordered: BTreeMap<String, VehicleFix> = before.into_iter().collect();
+    for (vehicle_id, fix) in ordered.into_iter() {
+        let (fix, interpolated) = match after.get(&vehicle_id) {
+            Some(next) => (fix.interpolate(next, timestamp.timestamp_millis()), true),
+            None => (fix, false),
+        };
+        if !filter.contains_position(fix.lon, fix.lat) {
+            continue;
+        }
+        vehicle_count += 1;
+        if interpolated {
+            interpolated_vehicle_count += 1;
+        }
+        let result = crate::types::VehicleSnapshotResult {
+            msg_type: "vehicle-snapshot-result".to_string(),
+            query_id: query.id.clone(),
+            timestamp: crate::utils::time::format_timestamp_millis(fix.timestamp)?,
+            vehicle_id,
+            vehicle_type: fix.vehicle_type,
+            gps: crate::types::GpsCoordinates {
+                lat: fix.lat,
+                lon: fix.lon,
+                alt: 0.0,
+            },
+            direction: fix.direction,
+            speed: fix.speed,
+            interpolated,
+        };
+        crate::utils::messaging::publish_message(
+            &ctx.parent.nats_client,
+            req.reply_to.clone(),
+            &result,
+        )
+        .await?;
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total vehicles in snapshot: {}", vehicle_count);
+
+    Ok(crate::types::VehicleSnapshotResponse {
+        msg_type: "vehicle-snapshot-response".to_string(),
+        vehicle_count,
+        interpolated_vehicle_count,
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+    })
+}
+
+async fn process_snapshot_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleSnapshotRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_snapshot_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle snapshot query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_snapshot_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.snapshot".to_string(),
+        process_snapshot_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+
+    fn event(vehicle_id: &'static str, timestamp: &'static str, lat: f64) -> TestEvent<'static> {
+        TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp,
+            lon: 4.35,
+            lat,
+        }
+    }
+
+    async fn snapshot(interpolate: bool) -> anyhow::Result<crate::types::VehicleSnapshotResponse> {
+        let ctx = crate::contexts::tests::data_handler_context(
+            crate::contexts::tests::test_config()?,
+            crate::query::tests::events_session(&[
+                event("a", "2024-01-01T10:00:00Z", 50.85),
+                event("a", "2024-01-01T10:02:00Z", 50.87),
+                event("b", "2024-01-01T09:55:00Z", 50.80),
+                // too old to be in the snapshot
+                event("c", "2024-01-01T09:40:00Z", 50.80),
+            ])?,
+        )
+        .await?;
+        let req = crate::contexts::tests::request(serde_json::json!({
+            "type": "vehicle-snapshot-request",
+            "id": "snapshot-1",
+            "timestamp": "2024-01-01T10:01:00Z",
+            "lookBackInMin": 10,
+            "interpolate": interpolate,
+        }))?;
+        execute_snapshot_query(&ctx, &req).await
+    }
+
+    #[tokio::test]
+    async fn interpolates_the_positions_of_the_vehicles_moving_after_the_snapshot()
+    -> anyhow::Result<()> {
+        let latest = snapshot(false).await?;
+        let interpolated = snapshot(true).await?;
+
+        assert_eq!(
+            (latest.vehicle_count, latest.interpolated_vehicle_count),
+            (2, 0)
+        );
+        assert_eq!(
+            (
+                interpolated.vehicle_count,
+                interpolated.interpolated_vehicle_count
+            ),
+            (2, 1)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, TimeZone, Utc};
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::{DataType, TimeUnit};
+use datafusion::arrow::ipc::writer::StreamWriter;
+use datafusion::common::ScalarValue;
+use datafusion::functions::expr_fn::{asin, coalesce, cos, radians, sin, sqrt};
+use datafusion::functions_aggregate::expr_fn::{avg, count, count_distinct, max, sum};
+use datafusion::functions_window::expr_fn::lag;
+use datafusion::logical_expr::ExprFunctionExt;
+use datafusion::prelude::*;
+use log;
+use std::time::{Duration, Instant};
+
+// Same radius as the haversine distance of the geo crate.
+const MEAN_EARTH_RADIUS_IN_METERS: f64 = 6_371_008.8;
+// Keeps each result message well below the max payload of NATS.
+const MAX_ROWS_PER_MESSAGE: usize = 5000;
+
+/// Distance between the previous position of the vehicle and the current one, using the haversine formula.
+fn segment_distance_expr() -> Expr {
+    let lat1 = radians(col("prev_lat"));
+    let lat2 = radians(col("gps_lat"));
+    let half_delta_lat = (lat2.clone() - lat1.clone()) / lit(2.0);
+    let half_delta_lon = (radians(col("gps_lon")) - radians(col("prev_lon"))) / lit(2.0);
+    let a = sin(half_delta_lat.clone()) * sin(half_delta_lat)
+        + cos(lat1) * cos(lat2) * sin(half_delta_lon.clone()) * sin(half_delta_lon);
+    coalesce(vec![
+        lit(2.0 * MEAN_EARTH_RADIUS_IN_METERS) * asin(sqrt(a)),
+        lit(0.0),
+    ])
+}
+
+fn previous_position_expr(column: &str, alias: &str) -> anyhow::Result<Expr> {
+    Ok(lag(col(column), Some(1), None)
+        .partition_by(vec![col(r#""vehicleId""#)])
+        .order_by(vec![col("timestamp").sort(true, false)])
+        .build()?
+        .alias(alias))
+}
+
+fn json_value(value: ScalarValue) -> serde_json::Value {
+    match value {
+        ScalarValue::TimestampMillisecond(Some(timestamp), _) => Utc
+            .timestamp_millis_opt(timestamp)
+            .single()
+            .map(|datetime| datetime.to_rfc3339().into())
+            .unwrap_or(serde_json::Value::Null),
+        ScalarValue::Int64(Some(value)) => value.into(),
+        ScalarValue::Float64(Some(value)) => value.into(),
+        ScalarValue::Utf8(Some(value)) => value.into(),
+        _ => serde_json::Value::Null,
+    }
+}
+
+fn to_rows(batch: &RecordBatch) -> anyhow::Result<Vec<Vec<serde_json::Value>>> {
+    let mut rows = Vec::with_capacity(batch.num_rows());
+    for i in 0..batch.num_rows() {
+        let mut row = Vec::with_capacity(batch.num_columns());
+        for column in batch.columns() {
+            row.push(json_value(ScalarValue::try_from_array(column, i)?));
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn to_arrow_ipc(batch: &RecordBatch) -> anyhow::Result<String> {
+    let mut writer = StreamWriter::try_new(Vec::new(), &batch.schema())?;
+    writer.write(batch)?;
+    writer.finish()?;
+    Ok(STANDARD.encode(writer.into_inner()?))
+}
+
+async fn execute_stats_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleStatsRequest>,
+) -> anyhow::Result<crate::types::VehicleStatsResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let format = query.format.unwrap_or(crate::types::TableFormat::Json);
+    let bucket_interval = query
+        .bucket_interval_in_min
+        .unwrap_or(ctx.config.partitioning.timePartition.aggregationPeriodInMin);
+    let from_date: DateTime<Utc> = query.from_date.parse()?;
+    let to_date: DateTime<Utc> = query.to_date.parse()?;
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    let mut filter = crate::query::VehicleFilter::with_range(from_date, to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+
+    let partitions = filter.partitions();
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, partitions.as_ref())?;
+    let time_range = crate::query::time_range_predicate(df.schema(), from_date, to_date)?;
+    df = df.filter(time_range)?;
+    if let Some(predicate) = filter.area_predicate() {
+        df = df.filter(predicate)?;
+    }
+    // Each segment between two consecutive positions counts in the bucket of its end
+    df = df.window(vec![
+        previous_position_expr("gps_lat", "prev_lat")?,
+        previous_position_expr("gps_lon", "prev_lon")?,
+    ])?;
+
+    let mut group_exprs = vec![
+        crate::query::time_bucket_expr(df.schema(), bucket_interval)?.alias("bucket"),
+        cast(col(r#""vehicleType""#), DataType::Utf8).alias("vehicle_type"),
+    ];
+    if query.group_by_zone.unwrap_or(false) {
+        let zones = crate::query::map_zones(&ctx.config.generator)?;
+        group_exprs.push(crate::query::zone_expr(&zones)?.alias("zone"));
+    }
+    let mut columns = vec![
+        cast(
+            col("bucket"),
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+        )
+        .alias("bucketStart"),
+        col("vehicle_type").alias("vehicleType"),
+    ];
+    let mut sort_exprs = vec![
+        col("bucket").sort(true, false),
+        col("vehicle_type").sort(true, false),
+    ];
+    if group_exprs.len() > 2 {
+        columns.push(col("zone"));
+        sort_exprs.push(col("zone").sort(true, true));
+    }
+    columns.extend([
+        col("active_vehicle_count").alias("activeVehicleCount"),
+        col("event_count").alias("eventCount"),
+        col("avg_speed").alias("avgSpeed"),
+        col("max_speed").alias("maxSpeed"),
+        col("distance").alias("distanceInMeters"),
+    ]);
+    df = df
+        .aggregate(
+            group_exprs,
+            vec![
+                count_distinct(col(r#""vehicleId""#)).alias("active_vehicle_count"),
+                count(lit(1)).alias("event_count"),
+                avg(col("speed")).alias("avg_speed"),
+                max(col("speed")).alias("max_speed"),
+                sum(segment_distance_expr()).alias("distance"),
+            ],
+        )?
+        .sort(sort_exprs)?
+        .select(columns)?;
+
+    let start_time = Instant::now();
+    // The aggregation only yields its rows once all the events have been read
+    let (batches, has_timed_out) = match tokio::time::timeout(
+        Duration::from_millis(query_timeout.try_into()?),
+        df.collect(),
+    )
+    .await
+    {
+        Ok(batches) => (batches?, false),
+        Err(_) => (vec![], true),
+    };
+
+    let mut row_count: usize = 0;
+    for batch in batches.iter() {
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            let chunk = batch.slice(offset, MAX_ROWS_PER_MESSAGE.min(batch.num_rows() - offset));
+            offset += chunk.num_rows();
+            row_count += chunk.num_rows();
+            let result = crate::types::VehicleStatsResult {
+                msg_type: "vehicle-stats-result".to_string(),
+                query_id: query.id.clone(),
+                columns: chunk
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().clone())
+                    .collect(),
+                rows: match format {
+                    crate::types::TableFormat::Json => to_rows(&chunk)?,
+                    crate::types::TableFormat::Arrow => vec![],
+                },
+                arrow_ipc: match format {
+                    crate::types::TableFormat::Json => None,
+                    crate::types::TableFormat::Arrow => Some(to_arrow_ipc(&chunk)?),
+                },
+            };
+            crate::utils::messaging::publish_message(
+                &ctx.parent.nats_client,
+                req.reply_to.clone(),
+                &result,
+            )
+            .await?;
+        }
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total stats rows: {}", row_count);
+
+    Ok(crate::types::VehicleStatsResponse {
+        msg_type: "vehicle-stats-response".to_string(),
+        row_count,
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+    })
+}
+
+async fn process_stats_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleStatsRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_stats_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle stats query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_stats_requests(ctx: crate::contexts::DataHandlerContext) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.stats".to_string(),
+        process_stats_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+    use datafusion::arrow::array::{Float64Array, StringArray, TimestampMillisecondArray};
+    use std::sync::Arc;
+
+    fn event(
+        vehicle_id: &'static str,
+        vehicle_type: &'static str,
+        timestamp: &'static str,
+    ) -> TestEvent<'static> {
+        TestEvent {
+            vehicle_id,
+            vehicle_type,
+            timestamp,
+            lon: 4.35,
+            lat: 50.85,
+        }
+    }
+
+    #[tokio::test]
+    async fn aggregates_one_row_per_bucket_and_vehicle_type() -> anyhow::Result<()> {
+        let ctx = crate::contexts::tests::data_handler_context(
+            crate::contexts::tests::test_config()?,
+            crate::query::tests::events_session(&[
+                event("a", "taxi", "2024-01-01T10:00:00Z"),
+                event("b", "bus", "2024-01-01T10:02:00Z"),
+                event("a", "taxi", "2024-01-01T10:05:00Z"),
+                event("a", "taxi", "2024-01-01T10:15:00Z"),
+            ])?,
+        )
+        .await?;
+        let req = crate::contexts::tests::request(serde_json::json!({
+            "type": "vehicle-stats-request",
+            "id": "stats-1",
+            "fromDate": "2024-01-01T10:00:00Z",
+            "toDate": "2024-01-01T11:00:00Z",
+            "bucketIntervalInMin": 10,
+        }))?;
+
+        let resp = execute_stats_query(&ctx, &req).await?;
+
+        assert_eq!(resp.row_count, 3);
+        assert!(!resp.timeout_expired);
+        Ok(())
+    }
+
+    #[test]
+    fn converts_the_rows_to_json_values() -> anyhow::Result<()> {
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "bucketStart",
+                Arc::new(TimestampMillisecondArray::from(vec![1_704_103_200_000])) as _,
+            ),
+            (
+                "vehicleType",
+                Arc::new(StringArray::from(vec!["taxi"])) as _,
+            ),
+            (
+                "avgSpeed",
+                Arc::new(Float64Array::from(vec![Some(30.0)])) as _,
+            ),
+            (
+                "distanceInMeters",
+                Arc::new(Float64Array::from(vec![None])) as _,
+            ),
+        ])?;
+
+        assert_eq!(
+            to_rows(&batch)?,
+            vec![vec![
+                serde_json::json!("2024-01-01T10:00:00+00:00"),
+                serde_json::json!("taxi"),
+                serde_json::json!(30.0),
+                serde_json::Value::Null,
+            ]]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use crate::utils::stops::{Stop, StopCriteria, StopDetector};
+use chrono::{DateTime, Utc};
+use datafusion::prelude::*;
+use futures_util::StreamExt;
+use log;
+use std::time::Instant;
+
+fn stop_result(
+    query_id: &str,
+    vehicle_id: &str,
+    vehicle_type: &str,
+    stop: &Stop,
+) -> anyhow::Result<crate::types::VehicleStopResult> {
+    Ok(crate::types::VehicleStopResult {
+        msg_type: "vehicle-stop-result".to_string(),
+        query_id: query_id.to_string(),
+        vehicle_id: vehicle_id.to_string(),
+        vehicle_type: vehicle_type.to_string(),
+        gps: crate::types::GpsCoordinates {
+            lat: stop.lat,
+            lon: stop.lon,
+            alt: 0.0,
+        },
+        start_time: crate::utils::time::format_timestamp_millis(stop.start_time)?,
+        end_time: crate::utils::time::format_timestamp_millis(stop.end_time)?,
+        duration_in_secs: stop.duration_in_ms() as f64 / 1000.0,
+        point_count: stop.point_count,
+    })
+}
+
+/// The vehicles having at least one position within the area that could belong to a stop.
+async fn candidate_vehicle_ids(
+    ctx: &crate::contexts::DataHandlerContext,
+    filter: &crate::query::VehicleFilter,
+    criteria: &StopCriteria,
+    start_time: &Instant,
+    query_timeout: u128,
+) -> anyhow::Result<(Vec<String>, bool)> {
+    let partitions = filter.partitions();
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, partitions.as_ref())?;
+    let time_range =
+        crate::query::time_range_predicate(df.schema(), filter.from_date, filter.to_date)?;
+    df = df.filter(time_range)?;
+    if let Some(predicate) = filter.area_predicate() {
+        df = df.filter(predicate)?;
+    }
+    // any position can start a stop when the radius is checked
+    if let (Some(max_speed), None) = (criteria.max_speed, criteria.max_radius_in_meters) {
+        df = df.filter(col("speed").lt_eq(lit(max_speed)))?;
+    }
+    let df = crate::query::first_fix_per_vehicle(df, false)?;
+    let (fixes, has_timed_out) = crate::query::collect_fixes(df, start_time, query_timeout).await?;
+    let mut vehicle_ids: Vec<String> = fixes.into_keys().collect();
+    vehicle_ids.sort();
+    Ok((vehicle_ids, has_timed_out))
+}
+
+async fn execute_stops_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleStopsRequest>,
+) -> anyhow::Result<crate::types::VehicleStopsResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let limit: usize = query
+        .limit
+        .unwrap_or(crate::types::DEFAULT_QUERY_LIMIT)
+        .try_into()?;
+    let criteria = StopCriteria::new(
+        query.max_speed,
+        query.radius_in_meters,
+        query.min_duration_in_min,
+    )?;
+    let from_date: DateTime<Utc> = query.from_date.parse()?;
+    let to_date: DateTime<Utc> = query.to_date.parse()?;
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    let mut filter = crate::query::VehicleFilter::with_range(from_date, to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+
+    let start_time = Instant::now();
+    let mut response = crate::types::VehicleStopsResponse {
+        msg_type: "vehicle-stops-response".to_string(),
+        processed_record_count: 0,
+        vehicle_count: 0,
+        stop_count: 0,
+        elapsed_time_in_MS: 0,
+        timeout_expired: false,
+        limit_reached: false,
+    };
+
+    // The whole track of the vehicles is needed, even outside of the area,
+    // otherwise a vehicle leaving the area for a while would be seen as stopped.
+    if filter.area.is_some() {
+        let (vehicle_ids, has_timed_out) =
+            candidate_vehicle_ids(ctx, &filter, &criteria, &start_time, query_timeout).await?;
+        if has_timed_out || vehicle_ids.is_empty() {
+            response.elapsed_time_in_MS = start_time.elapsed().as_millis();
+            response.timeout_expired = has_timed_out;
+            return Ok(response);
+        }
+        filter.vehicle_ids = vehicle_ids;
+    }
+
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, None)?;
+    let time_range = crate::query::time_range_predicate(df.schema(), from_date, to_date)?;
+    df = df
+        .filter(time_range)?
+        .sort(crate::utils::pagination::sort_exprs(
+            crate::types::VehicleQueryOrder::VehicleIdTimestamp,
+        ))?;
+    let columns = crate::query::VehicleEventColumns::try_new(df.schema())?;
+
+    // (vehicle id, vehicle type, detector)
+    let mut current: Option<(String, String, StopDetector)> = None;
+    let mut stops: Vec<crate::types::VehicleStopResult> = vec![];
+    let mut stream = df.execute_stream().await?;
+    'batches: while let Some(batch_result) = stream.next().await {
+        let batch = batch_result?;
+        response.processed_record_count += batch.num_rows();
+        let events = columns.read(&batch)?;
+        for i in 0..events.len() {
+            let event = events.event(i);
+            if !event.has_valid_position() {
+                continue;
+            }
+            let is_same_vehicle = current
+                .as_ref()
+                .is_some_and(|(vehicle_id, _, _)| vehicle_id == event.vehicle_id);
+            if !is_same_vehicle {
+                if let Some((vehicle_id, vehicle_type, mut detector)) = current.take()
+                    && let Some(stop) = detector.finish()
+                {
+                    stops.push(stop_result(&query.id, &vehicle_id, &vehicle_type, &stop)?);
+                }
+                current = Some((
+                    event.vehicle_id.to_string(),
+                    event.vehicle_type.to_string(),
+                    StopDetector::new(criteria),
+                ));
+                response.vehicle_count += 1;
+            }
+            if let Some((vehicle_id, vehicle_type, detector)) = current.as_mut()
+                && let Some(stop) =
+                    detector.push(event.timestamp, event.lon, event.lat, event.speed)
+            {
+                stops.push(stop_result(&query.id, vehicle_id, vehicle_type, &stop)?);
+            }
+        }
+        for stop in stops.drain(..) {
+            if !filter.contains_position(stop.gps.lon, stop.gps.lat) {
+                continue;
+            }
+            crate::utils::messaging::publish_message(
+                &ctx.parent.nats_client,
+                req.reply_to.clone(),
+                &stop,
+            )
+            .await?;
+            response.stop_count += 1;
+            if response.stop_count >= limit {
+                response.limit_reached = true;
+                break 'batches;
+            }
+        }
+        if start_time.elapsed().as_millis() >= query_timeout {
+            response.timeout_expired = true;
+            break;
+        }
+    }
+    if !response.limit_reached
+        && !response.timeout_expired
+        && let Some((vehicle_id, vehicle_type, mut detector)) = current.take()
+        && let Some(stop) = detector.finish()
+        && filter.contains_position(stop.lon, stop.lat)
+    {
+        let result = stop_result(&query.id, &vehicle_id, &vehicle_type, &stop)?;
+        crate::utils::messaging::publish_message(
+            &ctx.parent.nats_client,
+            req.reply_to.clone(),
+            &result,
+        )
+        .await?;
+        response.stop_count += 1;
+    }
+    response.elapsed_time_in_MS = start_time.elapsed().as_millis();
+    log::info!("Total stops: {}", response.stop_count);
+    Ok(response)
+}
+
+async fn process_stops_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleStopsRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_stops_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle stops query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_stops_requests(ctx: crate::contexts::DataHandlerContext) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.stops".to_string(),
+        process_stops_request,
+    );
+    anyhow::Ok(())
+}
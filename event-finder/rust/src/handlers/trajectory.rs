@@ -0,0 +1,328 @@
+use futures_util::StreamExt;
+use geo::{Coord, HaversineDistance, LineString, Point, SimplifyIdx};
+use log;
+use std::time::Instant;
+
+// Rough conversion used to express the Douglas-Peucker tolerance in meters instead of degrees of latitude.
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// Accumulates the ordered positions of a single vehicle.
+struct TrajectoryBuilder {
+    vehicle_id: String,
+    vehicle_type: String,
+    start_time: i64,
+    end_time: i64,
+    coords: Vec<Coord>,
+    distance_in_meters: f64,
+    max_speed: f64,
+}
+
+impl TrajectoryBuilder {
+    fn new(event: &crate::query::VehicleEvent) -> Self {
+        Self {
+            vehicle_id: event.vehicle_id.to_string(),
+            vehicle_type: event.vehicle_type.to_string(),
+            start_time: event.timestamp,
+            end_time: event.timestamp,
+            coords: vec![Coord {
+                x: event.lon,
+                y: event.lat,
+            }],
+            distance_in_meters: 0.0,
+            max_speed: event.speed,
+        }
+    }
+
+    fn push(&mut self, event: &crate::query::VehicleEvent) {
+        let coord = Coord {
+            x: event.lon,
+            y: event.lat,
+        };
+        if let Some(last) = self.coords.last() {
+            self.distance_in_meters += Point::from(*last).haversine_distance(&Point::from(coord));
+        }
+        self.coords.push(coord);
+        self.end_time = event.timestamp;
+        self.max_speed = self.max_speed.max(event.speed);
+    }
+
+    fn into_feature(
+        mut self,
+        simplify_tolerance_in_meters: Option<f64>,
+    ) -> anyhow::Result<geojson::Feature> {
+        let point_count = self.coords.len();
+        if point_count == 1 {
+            // a LineString needs at least 2 positions
+            self.coords.push(self.coords[0]);
+        }
+        let mut line = LineString::new(self.coords);
+        if let Some(tolerance) = simplify_tolerance_in_meters {
+            line = simplify(&line, tolerance);
+        }
+        let mut properties = geojson::JsonObject::new();
+        properties.insert("vehicleId".to_string(), self.vehicle_id.clone().into());
+        properties.insert("vehicleType".to_string(), self.vehicle_type.into());
+        properties.insert(
+            "startTime".to_string(),
+            crate::utils::time::format_timestamp_millis(self.start_time)?.into(),
+        );
+        properties.insert(
+            "endTime".to_string(),
+            crate::utils::time::format_timestamp_millis(self.end_time)?.into(),
+        );
+        properties.insert("pointCount".to_string(), point_count.into());
+        properties.insert(
+            "distanceInMeters".to_string(),
+            self.distance_in_meters.into(),
+        );
+        properties.insert("maxSpeed".to_string(), self.max_speed.into());
+        Ok(geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(geojson::Value::from(&line))),
+            id: Some(geojson::feature::Id::String(self.vehicle_id)),
+            properties: Some(properties),
+            foreign_members: None,
+        })
+    }
+}
+
+/// Simplifies the line with a tolerance in meters, in an equirectangular projection centered on its mean latitude,
+/// where a degree of longitude is as long as a degree of latitude.
+fn simplify(line: &LineString, tolerance_in_meters: f64) -> LineString {
+    let mean_lat = line.coords().map(|coord| coord.y).sum::<f64>() / line.0.len() as f64;
+    let lon_scale = mean_lat.to_radians().cos();
+    let projected: LineString = line
+        .coords()
+        .map(|coord| Coord {
+            x: coord.x * lon_scale,
+            y: coord.y,
+        })
+        .collect();
+    projected
+        .simplify_idx(&(tolerance_in_meters / METERS_PER_DEGREE))
+        .into_iter()
+        .map(|index| line.0[index])
+        .collect()
+}
+
+async fn publish_trajectory(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleTrajectoryRequest>,
+    builder: TrajectoryBuilder,
+) -> anyhow::Result<()> {
+    let result = crate::types::VehicleTrajectoryResult {
+        msg_type: "vehicle-trajectory-result".to_string(),
+        query_id: req.body.id.clone(),
+        feature: builder.into_feature(req.body.simplify_tolerance_in_meters)?,
+    };
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &result)
+        .await
+}
+
+async fn execute_trajectory_query(
+    ctx: &crate::contexts::DataHandlerContext,
+    req: &crate::types::Request<crate::types::VehicleTrajectoryRequest>,
+) -> anyhow::Result<crate::types::VehicleTrajectoryResponse> {
+    let query = &req.body;
+    let query_timeout = query
+        .timeout
+        .unwrap_or(ctx.config.finder.defaultTimeoutInMS);
+    let limit: usize = match query.limit {
+        Some(limit) => limit.try_into()?,
+        None => usize::MAX,
+    };
+    let area = crate::query::QueryArea::from_request(
+        query.geometry.as_ref(),
+        query.circle.as_ref(),
+        query.corridor.as_ref(),
+    )?;
+    let mut filter = crate::query::VehicleFilter::new(&query.from_date, &query.to_date, area)?;
+    filter.vehicle_types = query.vehicle_types.clone();
+    filter.vehicle_ids = query.vehicle_ids.clone();
+    if filter.area.is_none() && filter.vehicle_ids.is_empty() && filter.vehicle_types.is_empty() {
+        anyhow::bail!("A geometry, circle, corridor, vehicleIds or vehicleTypes must be specified");
+    }
+
+    let partitions = filter.partitions();
+    let mut df = ctx.get_session().table("events").await?;
+    df = filter.apply(df, partitions.as_ref())?;
+    df = df.sort(crate::utils::pagination::sort_exprs(
+        crate::types::VehicleQueryOrder::VehicleIdTimestamp,
+    ))?;
+    let columns = crate::query::VehicleEventColumns::try_new(df.schema())?;
+
+    let start_time = Instant::now();
+    let mut processed_row_count: usize = 0;
+    let mut selected_row_count: usize = 0;
+    let mut trajectory_count: usize = 0;
+    let mut limit_reached = false;
+    let mut has_timed_out = false;
+    let mut current: Option<TrajectoryBuilder> = None;
+
+    let mut stream = df.execute_stream().await?;
+    while let Some(batch_result) = stream.next().await {
+        let batch = batch_result?;
+        processed_row_count += batch.num_rows();
+        let events = columns.read(&batch)?;
+        for i in 0..events.len() {
+            let event = events.event(i);
+            if !event.has_valid_position() {
+                continue;
+            }
+            if !filter.contains_timestamp(event.datetime()?) {
+                continue;
+            }
+            if !filter.contains_position(event.lon, event.lat) {
+                continue;
+            }
+            match current.as_mut() {
+                Some(builder) if builder.vehicle_id == event.vehicle_id => builder.push(&event),
+                _ => {
+                    if let Some(builder) = current.take() {
+                        publish_trajectory(ctx, req, builder).await?;
+                        trajectory_count += 1;
+                    }
+                    if trajectory_count >= limit {
+                        limit_reached = true;
+                        break;
+                    }
+                    current = Some(TrajectoryBuilder::new(&event));
+                }
+            }
+            selected_row_count += 1;
+        }
+        if start_time.elapsed().as_millis() >= query_timeout {
+            has_timed_out = true;
+        }
+        if limit_reached || has_timed_out {
+            break;
+        }
+    }
+    if let Some(builder) = current.take() {
+        publish_trajectory(ctx, req, builder).await?;
+        trajectory_count += 1;
+    }
+    let duration = start_time.elapsed();
+    log::info!("Total trajectories: {}", trajectory_count);
+
+    Ok(crate::types::VehicleTrajectoryResponse {
+        msg_type: "vehicle-trajectory-response".to_string(),
+        processed_record_count: processed_row_count,
+        selected_record_count: selected_row_count,
+        trajectory_count,
+        elapsed_time_in_MS: duration.as_millis(),
+        timeout_expired: has_timed_out,
+        limit_reached,
+    })
+}
+
+async fn process_trajectory_request(
+    ctx: crate::contexts::DataHandlerContext,
+    req: crate::types::Request<crate::types::VehicleTrajectoryRequest>,
+) -> anyhow::Result<()> {
+    log::info!("Received NATS request: {:?}", req);
+    let result = execute_trajectory_query(&ctx, &req).await;
+    if let Err(e) = &result {
+        log::error!("Error executing vehicle trajectory query: {}", e);
+    }
+    let resp = crate::utils::messaging::create_response(&req.id, result);
+    log::info!("Sending NATS response: {:?}", resp);
+    crate::utils::messaging::publish_message(&ctx.parent.nats_client, req.reply_to.clone(), &resp)
+        .await
+}
+
+pub fn subscribe_to_trajectory_requests(
+    ctx: crate::contexts::DataHandlerContext,
+) -> anyhow::Result<()> {
+    let _ = crate::utils::messaging::message_loop(
+        ctx,
+        "requests.vehicles.trajectory".to_string(),
+        process_trajectory_request,
+    );
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::HaversineDestination;
+
+    fn event(
+        timestamp: i64,
+        lon: f64,
+        lat: f64,
+        speed: f64,
+    ) -> crate::query::VehicleEvent<'static> {
+        crate::query::VehicleEvent {
+            timestamp,
+            lat,
+            lon,
+            alt: 0.0,
+            vehicle_id: "a",
+            vehicle_type: "taxi",
+            direction: "north",
+            speed,
+            geo_hash: "u150upk",
+        }
+    }
+
+    /// A track going north, with a middle position shifted east by the given distance.
+    fn track(lat: f64, shift_in_meters: f64) -> TrajectoryBuilder {
+        let east = Point::new(4.35, lat + 0.01)
+            .haversine_destination(90.0, shift_in_meters)
+            .x();
+        let mut builder = TrajectoryBuilder::new(&event(0, 4.35, lat, 10.0));
+        builder.push(&event(1000, east, lat + 0.01, 30.0));
+        builder.push(&event(2000, 4.35, lat + 0.02, 20.0));
+        builder
+    }
+
+    fn line(feature: &geojson::Feature) -> Vec<Vec<f64>> {
+        match &feature.geometry.as_ref().unwrap().value {
+            geojson::Value::LineString(positions) => positions.clone(),
+            value => panic!("unexpected geometry {:?}", value),
+        }
+    }
+
+    #[test]
+    fn builds_the_line_and_the_properties_of_the_trajectory() -> anyhow::Result<()> {
+        let feature = track(50.0, 0.0).into_feature(None)?;
+
+        assert_eq!(line(&feature).len(), 3);
+        assert_eq!(feature.property("pointCount"), Some(&serde_json::json!(3)));
+        assert_eq!(feature.property("maxSpeed"), Some(&serde_json::json!(30.0)));
+        assert_eq!(
+            feature.property("endTime"),
+            Some(&serde_json::json!("1970-01-01T00:00:02+00:00"))
+        );
+        let distance = feature
+            .property("distanceInMeters")
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((distance - 2223.9).abs() < 1.0, "{}", distance);
+        Ok(())
+    }
+
+    #[test]
+    fn duplicates_the_single_position_of_a_trajectory() -> anyhow::Result<()> {
+        let feature = TrajectoryBuilder::new(&event(0, 4.35, 50.85, 10.0)).into_feature(None)?;
+
+        assert_eq!(line(&feature), vec![vec![4.35, 50.85], vec![4.35, 50.85]]);
+        assert_eq!(feature.property("pointCount"), Some(&serde_json::json!(1)));
+        Ok(())
+    }
+
+    #[test]
+    fn simplifies_the_trajectories_with_a_tolerance_in_meters_at_any_latitude() -> anyhow::Result<()>
+    {
+        for lat in [0.0, 50.0, 70.0] {
+            let within = track(lat, 40.0).into_feature(Some(50.0))?;
+            let beyond = track(lat, 60.0).into_feature(Some(50.0))?;
+
+            assert_eq!(line(&within).len(), 2, "at {}°", lat);
+            assert_eq!(line(&beyond).len(), 3, "at {}°", lat);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+use crate::utils::moves::VehiclePosition;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use datafusion::arrow::array::{
+    ArrayRef, BinaryArray, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray,
+    new_null_array,
+};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::catalog::{Session, TableProvider};
+use datafusion::datasource::TableType;
+use datafusion::datasource::memory::MemorySourceConfig;
+use datafusion::logical_expr::Expr;
+use datafusion::physical_plan::ExecutionPlan;
+use std::any::Any;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A position received from the live stream, with the partition values the collector gives to it.
+#[derive(Debug, Clone)]
+struct HotEvent {
+    timestamp: i64,
+    vehicle_id: String,
+    vehicle_type: String,
+    lat: f64,
+    lon: f64,
+    alt: f64,
+    geo_hash: String,
+    direction: String,
+    speed: f64,
+    partition_key: String,
+    window_start: i64,
+    received_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct HotTierState {
+    // ordered by reception
+    events: VecDeque<HotEvent>,
+    // incremented on each change, to know when the record batch must be rebuilt
+    version: u64,
+}
+
+/// The recent events that the collectors did not flush yet.
+#[derive(Debug)]
+pub struct HotTier {
+    aggregation_period_in_ms: i64,
+    retention: Duration,
+    max_event_count: usize,
+    geohash_length: usize,
+    // None when the data is not partitioned by geohash, in which case the enriched moves provide the partition key
+    partition_hash_length: Option<usize>,
+    state: Mutex<HotTierState>,
+}
+
+impl HotTier {
+    pub fn from_config(config: &crate::config::Config) -> anyhow::Result<Option<Arc<Self>>> {
+        let hot_tier = &config.finder.hotTier;
+        if !hot_tier.enabled {
+            return Ok(None);
+        }
+        let partition_hash_length = match &config.partitioning.dataPartition {
+            crate::config::DataPartitionStrategyConfig::GeohashDataPartitionStrategyConfig {
+                hashLength,
+            } => Some(*hashLength as usize),
+            _ => None,
+        };
+        if partition_hash_length.is_none() && hot_tier.source != "enriched-move" {
+            anyhow::bail!(
+                "The hot tier requires the enriched-move source when the data is not partitioned by geohash"
+            );
+        }
+        Ok(Some(Arc::new(Self {
+            aggregation_period_in_ms: i64::from(
+                config.partitioning.timePartition.aggregationPeriodInMin,
+            ) * 60_000,
+            retention: Duration::from_secs(u64::from(hot_tier.retentionInMin) * 60),
+            max_event_count: hot_tier.maxEventCount,
+            geohash_length: config.collector.geohashLength as usize,
+            partition_hash_length,
+            state: Mutex::new(HotTierState::default()),
+        })))
+    }
+
+    pub fn push(&self, position: VehiclePosition) -> anyhow::Result<()> {
+        let timestamp = position
+            .timestamp
+            .parse::<DateTime<Utc>>()?
+            .timestamp_millis();
+        let coord = geo::coord! { x: position.lon, y: position.lat };
+        let geo_hash = match position.geo_hash {
+            Some(geo_hash) => geo_hash,
+            None => geohash::encode(coord, self.geohash_length)?,
+        };
+        let partition_key = match (position.partition_key, self.partition_hash_length) {
+            (Some(partition_key), _) => partition_key,
+            (None, Some(hash_length)) => geohash::encode(coord, hash_length)?,
+            (None, None) => anyhow::bail!("Missing the partition key of the move"),
+        };
+        let event = HotEvent {
+            timestamp,
+            vehicle_id: position.vehicle_id,
+            vehicle_type: position.vehicle_type,
+            lat: position.lat,
+            lon: position.lon,
+            alt: position.alt,
+            geo_hash,
+            direction: position.direction,
+            speed: position.speed,
+            partition_key,
+            window_start: timestamp - timestamp.rem_euclid(self.aggregation_period_in_ms),
+            received_at: Instant::now(),
+        };
+        let mut state = self.state.lock().unwrap();
+        state.events.push_back(event);
+        while state.events.len() > self.max_event_count {
+            state.events.pop_front();
+        }
+        state.version += 1;
+        Ok(())
+    }
+
+    /// Drops the events written by the collectors for the given time window and partition keys,
+    /// or for all the partition keys when none are specified.
+    /// Only the events received before the flush are dropped, since a partial flush leaves the window open.
+    pub fn evict_flushed(
+        &self,
+        from_time: DateTime<Utc>,
+        to_time: DateTime<Utc>,
+        partition_keys: &HashSet<String>,
+        flushed_at: Instant,
+    ) -> usize {
+        let from_time = from_time.timestamp_millis();
+        let to_time = to_time.timestamp_millis();
+        let mut state = self.state.lock().unwrap();
+        let count = state.events.len();
+        state.events.retain(|event| {
+            !(event.received_at <= flushed_at
+                && event.timestamp >= from_time
+                && event.timestamp < to_time
+                && (partition_keys.is_empty() || partition_keys.contains(&event.partition_key)))
+        });
+        let evicted_count = count - state.events.len();
+        if evicted_count > 0 {
+            state.version += 1;
+        }
+        evicted_count
+    }
+
+    /// Drops the events that were never flushed within the retention period.
+    pub fn evict_expired(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let mut evicted_count = 0;
+        while state
+            .events
+            .front()
+            .is_some_and(|event| event.received_at.elapsed() > self.retention)
+        {
+            state.events.pop_front();
+            evicted_count += 1;
+        }
+        if evicted_count > 0 {
+            state.version += 1;
+        }
+        evicted_count
+    }
+
+    pub fn overlaps(&self, from_date: DateTime<Utc>, to_date: DateTime<Utc>) -> bool {
+        let from_time = from_date.timestamp_millis();
+        let to_time = to_date.timestamp_millis();
+        let state = self.state.lock().unwrap();
+        state
+            .events
+            .iter()
+            .any(|event| event.timestamp >= from_time && event.timestamp < to_time)
+    }
+
+    fn version(&self) -> u64 {
+        self.state.lock().unwrap().version
+    }
+
+    /// Builds a record batch with the same columns as the files written by the collectors.
+    fn to_record_batch(&self, schema: &SchemaRef) -> anyhow::Result<(u64, RecordBatch)> {
+        let state = self.state.lock().unwrap();
+        let events = &state.events;
+        let window_starts: Vec<DateTime<Utc>> = events
+            .iter()
+            .map(|event| {
+                Utc.timestamp_millis_opt(event.window_start)
+                    .single()
+                    .unwrap()
+            })
+            .collect();
+        let window_part = |format: &str| -> ArrayRef {
+            Arc::new(StringArray::from_iter_values(
+                window_starts
+                    .iter()
+                    .map(|window_start| window_start.format(format).to_string()),
+            ))
+        };
+        let mut columns: Vec<ArrayRef> = vec![];
+        for field in schema.fields().iter() {
+            let column: ArrayRef = match field.name().as_str() {
+                "timestamp" => Arc::new(TimestampMillisecondArray::from_iter_values(
+                    events.iter().map(|event| event.timestamp),
+                )),
+                "vehicleId" => Arc::new(StringArray::from_iter_values(
+                    events.iter().map(|event| &event.vehicle_id),
+                )),
+                "vehicleType" => Arc::new(StringArray::from_iter_values(
+                    events.iter().map(|event| &event.vehicle_type),
+                )),
+                "gps_lat" => Arc::new(Float64Array::from_iter_values(
+                    events.iter().map(|event| event.lat),
+                )),
+                "gps_lon" => Arc::new(Float64Array::from_iter_values(
+                    events.iter().map(|event| event.lon),
+                )),
+                "gps_alt" => Arc::new(Float64Array::from_iter_values(
+                    events.iter().map(|event| event.alt),
+                )),
+                "geoHash" => Arc::new(StringArray::from_iter_values(
+                    events.iter().map(|event| &event.geo_hash),
+                )),
+                "direction" => Arc::new(StringArray::from_iter_values(
+                    events.iter().map(|event| &event.direction),
+                )),
+                "speed" => Arc::new(Float64Array::from_iter_values(
+                    events.iter().map(|event| event.speed),
+                )),
+                // GeoParquet columns
+                "geometry" => {
+                    Arc::new(BinaryArray::from_iter_values(events.iter().map(|event| {
+                        crate::utils::geoparquet::point_to_wkb(event.lon, event.lat)
+                    })))
+                }
+                "bbox" => crate::utils::geoparquet::point_bbox_array(
+                    field.data_type(),
+                    &Float64Array::from_iter_values(events.iter().map(|event| event.lon)),
+                    &Float64Array::from_iter_values(events.iter().map(|event| event.lat)),
+                )?,
+                // partition columns, laid out like y=2024/m=01/d=01/hh=05/mm=00/start=2024-01-01-05-00/int=600/pk=f25ks
+                "y" => window_part("%Y"),
+                "m" => window_part("%m"),
+                "d" => window_part("%d"),
+                "hh" => window_part("%H"),
+                "mm" => window_part("%M"),
+                "start" => window_part("%Y-%m-%d-%H-%M"),
+                "int" => Arc::new(StringArray::from_iter_values(
+                    events
+                        .iter()
+                        .map(|_| (self.aggregation_period_in_ms / 1000).to_string()),
+                )),
+                "pk" => Arc::new(StringArray::from_iter_values(
+                    events.iter().map(|event| &event.partition_key),
+                )),
+                _ => new_null_array(field.data_type(), events.len()),
+            };
+            columns.push(cast(&column, field.data_type())?);
+        }
+        Ok((
+            state.version,
+            RecordBatch::try_new(schema.clone(), columns)?,
+        ))
+    }
+}
+
+/// Exposes the hot tier as a DataFusion table, with the same schema as the `events` files.
+#[derive(Debug)]
+pub struct HotTierTable {
+    hot_tier: Arc<HotTier>,
+    schema: SchemaRef,
+    cached_batch: Mutex<Option<(u64, RecordBatch)>>,
+}
+
+impl HotTierTable {
+    pub fn new(hot_tier: Arc<HotTier>, schema: SchemaRef) -> Self {
+        Self {
+            hot_tier,
+            schema,
+            cached_batch: Mutex::new(None),
+        }
+    }
+
+    fn record_batch(&self) -> anyhow::Result<RecordBatch> {
+        let mut cached_batch = self.cached_batch.lock().unwrap();
+        if let Some((version, batch)) = cached_batch.as_ref()
+            && *version == self.hot_tier.version()
+        {
+            return Ok(batch.clone());
+        }
+        let (version, batch) = self.hot_tier.to_record_batch(&self.schema)?;
+        *cached_batch = Some((version, batch.clone()));
+        Ok(batch)
+    }
+}
+
+#[async_trait]
+impl TableProvider for HotTierTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Temporary
+    }
+
+    async fn scan(
+        &self,
+        _state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> datafusion::error::Result<Arc<dyn ExecutionPlan>> {
+        let batch = self
+            .record_batch()
+            .map_err(|e| datafusion::error::DataFusionError::External(e.into()))?;
+        Ok(MemorySourceConfig::try_new_exec(
+            &[vec![batch]],
+            self.schema.clone(),
+            projection.cloned(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hot_tier() -> HotTier {
+        HotTier {
+            aggregation_period_in_ms: 600_000,
+            retention: Duration::from_secs(3600),
+            max_event_count: 100,
+            geohash_length: 7,
+            partition_hash_length: Some(5),
+            state: Mutex::new(HotTierState::default()),
+        }
+    }
+
+    fn position(vehicle_id: &str, timestamp: &str) -> VehiclePosition {
+        VehiclePosition {
+            vehicle_id: vehicle_id.to_string(),
+            vehicle_type: "taxi".to_string(),
+            timestamp: timestamp.to_string(),
+            lat: 48.85,
+            lon: 2.35,
+            alt: 0.0,
+            direction: "N".to_string(),
+            speed: 10.0,
+            geo_hash: None,
+            partition_key: Some("u09tv".to_string()),
+        }
+    }
+
+    #[test]
+    fn keeps_the_events_received_after_a_partial_flush() {
+        let hot_tier = hot_tier();
+        hot_tier
+            .push(position("v1", "2024-01-01T06:51:00Z"))
+            .unwrap();
+        let flushed_at = Instant::now();
+        std::thread::sleep(Duration::from_millis(1));
+        hot_tier
+            .push(position("v2", "2024-01-01T06:52:00Z"))
+            .unwrap();
+
+        let count = hot_tier.evict_flushed(
+            "2024-01-01T06:50:00Z".parse().unwrap(),
+            "2024-01-01T07:00:00Z".parse().unwrap(),
+            &HashSet::from(["u09tv".to_string()]),
+            flushed_at,
+        );
+
+        assert_eq!(count, 1);
+        let state = hot_tier.state.lock().unwrap();
+        assert_eq!(state.events.len(), 1);
+        assert_eq!(state.events[0].vehicle_id, "v2");
+    }
+}
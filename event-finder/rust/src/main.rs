@@ -1,5 +1,5 @@
 #![allow(non_snake_case)]
-use actix_web::{App, HttpServer};
+use actix_web::{App, HttpServer, web};
 use actix_web_prom::PrometheusMetricsBuilder;
 use async_nats::ServerAddr;
 use log;
@@ -8,9 +8,14 @@ use simple_logger::SimpleLogger;
 use std::env;
 use std::string::ToString;
 use std::sync::Arc;
+mod cache;
 mod config;
 mod contexts;
+mod geofences;
 mod handlers;
+mod hot_tier;
+mod query;
+mod query_events;
 mod types;
 mod utils;
 
@@ -36,9 +41,12 @@ async fn main() -> anyhow::Result<()> {
     let registry = prometheus::Registry::new();
     let counters = create_prometheus_counters(registry.clone());
 
-    start_nats_handlers(&config, &counters).await?;
+    let data_handler_ctx = start_nats_handlers(&config, &counters).await?;
+    if config.finder.flightSql.enabled {
+        handlers::flight_sql::start_flight_sql_server(data_handler_ctx.clone())?;
+    }
 
-    start_web_server(&config, &counters).await
+    start_web_server(&config, &counters, data_handler_ctx).await
 }
 
 fn create_prometheus_counters(
@@ -68,7 +76,7 @@ fn create_prometheus_counters(
 async fn start_nats_handlers(
     config: &Arc<crate::config::Config>,
     prometheus_counters: &contexts::PrometheusCounters,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<contexts::DataHandlerContext> {
     let (nats_server_addresses, enable_proto_buf) = get_nats_servers(&config)?;
     let nats_client = async_nats::connect(nats_server_addresses).await?;
     log::info!("Connected to NATS servers");
@@ -86,20 +94,38 @@ async fn start_nats_handlers(
         identity,
     };
 
-    let session = handlers::search::create_session_context(&config).await?;
-    let data_handler_ctx =
-        contexts::DataHandlerContext::new(base_handler_ctx.clone(), config.clone(), session);
+    let hot_tier = hot_tier::HotTier::from_config(config)?;
+    let session = handlers::search::create_session_context(&config, hot_tier.clone()).await?;
+    let data_handler_ctx = contexts::DataHandlerContext::new(
+        base_handler_ctx.clone(),
+        config.clone(),
+        session,
+        hot_tier,
+    );
 
     handlers::search::subscribe_to_search_requests(data_handler_ctx.clone())?;
     handlers::search::subscribe_to_generation_requests(data_handler_ctx.clone())?;
+    handlers::search::subscribe_to_aggregate_period_events(data_handler_ctx.clone())?;
+    handlers::trajectory::subscribe_to_trajectory_requests(data_handler_ctx.clone())?;
+    handlers::snapshot::subscribe_to_snapshot_requests(data_handler_ctx.clone())?;
+    handlers::nearest::subscribe_to_nearest_requests(data_handler_ctx.clone())?;
+    handlers::density::subscribe_to_density_requests(data_handler_ctx.clone())?;
+    handlers::stats::subscribe_to_stats_requests(data_handler_ctx.clone())?;
+    handlers::od_matrix::subscribe_to_od_matrix_requests(data_handler_ctx.clone())?;
+    handlers::stops::subscribe_to_stops_requests(data_handler_ctx.clone())?;
+    handlers::encounters::subscribe_to_encounters_requests(data_handler_ctx.clone())?;
+    handlers::export::subscribe_to_export_requests(data_handler_ctx.clone())?;
+    handlers::geofences::subscribe_to_geofence_requests(data_handler_ctx.clone())?;
+    handlers::hot_tier::subscribe_to_recent_events(data_handler_ctx.clone())?;
     handlers::ping::subscribe_to_ping_requests(base_handler_ctx.clone())?;
 
-    anyhow::Ok(())
+    anyhow::Ok(data_handler_ctx)
 }
 
 async fn start_web_server(
     config: &Arc<crate::config::Config>,
     prometheus_counters: &contexts::PrometheusCounters,
+    data_handler_ctx: contexts::DataHandlerContext,
 ) -> anyhow::Result<()> {
     let mut labels = std::collections::HashMap::new();
     labels.insert("service".to_string(), "finder".to_string());
@@ -118,10 +144,14 @@ async fn start_web_server(
         .unwrap();
 
     log::info!("Listening on: http://127.0.0.1:{}", port);
+    let data_handler_ctx = web::Data::new(data_handler_ctx);
     HttpServer::new(move || {
         App::new()
             .wrap(prometheus.clone())
+            .app_data(data_handler_ctx.clone())
             .service(handlers::ping::ping)
+            .service(handlers::rest::query_vehicles)
+            .service(handlers::rest::query_events)
     })
     .bind(("127.0.0.1", port))?
     .run()
@@ -0,0 +1,961 @@
+use chrono::{DateTime, Utc};
+use datafusion::arrow::array::{
+    Array, BooleanArray, BooleanBuilder, Float64Array, RecordBatch, StringViewArray,
+    TimestampMillisecondArray,
+};
+use datafusion::arrow::datatypes::{DataType, TimeUnit};
+use datafusion::common::cast::{as_float64_array, as_string_array};
+use datafusion::common::{DFSchema, DataFusionError, ScalarValue};
+use datafusion::functions::expr_fn::{date_bin, floor};
+use datafusion::functions_aggregate::expr_fn::{avg, last_value, min};
+use datafusion::functions_window::expr_fn::row_number;
+use datafusion::logical_expr::{
+    ColumnarValue, ExprFunctionExt, ScalarFunctionImplementation, ScalarUDF, Volatility, create_udf,
+};
+use datafusion::prelude::*;
+use futures_util::StreamExt;
+use geo::{
+    BoundingRect, Closest, Contains, Geometry, HaversineClosestPoint, HaversineDistance,
+    LineString, Point, point,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The area searched by a query.
+#[derive(Debug, Clone)]
+pub enum QueryArea {
+    Polygon(Geometry),
+    Circle {
+        center: Point,
+        radius_in_meters: f64,
+    },
+    Corridor {
+        line: LineString,
+        buffer_in_meters: f64,
+    },
+}
+
+impl QueryArea {
+    /// Builds the area from the mutually exclusive geometry, circle and corridor of a request.
+    pub fn from_request(
+        geometry: Option<&geojson::Geometry>,
+        circle: Option<&crate::types::CircleArea>,
+        corridor: Option<&crate::types::CorridorArea>,
+    ) -> anyhow::Result<Option<Self>> {
+        match (geometry, circle, corridor) {
+            (None, None, None) => Ok(None),
+            (Some(geometry), None, None) => Ok(Some(Self::Polygon(geometry.try_into()?))),
+            (None, Some(circle), None) => {
+                if circle.radius_in_meters <= 0.0 {
+                    anyhow::bail!("The radius of the circle must be greater than 0");
+                }
+                match Geometry::try_from(&circle.center)? {
+                    Geometry::Point(center) => Ok(Some(Self::Circle {
+                        center,
+                        radius_in_meters: circle.radius_in_meters,
+                    })),
+                    _ => anyhow::bail!("The center of the circle must be a GeoJSON Point"),
+                }
+            }
+            (None, None, Some(corridor)) => {
+                if corridor.buffer_in_meters <= 0.0 {
+                    anyhow::bail!("The buffer of the corridor must be greater than 0");
+                }
+                match Geometry::try_from(&corridor.line)? {
+                    Geometry::LineString(line) if line.0.len() >= 2 => Ok(Some(Self::Corridor {
+                        line,
+                        buffer_in_meters: corridor.buffer_in_meters,
+                    })),
+                    _ => anyhow::bail!(
+                        "The line of the corridor must be a GeoJSON LineString with at least 2 positions"
+                    ),
+                }
+            }
+            _ => anyhow::bail!("Only one of geometry, circle or corridor can be specified"),
+        }
+    }
+
+    /// A geometry containing the whole area, used to compute the geohash cover.
+    pub fn bounds(&self) -> Geometry {
+        match self {
+            Self::Polygon(geometry) => geometry.clone(),
+            Self::Circle {
+                center,
+                radius_in_meters,
+            } => Geometry::Polygon(crate::utils::geo::circle_polygon(
+                *center,
+                *radius_in_meters,
+            )),
+            Self::Corridor {
+                line,
+                buffer_in_meters,
+            } => Geometry::MultiPolygon(crate::utils::geo::corridor_polygons(
+                line,
+                *buffer_in_meters,
+            )),
+        }
+    }
+
+    pub fn contains(&self, lon: f64, lat: f64) -> bool {
+        let position = point!(x: lon, y: lat);
+        match self {
+            Self::Polygon(geometry) => geometry.contains(&position),
+            Self::Circle {
+                center,
+                radius_in_meters,
+            } => center.haversine_distance(&position) <= *radius_in_meters,
+            Self::Corridor {
+                line,
+                buffer_in_meters,
+            } => match line.haversine_closest_point(&position) {
+                Closest::Intersection(_) => true,
+                Closest::SinglePoint(closest) => {
+                    closest.haversine_distance(&position) <= *buffer_in_meters
+                }
+                Closest::Indeterminate => line
+                    .points()
+                    .any(|vertex| vertex.haversine_distance(&position) <= *buffer_in_meters),
+            },
+        }
+    }
+}
+
+/// The filters shared by all the queries over the `events` table.
+#[derive(Debug, Clone)]
+pub struct VehicleFilter {
+    pub from_date: DateTime<Utc>,
+    pub to_date: DateTime<Utc>,
+    pub area: Option<QueryArea>,
+    pub vehicle_types: Vec<String>,
+    pub vehicle_ids: Vec<String>,
+    pub min_speed: Option<f64>,
+    pub max_speed: Option<f64>,
+    pub directions: Vec<String>,
+}
+
+impl VehicleFilter {
+    pub fn new(from_date: &str, to_date: &str, area: Option<QueryArea>) -> anyhow::Result<Self> {
+        Self::with_range(from_date.parse()?, to_date.parse()?, area)
+    }
+
+    pub fn with_range(
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        area: Option<QueryArea>,
+    ) -> anyhow::Result<Self> {
+        let (from_date, _) = crate::utils::time::round_datetime_modulo_minutes(from_date, 10);
+        let (_, to_date) = crate::utils::time::round_datetime_modulo_minutes(to_date, 10);
+        if from_date >= to_date {
+            anyhow::bail!("fromDate must be before toDate");
+        }
+        Ok(Self {
+            from_date,
+            to_date,
+            area,
+            vehicle_types: vec![],
+            vehicle_ids: vec![],
+            min_speed: None,
+            max_speed: None,
+            directions: vec![],
+        })
+    }
+
+    pub fn from_query(query: &crate::types::VehicleQueryRequest) -> anyhow::Result<Self> {
+        let area = QueryArea::from_request(
+            query.geometry.as_ref(),
+            query.circle.as_ref(),
+            query.corridor.as_ref(),
+        )?;
+        let mut filter = Self::new(&query.from_date, &query.to_date, area)?;
+        filter.vehicle_types = query.vehicle_types.clone();
+        filter.vehicle_ids = query.vehicle_ids.clone();
+        filter.min_speed = query.min_speed;
+        filter.max_speed = query.max_speed;
+        filter.directions = query.directions.clone();
+        filter.validate()?;
+        Ok(filter)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.area.is_none() && self.vehicle_ids.is_empty() {
+            anyhow::bail!(
+                "A geometry, circle or corridor is required when no vehicleIds are specified"
+            );
+        }
+        Ok(())
+    }
+
+    /// The geohash partitions covering the area, or None when all the partitions must be scanned.
+    pub fn partitions(&self) -> Option<HashSet<String>> {
+        self.area
+            .as_ref()
+            .map(|area| crate::utils::geo::geohash_covering(&area.bounds(), 5))
+    }
+
+    /// Pushes the filters into the DataFusion plan.
+    /// Note that the time range is only enforced at the partition level and must be checked for each row.
+    pub fn apply(
+        &self,
+        mut df: DataFrame,
+        partitions: Option<&HashSet<String>>,
+    ) -> anyhow::Result<DataFrame> {
+        let from_date = self.from_date.format("%Y-%m-%d-%H-%M").to_string();
+        let to_date = self.to_date.format("%Y-%m-%d-%H-%M").to_string();
+        df = df.filter(col("start").gt_eq(lit(from_date)))?;
+        df = df.filter(col("start").lt(lit(to_date)))?;
+        if let Some(partitions) = partitions {
+            let partitions: Vec<Expr> = partitions.iter().map(lit).collect();
+            log::debug!("Partitions: {:?}", partitions);
+            df = df.filter(col("pk").in_list(partitions, false))?;
+        }
+        if let Some(area) = &self.area
+            && let Some(bounds) = area.bounds().bounding_rect()
+            && let Some(predicate) =
+                crate::utils::geoparquet::covering_predicate(df.schema(), bounds)
+        {
+            df = df.filter(predicate)?;
+        }
+        if !self.vehicle_types.is_empty() {
+            let vehicle_types = self.vehicle_types.iter().map(lit).collect::<Vec<Expr>>();
+            df = df.filter(col(r#""vehicleType""#).in_list(vehicle_types, false))?;
+        }
+        if !self.vehicle_ids.is_empty() {
+            let vehicle_ids = self.vehicle_ids.iter().map(lit).collect::<Vec<Expr>>();
+            df = df.filter(col(r#""vehicleId""#).in_list(vehicle_ids, false))?;
+        }
+        if let Some(min_speed) = self.min_speed {
+            df = df.filter(col("speed").gt_eq(lit(min_speed)))?;
+        }
+        if let Some(max_speed) = self.max_speed {
+            df = df.filter(col("speed").lt_eq(lit(max_speed)))?;
+        }
+        if !self.directions.is_empty() {
+            let directions = self.directions.iter().map(lit).collect::<Vec<Expr>>();
+            df = df.filter(col("direction").in_list(directions, false))?;
+        }
+        Ok(df)
+    }
+
+    /// Checks the area for each row within the plan, for the queries that aggregate the rows
+    /// instead of streaming them.
+    pub fn area_predicate(&self) -> Option<Expr> {
+        let area = self.area.clone()?;
+        let fun: ScalarFunctionImplementation = Arc::new(move |args: &[ColumnarValue]| {
+            let args = ColumnarValue::values_to_arrays(args)?;
+            let lon = as_float64_array(&args[0])?;
+            let lat = as_float64_array(&args[1])?;
+            let result: BooleanArray = lon
+                .iter()
+                .zip(lat.iter())
+                .map(|position| match position {
+                    (Some(lon), Some(lat)) => Some(area.contains(lon, lat)),
+                    _ => None,
+                })
+                .collect();
+            Ok(ColumnarValue::Array(Arc::new(result)))
+        });
+        let udf = create_udf(
+            "within_area",
+            vec![DataType::Float64, DataType::Float64],
+            DataType::Boolean,
+            Volatility::Immutable,
+            fun,
+        );
+        Some(udf.call(vec![col("gps_lon"), col("gps_lat")]))
+    }
+
+    pub fn contains_timestamp(&self, datetime: DateTime<Utc>) -> bool {
+        datetime >= self.from_date && datetime < self.to_date
+    }
+
+    pub fn contains_position(&self, lon: f64, lat: f64) -> bool {
+        match &self.area {
+            Some(area) => area.contains(lon, lat),
+            None => true,
+        }
+    }
+}
+
+/// `st_within(lon, lat, geometry)`: whether each position is within a GeoJSON geometry,
+/// registered on the session for the SQL clients.
+pub fn within_geometry_udf() -> ScalarUDF {
+    let fun: ScalarFunctionImplementation = Arc::new(|args: &[ColumnarValue]| {
+        let args = ColumnarValue::values_to_arrays(args)?;
+        let lon = as_float64_array(&args[0])?;
+        let lat = as_float64_array(&args[1])?;
+        let geometries = as_string_array(&args[2])?;
+        // the geometry is usually a literal, parsed only once
+        let mut parsed: Option<(&str, Geometry)> = None;
+        let mut result = BooleanBuilder::with_capacity(lon.len());
+        for i in 0..lon.len() {
+            if lon.is_null(i) || lat.is_null(i) || geometries.is_null(i) {
+                result.append_null();
+                continue;
+            }
+            let value = geometries.value(i);
+            let geometry = match &parsed {
+                Some((parsed_value, geometry)) if *parsed_value == value => geometry,
+                _ => {
+                    let geometry = value
+                        .parse::<geojson::Geometry>()
+                        .map_err(|e| DataFusionError::Execution(e.to_string()))
+                        .and_then(|geometry| {
+                            Geometry::try_from(&geometry)
+                                .map_err(|e| DataFusionError::Execution(e.to_string()))
+                        })?;
+                    &parsed.insert((value, geometry)).1
+                }
+            };
+            result.append_value(geometry.contains(&point!(x: lon.value(i), y: lat.value(i))));
+        }
+        Ok(ColumnarValue::Array(Arc::new(result.finish())))
+    });
+    create_udf(
+        "st_within",
+        vec![DataType::Float64, DataType::Float64, DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        fun,
+    )
+}
+
+/// `st_distance(lon1, lat1, lon2, lat2)`: the haversine distance between two positions, in meters.
+pub fn distance_udf() -> ScalarUDF {
+    let fun: ScalarFunctionImplementation = Arc::new(|args: &[ColumnarValue]| {
+        let args = ColumnarValue::values_to_arrays(args)?;
+        let lon1 = as_float64_array(&args[0])?;
+        let lat1 = as_float64_array(&args[1])?;
+        let lon2 = as_float64_array(&args[2])?;
+        let lat2 = as_float64_array(&args[3])?;
+        let result: Float64Array = (0..lon1.len())
+            .map(|i| {
+                if lon1.is_null(i) || lat1.is_null(i) || lon2.is_null(i) || lat2.is_null(i) {
+                    return None;
+                }
+                let from = point!(x: lon1.value(i), y: lat1.value(i));
+                let to = point!(x: lon2.value(i), y: lat2.value(i));
+                Some(from.haversine_distance(&to))
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    });
+    create_udf(
+        "st_distance",
+        vec![DataType::Float64; 4],
+        DataType::Float64,
+        Volatility::Immutable,
+        fun,
+    )
+}
+
+/// Selects the rows within the exact time range, as the partitions only provide a rough selection.
+pub fn time_range_predicate(
+    schema: &DFSchema,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+) -> anyhow::Result<Expr> {
+    Ok(col("timestamp")
+        .gt_eq(timestamp_literal(schema, from_date)?)
+        .and(col("timestamp").lt(timestamp_literal(schema, to_date)?)))
+}
+
+/// How the rows selected by a vehicle filter are checked, sampled, paginated and ordered.
+#[derive(Debug, Default)]
+pub struct VehicleSelection<'a> {
+    /// Checks the time range and the area of each row within the plan,
+    /// instead of leaving it to the caller.
+    pub check_rows: bool,
+    pub sample_interval_in_secs: Option<u32>,
+    pub sample_method: crate::types::VehicleSampleMethod,
+    pub cursor: Option<&'a crate::utils::pagination::VehicleQueryCursor>,
+    /// Ranks the copies of each event written by partial flushes in a `duplicate_rank` column.
+    pub rank_duplicates: bool,
+    pub order_by: Option<crate::types::VehicleQueryOrder>,
+}
+
+/// Builds the plan selecting the vehicle events of a query or an export.
+pub fn build_vehicle_dataframe(
+    events: DataFrame,
+    filter: &VehicleFilter,
+    partitions: Option<&HashSet<String>>,
+    selection: &VehicleSelection,
+) -> anyhow::Result<DataFrame> {
+    let mut df = filter.apply(events, partitions)?;
+    // The rows are checked before sampling them, to keep the coverage of the area
+    if selection.check_rows {
+        let time_range = time_range_predicate(df.schema(), filter.from_date, filter.to_date)?;
+        df = df.filter(time_range)?;
+        if let Some(predicate) = filter.area_predicate() {
+            df = df.filter(predicate)?;
+        }
+    }
+    if let Some(sample_interval_in_secs) = selection.sample_interval_in_secs {
+        if sample_interval_in_secs == 0 {
+            anyhow::bail!("sampleIntervalInSecs must be greater than 0");
+        }
+        df = sample_per_vehicle(
+            df,
+            i64::from(sample_interval_in_secs) * 1000,
+            selection.sample_method,
+        )?;
+    }
+    if let Some(cursor) = selection.cursor {
+        let predicate = crate::utils::pagination::cursor_predicate(cursor, df.schema())?;
+        df = df.filter(predicate)?;
+    }
+    if selection.rank_duplicates {
+        let duplicate_rank = row_number()
+            .partition_by(vec![col(r#""vehicleId""#), col("timestamp")])
+            .build()?
+            .alias("duplicate_rank");
+        df = df.window(vec![duplicate_rank])?;
+    }
+    if let Some(order_by) = selection.order_by {
+        df = df.sort(crate::utils::pagination::sort_exprs(order_by))?;
+    }
+    Ok(df)
+}
+
+/// The number of columns of the vehicle results, before the optional `duplicate_rank` column.
+pub const RESULT_COLUMN_COUNT: usize = 9;
+
+/// Selects the columns of the vehicle results, followed by the `duplicate_rank` column when present.
+pub fn plain_result_columns(df: DataFrame) -> anyhow::Result<DataFrame> {
+    let mut columns = vec![
+        col("timestamp"),
+        col(r#""vehicleId""#),
+        col(r#""vehicleType""#),
+        col("gps_lat"),
+        col("gps_lon"),
+        col("gps_alt"),
+        col(r#""geoHash""#),
+        col("speed"),
+        col("direction"),
+    ];
+    if df
+        .schema()
+        .has_column_with_unqualified_name("duplicate_rank")
+    {
+        columns.push(col("duplicate_rank"));
+    }
+    Ok(with_plain_strings(df.select(columns)?)?)
+}
+
+/// Casts the string and binary views to plain columns, which all the Arrow clients and tools can read.
+pub fn with_plain_strings(df: DataFrame) -> datafusion::error::Result<DataFrame> {
+    let has_views = df
+        .schema()
+        .fields()
+        .iter()
+        .any(|field| plain_type(field.data_type()).is_some());
+    if !has_views {
+        return Ok(df);
+    }
+    let columns = df
+        .schema()
+        .iter()
+        .map(|(qualifier, field)| {
+            let column = Expr::Column(datafusion::common::Column::from((qualifier, field)));
+            match plain_type(field.data_type()) {
+                Some(data_type) => {
+                    cast(column, data_type).alias_qualified(qualifier.cloned(), field.name())
+                }
+                None => column,
+            }
+        })
+        .collect::<Vec<_>>();
+    df.select(columns)
+}
+
+pub fn plain_type(data_type: &DataType) -> Option<DataType> {
+    match data_type {
+        DataType::Utf8View => Some(DataType::Utf8),
+        DataType::BinaryView => Some(DataType::Binary),
+        _ => None,
+    }
+}
+
+/// The column of the grid cell containing each row.
+pub fn grid_column_expr(grid: &crate::utils::geo::MapGrid) -> Expr {
+    cast(
+        floor((col("gps_lon") - lit(grid.origin.x)) / lit(grid.cell_width_in_degrees)),
+        DataType::Int64,
+    )
+}
+
+/// The row of the grid cell containing each row.
+pub fn grid_row_expr(grid: &crate::utils::geo::MapGrid) -> Expr {
+    cast(
+        floor((col("gps_lat") - lit(grid.origin.y)) / lit(grid.cell_height_in_degrees)),
+        DataType::Int64,
+    )
+}
+
+/// The zones of the generator's map.
+pub fn map_zones(
+    generator: &crate::config::GeneratorConfig,
+) -> anyhow::Result<crate::utils::geo::MapZones> {
+    let map = &generator.map;
+    let zone_size = &generator.zoneSize;
+    if zone_size.widthInKm <= 0.0 || zone_size.heightInKm <= 0.0 {
+        anyhow::bail!("The zone size of the generator must be greater than 0");
+    }
+    if map.widthInKm <= 0.0 || map.heightInKm <= 0.0 {
+        anyhow::bail!("The map size of the generator must be greater than 0");
+    }
+    Ok(crate::utils::geo::MapZones::new(
+        geo::coord! { x: map.topLeftOrigin.lon, y: map.topLeftOrigin.lat },
+        map.widthInKm * 1000.0,
+        map.heightInKm * 1000.0,
+        zone_size.widthInKm * 1000.0,
+        zone_size.heightInKm * 1000.0,
+    ))
+}
+
+/// The zone containing each row, or null outside of the map.
+pub fn zone_expr(zones: &crate::utils::geo::MapZones) -> anyhow::Result<Expr> {
+    let column = grid_column_expr(&zones.grid);
+    let row = grid_row_expr(&zones.grid);
+    Ok(when(
+        column
+            .clone()
+            .between(lit(0i64), lit(zones.column_count - 1))
+            .and(row.clone().between(lit(0i64), lit(zones.row_count - 1))),
+        cast(
+            column * lit(zones.row_count) + row + lit(1i64),
+            DataType::Utf8,
+        ),
+    )
+    .end()?)
+}
+
+/// The start of the time bucket containing each row, in milliseconds since the epoch.
+pub fn time_bucket_expr(schema: &DFSchema, interval_in_min: u32) -> anyhow::Result<Expr> {
+    if interval_in_min == 0 {
+        anyhow::bail!("The bucket interval must be greater than 0");
+    }
+    let interval = ScalarValue::new_interval_mdn(0, 0, i64::from(interval_in_min) * 60_000_000_000);
+    let origin = timestamp_literal(schema, DateTime::UNIX_EPOCH)?;
+    // date_bin yields nanoseconds
+    Ok(cast(
+        cast(
+            date_bin(lit(interval), col("timestamp"), origin),
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+        ),
+        DataType::Int64,
+    ))
+}
+
+/// Creates a literal that can be compared with the `timestamp` column, using the same timezone.
+pub fn timestamp_literal(schema: &DFSchema, timestamp: DateTime<Utc>) -> anyhow::Result<Expr> {
+    let timezone = match schema.field_with_unqualified_name("timestamp")?.data_type() {
+        DataType::Timestamp(_, tz) => tz.clone(),
+        _ => None,
+    };
+    Ok(lit(ScalarValue::TimestampMillisecond(
+        Some(timestamp.timestamp_millis()),
+        timezone,
+    )))
+}
+
+/// Positions of the `events` columns in the schema of a DataFrame.
+#[derive(Debug, Clone)]
+pub struct VehicleEventColumns {
+    timestamp: usize,
+    lat: usize,
+    lon: usize,
+    alt: usize,
+    vehicle_id: usize,
+    vehicle_type: usize,
+    direction: usize,
+    speed: usize,
+    geo_hash: usize,
+}
+
+impl VehicleEventColumns {
+    pub fn try_new(schema: &DFSchema) -> anyhow::Result<Self> {
+        Ok(Self {
+            timestamp: schema.index_of_column(&Column::from_qualified_name("timestamp"))?,
+            lat: schema.index_of_column(&Column::from_qualified_name("gps_lat"))?,
+            lon: schema.index_of_column(&Column::from_qualified_name("gps_lon"))?,
+            alt: schema.index_of_column(&Column::from_qualified_name("gps_alt"))?,
+            vehicle_id: schema.index_of_column(&Column::from_qualified_name(r#""vehicleId""#))?,
+            vehicle_type: schema
+                .index_of_column(&Column::from_qualified_name(r#""vehicleType""#))?,
+            direction: schema.index_of_column(&Column::from_qualified_name("direction"))?,
+            speed: schema.index_of_column(&Column::from_qualified_name("speed"))?,
+            geo_hash: schema.index_of_column(&Column::from_qualified_name(r#""geoHash""#))?,
+        })
+    }
+
+    pub fn read<'a>(&self, batch: &'a RecordBatch) -> anyhow::Result<VehicleEventBatch<'a>> {
+        Ok(VehicleEventBatch {
+            timestamp: downcast_column(batch, self.timestamp, "timestamp")?,
+            lat: downcast_column(batch, self.lat, "gps_lat")?,
+            lon: downcast_column(batch, self.lon, "gps_lon")?,
+            alt: downcast_column(batch, self.alt, "gps_alt")?,
+            vehicle_id: downcast_column(batch, self.vehicle_id, "vehicleId")?,
+            vehicle_type: downcast_column(batch, self.vehicle_type, "vehicleType")?,
+            direction: downcast_column(batch, self.direction, "direction")?,
+            speed: downcast_column(batch, self.speed, "speed")?,
+            geo_hash: downcast_column(batch, self.geo_hash, "geoHash")?,
+        })
+    }
+}
+
+fn downcast_column<'a, T: Array + 'static>(
+    batch: &'a RecordBatch,
+    index: usize,
+    name: &str,
+) -> anyhow::Result<&'a T> {
+    batch
+        .column(index)
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| {
+            anyhow::format_err!(
+                "Unable to cast column '{}' with unexpected type {}",
+                name,
+                batch.column(index).data_type()
+            )
+        })
+}
+
+pub struct VehicleEventBatch<'a> {
+    timestamp: &'a TimestampMillisecondArray,
+    lat: &'a Float64Array,
+    lon: &'a Float64Array,
+    alt: &'a Float64Array,
+    vehicle_id: &'a StringViewArray,
+    vehicle_type: &'a StringViewArray,
+    direction: &'a StringViewArray,
+    speed: &'a Float64Array,
+    geo_hash: &'a StringViewArray,
+}
+
+impl<'a> VehicleEventBatch<'a> {
+    pub fn len(&self) -> usize {
+        self.timestamp.len()
+    }
+
+    pub fn event(&self, i: usize) -> VehicleEvent<'a> {
+        VehicleEvent {
+            timestamp: self.timestamp.value(i),
+            lat: self.lat.value(i),
+            lon: self.lon.value(i),
+            alt: self.alt.value(i),
+            vehicle_id: self.vehicle_id.value(i),
+            vehicle_type: self.vehicle_type.value(i),
+            direction: self.direction.value(i),
+            speed: self.speed.value(i),
+            geo_hash: self.geo_hash.value(i),
+        }
+    }
+}
+
+/// A single row of the `events` table.
+#[derive(Debug, Clone, Copy)]
+pub struct VehicleEvent<'a> {
+    pub timestamp: i64,
+    pub lat: f64,
+    pub lon: f64,
+    pub alt: f64,
+    pub vehicle_id: &'a str,
+    pub vehicle_type: &'a str,
+    pub direction: &'a str,
+    pub speed: f64,
+    pub geo_hash: &'a str,
+}
+
+impl VehicleEvent<'_> {
+    pub fn datetime(&self) -> anyhow::Result<DateTime<Utc>> {
+        crate::utils::time::datetime_from_millis(self.timestamp)
+    }
+
+    pub fn has_valid_position(&self) -> bool {
+        !(self.lat.is_nan() || self.lon.is_nan() || self.alt.is_nan())
+    }
+
+    pub fn to_result(self, query_id: &str) -> anyhow::Result<crate::types::VehicleQueryResult> {
+        Ok(crate::types::VehicleQueryResult {
+            msg_type: "vehicle-query-result".to_string(),
+            query_id: query_id.to_string(),
+            timestamp: self.datetime()?.to_rfc3339(),
+            vehicle_id: self.vehicle_id.to_string(),
+            vehicle_type: self.vehicle_type.to_string(),
+            gps: crate::types::GpsCoordinates {
+                lat: self.lat,
+                lon: self.lon,
+                alt: 0.0,
+            },
+            direction: self.direction.to_string(),
+            speed: self.speed,
+            geo_hash: self.geo_hash.to_string(),
+        })
+    }
+}
+
+/// A position of a vehicle, detached from its record batch.
+#[derive(Debug, Clone)]
+pub struct VehicleFix {
+    pub timestamp: i64,
+    pub lat: f64,
+    pub lon: f64,
+    pub vehicle_type: String,
+    pub direction: String,
+    pub speed: f64,
+}
+
+impl From<VehicleEvent<'_>> for VehicleFix {
+    fn from(event: VehicleEvent<'_>) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            lat: event.lat,
+            lon: event.lon,
+            vehicle_type: event.vehicle_type.to_string(),
+            direction: event.direction.to_string(),
+            speed: event.speed,
+        }
+    }
+}
+
+impl VehicleFix {
+    /// Linear interpolation between this fix and the next one, at the given time.
+    pub fn interpolate(&self, next: &VehicleFix, timestamp: i64) -> VehicleFix {
+        let ratio = if next.timestamp > self.timestamp {
+            (timestamp - self.timestamp) as f64 / (next.timestamp - self.timestamp) as f64
+        } else {
+            0.0
+        };
+        VehicleFix {
+            timestamp,
+            lat: self.lat + (next.lat - self.lat) * ratio,
+            lon: self.lon + (next.lon - self.lon) * ratio,
+            vehicle_type: self.vehicle_type.clone(),
+            direction: self.direction.clone(),
+            speed: self.speed + (next.speed - self.speed) * ratio,
+        }
+    }
+}
+
+/// Keeps a single row per vehicle: the latest one when `latest` is true, the earliest one otherwise.
+pub fn first_fix_per_vehicle(df: DataFrame, latest: bool) -> anyhow::Result<DataFrame> {
+    let rank = row_number()
+        .partition_by(vec![col(r#""vehicleId""#)])
+        .order_by(vec![col("timestamp").sort(!latest, false)])
+        .build()?
+        .alias("fix_rank");
+    Ok(df
+        .window(vec![rank])?
+        .filter(col("fix_rank").eq(lit(1u64)))?)
+}
+
+/// Keeps at most one row per vehicle and sample interval: the first or last position within the
+/// interval, or the average of its positions stamped with the time of the first one.
+pub fn sample_per_vehicle(
+    df: DataFrame,
+    sample_interval_in_ms: i64,
+    method: crate::types::VehicleSampleMethod,
+) -> anyhow::Result<DataFrame> {
+    let sample = (cast(col("timestamp"), DataType::Int64) / lit(sample_interval_in_ms))
+        * lit(sample_interval_in_ms);
+    let df = df.with_column("sample", sample)?;
+    let latest = match method {
+        crate::types::VehicleSampleMethod::First => false,
+        crate::types::VehicleSampleMethod::Last => true,
+        crate::types::VehicleSampleMethod::Average => {
+            let by_time = || vec![col("timestamp").sort(true, false)];
+            return Ok(df.aggregate(
+                vec![col(r#""vehicleId""#), col("sample")],
+                vec![
+                    min(col("timestamp")).alias("timestamp"),
+                    last_value(col(r#""vehicleType""#), by_time()).alias("vehicleType"),
+                    avg(col("gps_lat")).alias("gps_lat"),
+                    avg(col("gps_lon")).alias("gps_lon"),
+                    avg(col("gps_alt")).alias("gps_alt"),
+                    last_value(col(r#""geoHash""#), by_time()).alias("geoHash"),
+                    avg(col("speed")).alias("speed"),
+                    last_value(col("direction"), by_time()).alias("direction"),
+                ],
+            )?);
+        }
+    };
+    let rank = row_number()
+        .partition_by(vec![col(r#""vehicleId""#), col("sample")])
+        .order_by(vec![col("timestamp").sort(!latest, false)])
+        .build()?
+        .alias("sample_rank");
+    Ok(df
+        .window(vec![rank])?
+        .filter(col("sample_rank").eq(lit(1u64)))?)
+}
+
+pub async fn collect_fixes(
+    df: DataFrame,
+    start_time: &Instant,
+    query_timeout: u128,
+) -> anyhow::Result<(HashMap<String, VehicleFix>, bool)> {
+    let columns = VehicleEventColumns::try_new(df.schema())?;
+    let mut fixes = HashMap::new();
+    let mut stream = df.execute_stream().await?;
+    while let Some(batch_result) = stream.next().await {
+        let batch = batch_result?;
+        let events = columns.read(&batch)?;
+        for i in 0..events.len() {
+            let event = events.event(i);
+            if event.has_valid_position() {
+                fixes.insert(event.vehicle_id.to_string(), VehicleFix::from(event));
+            }
+        }
+        if start_time.elapsed().as_millis() >= query_timeout {
+            return Ok((fixes, true));
+        }
+    }
+    Ok((fixes, false))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use datafusion::arrow::array::StringArray;
+    use datafusion::arrow::datatypes::{Field, Schema};
+    use datafusion::datasource::MemTable;
+
+    /// A move of the in-memory `events` table of the tests.
+    pub struct TestEvent<'a> {
+        pub vehicle_id: &'a str,
+        pub vehicle_type: &'a str,
+        pub timestamp: &'a str,
+        pub lon: f64,
+        pub lat: f64,
+    }
+
+    /// A session with an `events` table holding the given moves, with the string views and the
+    /// `start` and `pk` partition columns of the collector files.
+    pub fn events_session(events: &[TestEvent]) -> anyhow::Result<SessionContext> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new("vehicleId", DataType::Utf8View, false),
+            Field::new("vehicleType", DataType::Utf8View, false),
+            Field::new("gps_lat", DataType::Float64, false),
+            Field::new("gps_lon", DataType::Float64, false),
+            Field::new("gps_alt", DataType::Float64, false),
+            Field::new("geoHash", DataType::Utf8View, false),
+            Field::new("speed", DataType::Float64, false),
+            Field::new("direction", DataType::Utf8View, false),
+            Field::new("start", DataType::Utf8, false),
+            Field::new("pk", DataType::Utf8, false),
+        ]));
+        let mut timestamps = vec![];
+        let mut geo_hashes = vec![];
+        let mut starts = vec![];
+        for event in events {
+            let datetime: DateTime<Utc> = event.timestamp.parse()?;
+            let (start, _) = crate::utils::time::round_datetime_modulo_minutes(datetime, 10);
+            timestamps.push(datetime.timestamp_millis());
+            geo_hashes.push(geohash::encode(
+                geo::coord! { x: event.lon, y: event.lat },
+                7,
+            )?);
+            starts.push(start.format("%Y-%m-%d-%H-%M").to_string());
+        }
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(TimestampMillisecondArray::from(timestamps).with_timezone("UTC")),
+                Arc::new(StringViewArray::from_iter_values(
+                    events.iter().map(|event| event.vehicle_id),
+                )),
+                Arc::new(StringViewArray::from_iter_values(
+                    events.iter().map(|event| event.vehicle_type),
+                )),
+                Arc::new(Float64Array::from_iter_values(
+                    events.iter().map(|event| event.lat),
+                )),
+                Arc::new(Float64Array::from_iter_values(
+                    events.iter().map(|event| event.lon),
+                )),
+                Arc::new(Float64Array::from(vec![0.0; events.len()])),
+                Arc::new(StringViewArray::from_iter_values(&geo_hashes)),
+                Arc::new(Float64Array::from(vec![30.0; events.len()])),
+                Arc::new(StringViewArray::from(vec!["north"; events.len()])),
+                Arc::new(StringArray::from_iter_values(&starts)),
+                Arc::new(StringArray::from_iter_values(
+                    geo_hashes.iter().map(|geo_hash| &geo_hash[..5]),
+                )),
+            ],
+        )?;
+        let session = SessionContext::new();
+        session.register_table(
+            "events",
+            Arc::new(MemTable::try_new(schema, vec![vec![batch]])?),
+        )?;
+        Ok(session)
+    }
+
+    fn filter(fields: serde_json::Value) -> anyhow::Result<VehicleFilter> {
+        let mut query = serde_json::json!({
+            "type": "vehicle-query-request",
+            "id": "q1",
+            "fromDate": "2024-01-01T10:00:00Z",
+            "toDate": "2024-01-01T11:00:00Z",
+        });
+        query
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        VehicleFilter::from_query(&serde_json::from_value(query)?)
+    }
+
+    #[test]
+    fn accepts_the_filters_with_an_area_or_vehicle_ids() {
+        let circle = serde_json::json!({
+            "center": { "type": "Point", "coordinates": [4.35, 50.85] },
+            "radiusInMeters": 500.0,
+        });
+        assert!(filter(serde_json::json!({ "vehicleIds": ["a"] })).is_ok());
+        assert!(filter(serde_json::json!({ "circle": circle })).is_ok());
+    }
+
+    #[test]
+    fn rejects_the_invalid_filters() {
+        let circle = |radius_in_meters: f64| {
+            serde_json::json!({
+                "center": { "type": "Point", "coordinates": [4.35, 50.85] },
+                "radiusInMeters": radius_in_meters,
+            })
+        };
+        let cases = [
+            (
+                serde_json::json!({ "vehicleTypes": ["taxi"] }),
+                "A geometry, circle or corridor is required when no vehicleIds are specified",
+            ),
+            (
+                serde_json::json!({ "vehicleIds": ["a"], "toDate": "2024-01-01T09:00:00Z" }),
+                "fromDate must be before toDate",
+            ),
+            (
+                serde_json::json!({ "circle": circle(0.0) }),
+                "The radius of the circle must be greater than 0",
+            ),
+            (
+                serde_json::json!({
+                    "corridor": {
+                        "line": { "type": "LineString", "coordinates": [[4.35, 50.85], [4.36, 50.86]] },
+                        "bufferInMeters": -1.0,
+                    },
+                }),
+                "The buffer of the corridor must be greater than 0",
+            ),
+        ];
+        for (fields, message) in cases {
+            let error = filter(fields.clone()).unwrap_err();
+            assert_eq!(error.to_string(), message, "{}", fields);
+        }
+    }
+}
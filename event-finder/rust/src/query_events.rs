@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+// the events kept for the slow subscribers, which skip the older ones
+const EVENT_BUFFER_SIZE: usize = 256;
+// the results sent in a single event
+const RESULT_CHUNK_SIZE: usize = 1000;
+const PROGRESS_INTERVAL_IN_MS: u128 = 1000;
+// the stopped queries replayed to the clients subscribing just after a fast query
+const STOPPED_QUERY_RETENTION: Duration = Duration::from_secs(60);
+
+/// An event of the lifecycle of a vehicle query, sent to its subscribers.
+#[derive(Debug, Clone)]
+pub enum VehicleQueryEvent {
+    Started(crate::types::VehicleQueryStartedEvent),
+    Progress(crate::types::VehicleQueryProgressEvent),
+    Results(crate::types::VehicleQueryResultChunk),
+    Stopped(crate::types::VehicleQueryStoppedEvent),
+}
+
+impl VehicleQueryEvent {
+    pub fn msg_type(&self) -> &str {
+        match self {
+            Self::Started(event) => &event.msg_type,
+            Self::Progress(event) => &event.msg_type,
+            Self::Results(event) => &event.msg_type,
+            Self::Stopped(event) => &event.msg_type,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        match self {
+            Self::Started(event) => serde_json::to_string(event),
+            Self::Progress(event) => serde_json::to_string(event),
+            Self::Results(event) => serde_json::to_string(event),
+            Self::Stopped(event) => serde_json::to_string(event),
+        }
+    }
+}
+
+struct QueryChannel {
+    sender: broadcast::Sender<VehicleQueryEvent>,
+    // replayed to the clients subscribing while the query runs
+    started: Option<VehicleQueryEvent>,
+    // replayed to the clients subscribing until the channel expires
+    stopped: Option<(VehicleQueryEvent, Instant)>,
+}
+
+impl QueryChannel {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self {
+            sender,
+            started: None,
+            stopped: None,
+        }
+    }
+}
+
+/// Forgets the queries stopped for longer than the retention period.
+fn remove_expired_queries(queries: &mut HashMap<String, QueryChannel>, now: Instant) {
+    queries.retain(|_, channel| match &channel.stopped {
+        Some((_, stopped_at)) => now.duration_since(*stopped_at) < STOPPED_QUERY_RETENTION,
+        None => true,
+    });
+}
+
+/// Dispatches the events of the running vehicle queries, whether they were received over NATS or HTTP,
+/// to the clients subscribed to them by query id.
+/// The clients can subscribe before the query starts, or shortly after it stopped to receive its start and stop events.
+#[derive(Default)]
+pub struct QueryEventHub {
+    queries: Mutex<HashMap<String, QueryChannel>>,
+}
+
+impl QueryEventHub {
+    /// Publishes the start of a query, returning the sender of its next events.
+    pub fn start(
+        &self,
+        event: crate::types::VehicleQueryStartedEvent,
+    ) -> broadcast::Sender<VehicleQueryEvent> {
+        let mut queries = self.queries.lock().unwrap();
+        remove_expired_queries(&mut queries, Instant::now());
+        let channel = queries
+            .entry(event.query.body.id.clone())
+            .or_insert_with(QueryChannel::new);
+        let event = VehicleQueryEvent::Started(event);
+        let _ = channel.sender.send(event.clone());
+        channel.started = Some(event);
+        channel.stopped = None;
+        channel.sender.clone()
+    }
+
+    /// Publishes the end of a query, which closes the streams of its subscribers.
+    pub fn stop(&self, event: crate::types::VehicleQueryStoppedEvent) {
+        let mut queries = self.queries.lock().unwrap();
+        let now = Instant::now();
+        remove_expired_queries(&mut queries, now);
+        if let Some(channel) = queries.get_mut(&event.query.body.id) {
+            let event = VehicleQueryEvent::Stopped(event);
+            let _ = channel.sender.send(event.clone());
+            channel.stopped = Some((event, now));
+        }
+    }
+
+    /// Subscribes to the events of a query, returning the events to replay first:
+    /// its start event when it is already running, and its stop event when it recently stopped.
+    pub fn subscribe(
+        &self,
+        query_id: &str,
+    ) -> (
+        Vec<VehicleQueryEvent>,
+        broadcast::Receiver<VehicleQueryEvent>,
+    ) {
+        let mut queries = self.queries.lock().unwrap();
+        remove_expired_queries(&mut queries, Instant::now());
+        let channel = queries
+            .entry(query_id.to_string())
+            .or_insert_with(QueryChannel::new);
+        let replay = channel
+            .started
+            .iter()
+            .chain(channel.stopped.as_ref().map(|(event, _)| event))
+            .cloned()
+            .collect();
+        (replay, channel.sender.subscribe())
+    }
+
+    /// Forgets the queries that never started once their last subscriber is gone.
+    pub fn unsubscribe(&self, query_id: &str) {
+        let mut queries = self.queries.lock().unwrap();
+        if let Some(channel) = queries.get(query_id)
+            && channel.started.is_none()
+            && channel.sender.receiver_count() == 0
+        {
+            queries.remove(query_id);
+        }
+    }
+}
+
+/// Sends the progress and the results of a running query to its subscribers, if any.
+pub struct VehicleQueryReporter {
+    sender: broadcast::Sender<VehicleQueryEvent>,
+    query_id: String,
+    start_time: Instant,
+    last_progress_time: Instant,
+    results: Vec<crate::types::VehicleQueryResult>,
+}
+
+impl VehicleQueryReporter {
+    pub fn new(sender: broadcast::Sender<VehicleQueryEvent>, query_id: &str) -> Self {
+        Self {
+            sender,
+            query_id: query_id.to_string(),
+            start_time: Instant::now(),
+            last_progress_time: Instant::now(),
+            results: vec![],
+        }
+    }
+
+    pub fn add_result(&mut self, result: &crate::types::VehicleQueryResult) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        self.results.push(result.clone());
+        if self.results.len() >= RESULT_CHUNK_SIZE {
+            self.flush_results();
+        }
+    }
+
+    pub fn flush_results(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let _ = self.sender.send(VehicleQueryEvent::Results(
+            crate::types::VehicleQueryResultChunk {
+                msg_type: "vehicle-query-result-chunk".to_string(),
+                query_id: self.query_id.clone(),
+                results: std::mem::take(&mut self.results),
+            },
+        ));
+    }
+
+    /// Sends the progress of the query, at most once per interval.
+    pub fn report_progress(
+        &mut self,
+        processed_files_count: usize,
+        processed_record_count: usize,
+        selected_record_count: usize,
+    ) {
+        self.flush_results();
+        if self.sender.receiver_count() == 0
+            || self.last_progress_time.elapsed().as_millis() < PROGRESS_INTERVAL_IN_MS
+        {
+            return;
+        }
+        self.last_progress_time = Instant::now();
+        let _ = self.sender.send(VehicleQueryEvent::Progress(
+            crate::types::VehicleQueryProgressEvent {
+                msg_type: "vehicle-query-progress".to_string(),
+                query_id: self.query_id.clone(),
+                processed_files_count,
+                processed_record_count,
+                selected_record_count,
+                elapsed_time_in_MS: self.start_time.elapsed().as_millis(),
+            },
+        ));
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn query(query_id: &str) -> crate::types::Request<crate::types::VehicleQueryRequest> {
+        serde_json::from_value(serde_json::json!({
+            "id": "request-1",
+            "type": "request",
+            "replyTo": "test.query",
+            "body": {
+                "type": "vehicle-query-request",
+                "id": query_id,
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+            },
+        }))
+        .unwrap()
+    }
+
+    pub fn started(query_id: &str) -> crate::types::VehicleQueryStartedEvent {
+        crate::types::VehicleQueryStartedEvent {
+            msg_type: "vehicle-query-started".to_string(),
+            query: query(query_id),
+        }
+    }
+
+    pub fn stopped(query_id: &str) -> crate::types::VehicleQueryStoppedEvent {
+        crate::types::VehicleQueryStoppedEvent {
+            msg_type: "vehicle-query-stopped".to_string(),
+            query: query(query_id),
+            is_success: true,
+            response: None,
+            error: None,
+        }
+    }
+
+    fn result(vehicle_id: &str) -> crate::types::VehicleQueryResult {
+        crate::types::VehicleQueryResult {
+            msg_type: "vehicle-query-result".to_string(),
+            query_id: "q1".to_string(),
+            timestamp: "2024-01-01T10:00:00Z".to_string(),
+            vehicle_id: vehicle_id.to_string(),
+            vehicle_type: "car".to_string(),
+            gps: crate::types::GpsCoordinates {
+                lat: 50.85,
+                lon: 4.35,
+                alt: 0.0,
+            },
+            direction: "north".to_string(),
+            speed: 10.0,
+            geo_hash: "u150upk".to_string(),
+        }
+    }
+
+    fn msg_types(events: &[VehicleQueryEvent]) -> Vec<&str> {
+        events.iter().map(|event| event.msg_type()).collect()
+    }
+
+    #[test]
+    fn streams_the_events_to_the_clients_subscribed_before_the_start() {
+        let hub = QueryEventHub::default();
+        let (replay, mut receiver) = hub.subscribe("q1");
+        assert!(replay.is_empty());
+
+        let sender = hub.start(started("q1"));
+        let mut reporter = VehicleQueryReporter::new(sender, "q1");
+        reporter.add_result(&result("a"));
+        reporter.flush_results();
+        hub.stop(stopped("q1"));
+
+        let events = std::iter::from_fn(|| receiver.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(
+            msg_types(&events),
+            vec![
+                "vehicle-query-started",
+                "vehicle-query-result-chunk",
+                "vehicle-query-stopped"
+            ]
+        );
+    }
+
+    #[test]
+    fn replays_the_start_of_a_running_query() {
+        let hub = QueryEventHub::default();
+        hub.start(started("q1"));
+
+        let (replay, _) = hub.subscribe("q1");
+
+        assert_eq!(msg_types(&replay), vec!["vehicle-query-started"]);
+    }
+
+    #[test]
+    fn replays_the_start_and_the_stop_of_a_recently_stopped_query() {
+        let hub = QueryEventHub::default();
+        hub.start(started("q1"));
+        hub.stop(stopped("q1"));
+
+        let (replay, _) = hub.subscribe("q1");
+
+        assert_eq!(
+            msg_types(&replay),
+            vec!["vehicle-query-started", "vehicle-query-stopped"]
+        );
+    }
+
+    #[test]
+    fn forgets_the_stopped_queries_after_the_retention_period() {
+        let hub = QueryEventHub::default();
+        hub.start(started("q1"));
+        hub.stop(stopped("q1"));
+
+        remove_expired_queries(
+            &mut hub.queries.lock().unwrap(),
+            Instant::now() + STOPPED_QUERY_RETENTION,
+        );
+        let (replay, _) = hub.subscribe("q1");
+
+        assert!(replay.is_empty());
+    }
+
+    #[test]
+    fn forgets_the_queries_that_never_started_without_subscribers() {
+        let hub = QueryEventHub::default();
+        let (_, receiver) = hub.subscribe("q1");
+        hub.start(started("q2"));
+        let (_, other_receiver) = hub.subscribe("q2");
+
+        drop((receiver, other_receiver));
+        hub.unsubscribe("q1");
+        hub.unsubscribe("q2");
+
+        let queries = hub.queries.lock().unwrap();
+        assert!(!queries.contains_key("q1"));
+        assert!(queries.contains_key("q2"));
+    }
+
+    #[test]
+    fn sends_the_results_by_chunks_only_to_the_subscribers() {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        let mut reporter = VehicleQueryReporter::new(sender.clone(), "q1");
+        reporter.add_result(&result("ignored"));
+        assert!(reporter.results.is_empty());
+
+        let mut receiver = sender.subscribe();
+        for _ in 0..=RESULT_CHUNK_SIZE {
+            reporter.add_result(&result("a"));
+        }
+        // the progress of the start of the query is throttled, but the pending results are flushed
+        reporter.report_progress(1, 10, RESULT_CHUNK_SIZE + 1);
+
+        let chunk_sizes = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|event| match event {
+                VehicleQueryEvent::Results(chunk) => chunk.results.len(),
+                event => panic!("unexpected {} event", event.msg_type()),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(chunk_sizes, vec![RESULT_CHUNK_SIZE, 1]);
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+pub const DEFAULT_QUERY_LIMIT: u64 = 100;
+
 pub trait HasMessageType: for<'de> Deserialize<'de> + Serialize + Clone + 'static {
     fn get_msg_type(&self) -> &str;
 }
@@ -12,13 +14,59 @@ pub struct VehicleQueryRequest {
     pub id: String,
     pub from_date: String,
     pub to_date: String,
-    pub geometry: geojson::Geometry,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
     pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub min_speed: Option<f64>,
+    pub max_speed: Option<f64>,
+    #[serde(default)]
+    pub directions: Vec<String>,
     pub limit: Option<u64>,
     pub timeout: Option<u128>,
     pub ttl: Option<String>,
     pub parallelize: Option<bool>,
     pub use_chunking: Option<bool>,
+    pub order_by: Option<VehicleQueryOrder>,
+    pub cursor: Option<String>,
+    pub deduplicate: Option<bool>,
+    pub sample_interval_in_secs: Option<u32>,
+    pub sample_method: Option<VehicleSampleMethod>,
+    pub format: Option<TableFormat>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CircleArea {
+    pub center: geojson::Geometry, // Point
+    pub radius_in_meters: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CorridorArea {
+    pub line: geojson::Geometry, // LineString
+    pub buffer_in_meters: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VehicleQueryOrder {
+    TimestampAsc,
+    TimestampDesc,
+    VehicleIdTimestamp,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum VehicleSampleMethod {
+    #[default]
+    First,
+    Last,
+    Average,
 }
 
 impl HasMessageType for VehicleQueryRequest {
@@ -40,6 +88,11 @@ pub struct VehicleQueryResponse {
     pub elapsed_time_in_MS: u128,
     pub timeout_expired: bool,
     pub limit_reached: bool,
+    #[serde(default)]
+    pub from_cache: bool,
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub duplicate_count: usize,
 }
 
 impl HasMessageType for VehicleQueryResponse {
@@ -206,6 +259,39 @@ impl HasMessageType for VehicleQueryStoppedEvent {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleQueryProgressEvent {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-query-progress"
+    pub query_id: String,
+    pub processed_files_count: usize,
+    pub processed_record_count: usize,
+    pub selected_record_count: usize,
+    pub elapsed_time_in_MS: u128,
+}
+
+impl HasMessageType for VehicleQueryProgressEvent {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleQueryResultChunk {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-query-result-chunk"
+    pub query_id: String,
+    pub results: Vec<VehicleQueryResult>,
+}
+
+impl HasMessageType for VehicleQueryResultChunk {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ServiceIdentity {
@@ -256,3 +342,792 @@ impl HasMessageType for VehicleGenerationStopped {
         &self.msg_type
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregatePeriodCreated {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "aggregate-period-created"
+    pub collector_index: usize,
+    pub from_time: String,
+    pub to_time: String,
+    pub partition_key: String,
+    pub is_partial: bool,
+    pub event_count: usize,
+    #[serde(default)]
+    pub partitions: Vec<DataPartitionStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataPartitionStats {
+    pub url: String,
+    pub item_count: usize,
+    pub partition_key: String,
+}
+
+impl HasMessageType for AggregatePeriodCreated {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleTrajectoryRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-trajectory-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub simplify_tolerance_in_meters: Option<f64>,
+    pub limit: Option<u64>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleTrajectoryRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleTrajectoryResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-trajectory-result"
+    pub query_id: String,
+    pub feature: geojson::Feature,
+}
+
+impl HasMessageType for VehicleTrajectoryResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleTrajectoryResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-trajectory-response"
+    pub processed_record_count: usize,
+    pub selected_record_count: usize,
+    pub trajectory_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+    pub limit_reached: bool,
+}
+
+impl HasMessageType for VehicleTrajectoryResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleSnapshotRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-snapshot-request"
+    pub id: String,
+    pub timestamp: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub look_back_in_min: Option<u32>,
+    pub interpolate: Option<bool>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleSnapshotRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleSnapshotResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-snapshot-result"
+    pub query_id: String,
+    pub timestamp: String,
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub gps: GpsCoordinates,
+    pub direction: String,
+    pub speed: f64,
+    pub interpolated: bool,
+}
+
+impl HasMessageType for VehicleSnapshotResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleSnapshotResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-snapshot-response"
+    pub vehicle_count: usize,
+    pub interpolated_vehicle_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+}
+
+impl HasMessageType for VehicleSnapshotResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleNearestRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-nearest-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub point: geojson::Geometry,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    pub k: usize,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleNearestRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleNearestResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-nearest-result"
+    pub query_id: String,
+    pub rank: usize,
+    pub distance_in_meters: f64,
+    pub timestamp: String,
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub gps: GpsCoordinates,
+    pub direction: String,
+    pub speed: f64,
+}
+
+impl HasMessageType for VehicleNearestResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleNearestResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-nearest-response"
+    pub vehicle_count: usize,
+    pub ring_count: usize,
+    pub partition_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+}
+
+impl HasMessageType for VehicleNearestResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleDensityRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-density-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub precision: Option<u8>,
+    pub grid_cell_size_in_meters: Option<f64>,
+    pub bucket_interval_in_min: Option<u32>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleDensityRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleDensityResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-density-result"
+    pub query_id: String,
+    pub bucket_start: Option<String>,
+    pub feature_collection: geojson::FeatureCollection,
+}
+
+impl HasMessageType for VehicleDensityResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleDensityResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-density-response"
+    pub cell_count: usize,
+    pub bucket_count: usize,
+    pub event_count: i64,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+}
+
+impl HasMessageType for VehicleDensityResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TableFormat {
+    Json,
+    Arrow,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleStatsRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-stats-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub bucket_interval_in_min: Option<u32>,
+    pub group_by_zone: Option<bool>,
+    pub format: Option<TableFormat>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleStatsRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleStatsResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-stats-result"
+    pub query_id: String,
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub rows: Vec<Vec<serde_json::Value>>,
+    pub arrow_ipc: Option<String>, // base64 Arrow IPC stream, when the format is arrow
+}
+
+impl HasMessageType for VehicleStatsResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleStatsResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-stats-response"
+    pub row_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+}
+
+impl HasMessageType for VehicleStatsResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleOdMatrixRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-od-matrix-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub group_by_vehicle_type: Option<bool>,
+    pub stop_max_speed: Option<f64>,
+    pub stop_radius_in_meters: Option<f64>,
+    pub stop_min_duration_in_min: Option<u32>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleOdMatrixRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OdMatrix {
+    pub vehicle_type: Option<String>,
+    // trip_counts[origin - origin_offset][destination], in the order of the zone ids
+    pub trip_counts: Vec<Vec<u64>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleOdMatrixResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-od-matrix-result"
+    pub query_id: String,
+    // index of the first origin zone of the rows
+    pub origin_offset: usize,
+    // only sent with the first rows
+    #[serde(default)]
+    pub zone_ids: Vec<String>,
+    pub zones: Option<geojson::FeatureCollection>,
+    pub matrices: Vec<OdMatrix>,
+}
+
+impl HasMessageType for VehicleOdMatrixResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleOdMatrixResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-od-matrix-response"
+    pub processed_record_count: usize,
+    pub vehicle_count: usize,
+    pub trip_count: u64,
+    pub outside_trip_count: u64,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+}
+
+impl HasMessageType for VehicleOdMatrixResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleStopsRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-stops-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub max_speed: Option<f64>,
+    pub radius_in_meters: Option<f64>,
+    pub min_duration_in_min: Option<u32>,
+    pub limit: Option<u64>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleStopsRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleStopResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-stop-result"
+    pub query_id: String,
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub gps: GpsCoordinates,
+    pub start_time: String,
+    pub end_time: String,
+    pub duration_in_secs: f64,
+    pub point_count: usize,
+}
+
+impl HasMessageType for VehicleStopResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleStopsResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-stops-response"
+    pub processed_record_count: usize,
+    pub vehicle_count: usize,
+    pub stop_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+    pub limit_reached: bool,
+}
+
+impl HasMessageType for VehicleStopsResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleEncountersRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-encounters-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub distance_in_meters: f64,
+    pub min_duration_in_secs: u32,
+    pub sample_interval_in_secs: Option<u32>,
+    pub limit: Option<u64>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleEncountersRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleEncounterResult {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-encounter-result"
+    pub query_id: String,
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub other_vehicle_id: String,
+    pub other_vehicle_type: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub duration_in_secs: f64,
+    pub min_distance_in_meters: f64,
+    // midpoint of the two vehicles when they were the closest
+    pub meeting_point: GpsCoordinates,
+}
+
+impl HasMessageType for VehicleEncounterResult {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleEncountersResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-encounters-response"
+    pub processed_record_count: usize,
+    pub sample_count: usize,
+    pub encounter_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+    pub limit_reached: bool,
+}
+
+impl HasMessageType for VehicleEncountersResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveCommand {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "move"
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub zone_id: Option<String>,
+    pub direction: String,
+    pub speed: f64,
+    pub gps: GpsCoordinates,
+    pub timestamp: String,
+}
+
+impl HasMessageType for MoveCommand {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichedMoveCommand {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "enriched-move"
+    pub command: MoveCommand,
+    pub collector_index: usize,
+    pub geo_hash: String,
+    pub partition_key: String,
+}
+
+impl HasMessageType for EnrichedMoveCommand {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceSubscribeRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "geofence-subscribe-request"
+    pub id: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub lease_in_secs: Option<u64>,
+}
+
+impl HasMessageType for GeofenceSubscribeRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceSubscribeResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "geofence-subscribe-response"
+    pub subscription_id: String,
+    pub expires_at: String,
+}
+
+impl HasMessageType for GeofenceSubscribeResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceUnsubscribeRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "geofence-unsubscribe-request"
+    pub subscription_id: String,
+}
+
+impl HasMessageType for GeofenceUnsubscribeRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceUnsubscribeResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "geofence-unsubscribe-response"
+    pub subscription_id: String,
+}
+
+impl HasMessageType for GeofenceUnsubscribeResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GeofenceEventType {
+    Enter,
+    Exit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceEvent {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "geofence-event"
+    pub subscription_id: String,
+    pub event: GeofenceEventType,
+    pub timestamp: String,
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub gps: GpsCoordinates,
+    pub direction: String,
+    pub speed: f64,
+}
+
+impl HasMessageType for GeofenceEvent {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceExpired {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "geofence-expired"
+    pub subscription_id: String,
+}
+
+impl HasMessageType for GeofenceExpired {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Parquet,
+    Csv,
+    GeoJson,
+    GeoParquet,
+    Gpx,
+    Kml,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleExportRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-export-request"
+    pub id: String,
+    pub from_date: String,
+    pub to_date: String,
+    pub geometry: Option<geojson::Geometry>,
+    pub circle: Option<CircleArea>,
+    pub corridor: Option<CorridorArea>,
+    #[serde(default)]
+    pub vehicle_types: Vec<String>,
+    #[serde(default)]
+    pub vehicle_ids: Vec<String>,
+    pub min_speed: Option<f64>,
+    pub max_speed: Option<f64>,
+    #[serde(default)]
+    pub directions: Vec<String>,
+    pub deduplicate: Option<bool>,
+    pub sample_interval_in_secs: Option<u32>,
+    pub sample_method: Option<VehicleSampleMethod>,
+    pub format: ExportFormat,
+    pub max_rows_per_file: Option<usize>,
+    pub file_per_vehicle: Option<bool>,
+    pub limit: Option<u64>,
+    pub timeout: Option<u128>,
+}
+
+impl HasMessageType for VehicleExportRequest {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedFile {
+    pub path: String,
+    pub size_in_bytes: usize,
+    pub row_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleExportProgress {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-export-progress"
+    pub export_id: String,
+    pub file_count: usize,
+    pub row_count: usize,
+    pub written_bytes: usize,
+    pub elapsed_time_in_MS: u128,
+}
+
+impl HasMessageType for VehicleExportProgress {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleExportResponse {
+    #[serde(rename = "type")]
+    pub msg_type: String, // "vehicle-export-response"
+    pub files: Vec<ExportedFile>,
+    pub row_count: usize,
+    pub duplicate_count: usize,
+    pub elapsed_time_in_MS: u128,
+    pub timeout_expired: bool,
+    pub limit_reached: bool,
+}
+
+impl HasMessageType for VehicleExportResponse {
+    fn get_msg_type(&self) -> &str {
+        &self.msg_type
+    }
+}
@@ -0,0 +1,214 @@
+use async_nats::HeaderMap;
+use datafusion::arrow::array::RecordBatch;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::ipc::writer::StreamWriter;
+
+pub const ARROW_STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+// the error is sent in a header, which must fit on a single line
+const MAX_ERROR_HEADER_LENGTH: usize = 1024;
+
+/// Encodes record batches as the messages of an Arrow IPC stream: the schema first, then one message per batch
+/// and finally the end-of-stream marker. Concatenating the payloads gives a valid IPC stream.
+struct ArrowStreamEncoder {
+    writer: StreamWriter<Vec<u8>>,
+    error: Option<String>,
+}
+
+impl ArrowStreamEncoder {
+    fn try_new(schema: &Schema) -> anyhow::Result<Self> {
+        Ok(Self {
+            writer: StreamWriter::try_new(Vec::new(), schema)?,
+            error: None,
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<(HeaderMap, Vec<u8>)> {
+        self.writer.write(batch)?;
+        Ok(self.take_message("batch"))
+    }
+
+    fn finish(&mut self, error: Option<&anyhow::Error>) -> anyhow::Result<(HeaderMap, Vec<u8>)> {
+        self.error = error.map(|error| error_header_value(&error.to_string()));
+        self.writer.finish()?;
+        Ok(self.take_message("eos"))
+    }
+
+    /// Returns the pending bytes of the stream, with the headers of the message.
+    fn take_message(&mut self, message_type: &str) -> (HeaderMap, Vec<u8>) {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Type", ARROW_STREAM_CONTENT_TYPE);
+        headers.insert("arrow/message", message_type);
+        if let Some(error) = &self.error {
+            headers.insert("arrow/error", error.as_str());
+        }
+        (headers, std::mem::take(self.writer.get_mut()))
+    }
+}
+
+/// Turns the error message into a single line of bounded length, as the line breaks would end the header.
+fn error_header_value(error: &str) -> String {
+    let mut value = error
+        .split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if value.len() > MAX_ERROR_HEADER_LENGTH {
+        let mut end = MAX_ERROR_HEADER_LENGTH - "...".len();
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push_str("...");
+    }
+    value
+}
+
+/// Publishes record batches as an Arrow IPC stream, one NATS message per IPC message.
+pub struct ArrowStreamPublisher {
+    nats_client: async_nats::Client,
+    subject: String,
+    encoder: ArrowStreamEncoder,
+}
+
+impl ArrowStreamPublisher {
+    pub async fn try_new(
+        nats_client: &async_nats::Client,
+        subject: &str,
+        schema: &Schema,
+    ) -> anyhow::Result<Self> {
+        let mut encoder = ArrowStreamEncoder::try_new(schema)?;
+        let schema_message = encoder.take_message("schema");
+        let publisher = Self {
+            nats_client: nats_client.clone(),
+            subject: subject.to_string(),
+            encoder,
+        };
+        publisher.publish(schema_message).await?;
+        Ok(publisher)
+    }
+
+    /// Publishes the batch, returning the size of the message.
+    pub async fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<usize> {
+        let message = self.encoder.write(batch)?;
+        self.publish(message).await
+    }
+
+    pub async fn finish(mut self) -> anyhow::Result<()> {
+        let message = self.encoder.finish(None)?;
+        self.publish(message).await?;
+        Ok(())
+    }
+
+    /// Ends the stream after a failure, with the error in the `arrow/error` header of the end-of-stream marker,
+    /// so that the readers do not wait for the missing batches.
+    pub async fn abort(mut self, error: &anyhow::Error) -> anyhow::Result<()> {
+        let message = self.encoder.finish(Some(error))?;
+        self.publish(message).await?;
+        Ok(())
+    }
+
+    async fn publish(&self, (headers, payload): (HeaderMap, Vec<u8>)) -> anyhow::Result<usize> {
+        let size = payload.len();
+        crate::utils::messaging::publish_payload(
+            &self.nats_client,
+            self.subject.clone(),
+            Some(headers),
+            payload,
+        )
+        .await?;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{Int64Array, StringArray};
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use datafusion::arrow::ipc::reader::StreamReader;
+    use std::sync::Arc;
+
+    fn batch(vehicle_ids: &[&str]) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("timestamp", DataType::Int64, false),
+            Field::new("vehicleId", DataType::Utf8, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from_iter_values(0..vehicle_ids.len() as i64)),
+                Arc::new(StringArray::from(vehicle_ids.to_vec())),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+        headers.get(name).map(|value| value.as_str())
+    }
+
+    /// Encodes the batches, and returns the headers of the messages and the concatenation of their payloads.
+    fn encode(
+        batches: &[RecordBatch],
+        error: Option<&anyhow::Error>,
+    ) -> anyhow::Result<(Vec<HeaderMap>, Vec<u8>)> {
+        let mut encoder = ArrowStreamEncoder::try_new(&batches[0].schema())?;
+        let mut messages = vec![encoder.take_message("schema")];
+        for batch in batches {
+            messages.push(encoder.write(batch)?);
+        }
+        messages.push(encoder.finish(error)?);
+        Ok(messages.into_iter().fold(
+            (vec![], vec![]),
+            |(mut headers, mut payload), (message_headers, message_payload)| {
+                headers.push(message_headers);
+                payload.extend(message_payload);
+                (headers, payload)
+            },
+        ))
+    }
+
+    #[test]
+    fn concatenates_the_messages_into_an_ipc_stream() -> anyhow::Result<()> {
+        let batches = [batch(&["a", "b"]), batch(&["c"])];
+
+        let (headers, payload) = encode(&batches, None)?;
+
+        assert_eq!(
+            headers
+                .iter()
+                .map(|headers| header(headers, "arrow/message"))
+                .collect::<Vec<_>>(),
+            vec![Some("schema"), Some("batch"), Some("batch"), Some("eos")]
+        );
+        assert!(headers.iter().all(|headers| {
+            header(headers, "Content-Type") == Some(ARROW_STREAM_CONTENT_TYPE)
+                && header(headers, "arrow/error").is_none()
+        }));
+        let read =
+            StreamReader::try_new(payload.as_slice(), None)?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(read, batches);
+        Ok(())
+    }
+
+    #[test]
+    fn ends_the_stream_with_the_error_on_a_single_line() -> anyhow::Result<()> {
+        let batches = [batch(&["a"])];
+        let error = anyhow::format_err!("Execution error:\r\n\t{}\0", "é".repeat(1000));
+
+        let (headers, payload) = encode(&batches, Some(&error))?;
+
+        let eos = headers.last().unwrap();
+        assert_eq!(header(eos, "arrow/message"), Some("eos"));
+        let error = header(eos, "arrow/error").unwrap();
+        assert!(error.starts_with("Execution error: éé"));
+        assert!(error.ends_with("éé..."));
+        assert!(error.len() <= MAX_ERROR_HEADER_LENGTH);
+        assert!(!error.contains(|c: char| c.is_control()));
+        // the batches sent before the failure can still be read
+        let read =
+            StreamReader::try_new(payload.as_slice(), None)?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(read, batches);
+        Ok(())
+    }
+}
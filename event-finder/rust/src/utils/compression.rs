@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+use std::io::{Read, Write};
+
+pub const ACCEPT_ENCODING_HEADER: &str = "accept-encoding";
+pub const CONTENT_ENCODING_HEADER: &str = "content-encoding";
+/// The default max payload of a NATS server, used until the client has received the server info.
+pub const DEFAULT_MAX_PAYLOAD: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Zstd,
+    Gzip,
+    Lz4,
+}
+
+impl ContentEncoding {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "zstd" => Some(Self::Zstd),
+            "gzip" => Some(Self::Gzip),
+            "lz4" => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+
+    /// Picks the first supported encoding of an `accept-encoding` header, like `zstd|gzip` or `lz4, gzip`.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        accept_encoding
+            .split([',', '|'])
+            .find_map(|value| Self::parse(value.split(';').next().unwrap_or_default()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+            Self::Lz4 => "lz4",
+        }
+    }
+
+    pub fn compress(&self, payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(match self {
+            Self::Zstd => zstd::encode_all(payload, 0)?,
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(payload)?;
+                encoder.finish()?
+            }
+            Self::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                encoder.write_all(payload)?;
+                encoder.finish()?
+            }
+        })
+    }
+
+    /// Decompresses the payload, failing when it would expand beyond `max_size` bytes.
+    pub fn decompress(&self, payload: &[u8], max_size: usize) -> anyhow::Result<Vec<u8>> {
+        let decoder: Box<dyn Read + '_> = match self {
+            Self::Zstd => Box::new(zstd::Decoder::new(payload)?),
+            Self::Gzip => Box::new(flate2::read::GzDecoder::new(payload)),
+            Self::Lz4 => Box::new(lz4_flex::frame::FrameDecoder::new(payload)),
+        };
+        let mut decompressed = Vec::new();
+        decoder
+            .take(max_size as u64 + 1)
+            .read_to_end(&mut decompressed)?;
+        if decompressed.len() > max_size {
+            anyhow::bail!(
+                "The {} payload exceeds {} bytes once decompressed",
+                self.as_str(),
+                max_size
+            );
+        }
+        Ok(decompressed)
+    }
+}
+
+tokio::task_local! {
+    /// The encoding accepted by the sender of the request being processed.
+    pub static ACCEPTED_ENCODING: Option<ContentEncoding>;
+}
+
+pub fn accepted_encoding() -> Option<ContentEncoding> {
+    ACCEPTED_ENCODING
+        .try_with(|encoding| *encoding)
+        .ok()
+        .flatten()
+}
+
+pub fn negotiate_encoding(msg: &async_nats::Message) -> Option<ContentEncoding> {
+    msg.headers
+        .as_ref()
+        .and_then(|headers| headers.get(ACCEPT_ENCODING_HEADER))
+        .and_then(|value| ContentEncoding::negotiate(value.as_str()))
+}
+
+/// Returns the payload of the message, decompressed according to its `content-encoding` header
+/// up to `max_size` bytes.
+pub fn decode_payload(msg: &async_nats::Message, max_size: usize) -> anyhow::Result<Cow<'_, [u8]>> {
+    let content_encoding = msg
+        .headers
+        .as_ref()
+        .and_then(|headers| headers.get(CONTENT_ENCODING_HEADER));
+    match content_encoding {
+        Some(value) => {
+            let encoding = ContentEncoding::parse(value.as_str()).ok_or_else(|| {
+                anyhow::format_err!("Unsupported content encoding '{}'", value.as_str())
+            })?;
+            Ok(Cow::Owned(encoding.decompress(&msg.payload, max_size)?))
+        }
+        None => Ok(Cow::Borrowed(&msg.payload)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENCODINGS: [ContentEncoding; 3] = [
+        ContentEncoding::Zstd,
+        ContentEncoding::Gzip,
+        ContentEncoding::Lz4,
+    ];
+
+    fn message(content_encoding: Option<&str>, payload: Vec<u8>) -> async_nats::Message {
+        let headers = content_encoding.map(|value| {
+            let mut headers = async_nats::HeaderMap::new();
+            headers.insert(CONTENT_ENCODING_HEADER, value);
+            headers
+        });
+        async_nats::Message {
+            subject: "requests.vehicles.query".into(),
+            reply: None,
+            length: payload.len(),
+            payload: payload.into(),
+            headers,
+            status: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn negotiates_the_first_supported_encoding() {
+        assert_eq!(
+            ContentEncoding::negotiate("zstd|gzip"),
+            Some(ContentEncoding::Zstd)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate("br, gzip;q=0.8, lz4"),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate(" LZ4 "),
+            Some(ContentEncoding::Lz4)
+        );
+        assert_eq!(ContentEncoding::negotiate("br|deflate"), None);
+        assert_eq!(ContentEncoding::negotiate(""), None);
+    }
+
+    #[test]
+    fn round_trips_the_payloads_with_each_encoding() -> anyhow::Result<()> {
+        let payload = r#"{"type":"vehicle-query-result","vehicleId":"a"}"#.repeat(100);
+        for encoding in ENCODINGS {
+            let compressed = encoding.compress(payload.as_bytes())?;
+
+            assert!(compressed.len() < payload.len(), "{:?}", encoding);
+            assert_eq!(ContentEncoding::parse(encoding.as_str()), Some(encoding));
+            assert_eq!(
+                encoding.decompress(&compressed, DEFAULT_MAX_PAYLOAD)?,
+                payload.as_bytes()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn decodes_the_payloads_according_to_their_content_encoding() -> anyhow::Result<()> {
+        let payload = b"{\"type\":\"request\"}".to_vec();
+        let compressed = ContentEncoding::Gzip.compress(&payload)?;
+
+        assert_eq!(
+            decode_payload(&message(Some("gzip"), compressed), DEFAULT_MAX_PAYLOAD)?.as_ref(),
+            payload
+        );
+        assert!(matches!(
+            decode_payload(&message(None, payload.clone()), DEFAULT_MAX_PAYLOAD)?,
+            Cow::Borrowed(_)
+        ));
+        assert!(
+            decode_payload(&message(Some("br"), payload.clone()), DEFAULT_MAX_PAYLOAD).is_err()
+        );
+        assert!(decode_payload(&message(Some("zstd"), payload), DEFAULT_MAX_PAYLOAD).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_the_payloads_exceeding_the_max_size_once_decompressed() -> anyhow::Result<()> {
+        let payload = vec![0u8; 4 * DEFAULT_MAX_PAYLOAD];
+        for encoding in ENCODINGS {
+            let compressed = encoding.compress(&payload)?;
+
+            assert!(compressed.len() < DEFAULT_MAX_PAYLOAD, "{:?}", encoding);
+            assert!(
+                encoding
+                    .decompress(&compressed, DEFAULT_MAX_PAYLOAD)
+                    .is_err()
+            );
+            assert_eq!(
+                encoding.decompress(&compressed, payload.len())?.len(),
+                payload.len()
+            );
+        }
+        assert!(
+            decode_payload(
+                &message(Some("zstd"), ContentEncoding::Zstd.compress(&payload)?),
+                DEFAULT_MAX_PAYLOAD
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+}
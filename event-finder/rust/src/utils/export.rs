@@ -0,0 +1,485 @@
+use crate::types::ExportFormat;
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use datafusion::arrow::array::{Float64Array, RecordBatch, StringArray, TimestampMillisecondArray};
+use datafusion::arrow::csv::WriterBuilder;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::common::cast::{as_float64_array, as_string_array, as_timestamp_millisecond_array};
+use datafusion::parquet::arrow::ArrowWriter;
+use std::borrow::Cow;
+use std::io::Write;
+use std::sync::Arc;
+
+const GPX_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="event-finder" xmlns="http://www.topografix.com/GPX/1/1" xmlns:vehicle="urn:event-finder:vehicle">
+"#;
+const KML_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2" xmlns:gx="http://www.google.com/kml/ext/2.2">
+<Document>
+<Schema id="vehicle" name="vehicle"><gx:SimpleArrayField name="speed" type="float"/><gx:SimpleArrayField name="direction" type="string"/></Schema>
+"#;
+
+pub fn file_extension(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Parquet | ExportFormat::GeoParquet => "parquet",
+        ExportFormat::Csv => "csv",
+        ExportFormat::GeoJson => "geojson",
+        ExportFormat::Gpx => "gpx",
+        ExportFormat::Kml => "kml",
+    }
+}
+
+fn escape_xml(value: &str) -> Cow<'_, str> {
+    if !value.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;"),
+    )
+}
+
+/// The columns of an exported batch.
+struct ExportColumns<'a> {
+    timestamp: &'a TimestampMillisecondArray,
+    lat: &'a Float64Array,
+    lon: &'a Float64Array,
+    alt: &'a Float64Array,
+    speed: &'a Float64Array,
+    vehicle_id: &'a StringArray,
+    vehicle_type: &'a StringArray,
+    direction: &'a StringArray,
+    geo_hash: &'a StringArray,
+}
+
+impl<'a> ExportColumns<'a> {
+    fn try_new(batch: &'a RecordBatch) -> anyhow::Result<Self> {
+        let column = |name: &str| -> anyhow::Result<_> {
+            Ok(batch.column(batch.schema_ref().index_of(name)?))
+        };
+        Ok(Self {
+            timestamp: as_timestamp_millisecond_array(column("timestamp")?)?,
+            lat: as_float64_array(column("gps_lat")?)?,
+            lon: as_float64_array(column("gps_lon")?)?,
+            alt: as_float64_array(column("gps_alt")?)?,
+            speed: as_float64_array(column("speed")?)?,
+            vehicle_id: as_string_array(column("vehicleId")?)?,
+            vehicle_type: as_string_array(column("vehicleType")?)?,
+            direction: as_string_array(column("direction")?)?,
+            geo_hash: as_string_array(column("geoHash")?)?,
+        })
+    }
+
+    fn datetime(&self, i: usize) -> Option<DateTime<Utc>> {
+        Utc.timestamp_millis_opt(self.timestamp.value(i)).single()
+    }
+
+    fn time(&self, i: usize) -> String {
+        self.datetime(i)
+            .map(|datetime| datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            .unwrap_or_default()
+    }
+}
+
+/// The positions of a vehicle, rendered as a KML `gx:Track` once all of them are known,
+/// since the track lists all the times before all the coordinates.
+#[derive(Default)]
+pub struct KmlTrack {
+    vehicle_id: String,
+    vehicle_type: String,
+    whens: Vec<String>,
+    coords: Vec<String>,
+    speeds: Vec<f64>,
+    directions: Vec<String>,
+}
+
+impl KmlTrack {
+    fn render(&self, buffer: &mut Vec<u8>) -> anyhow::Result<()> {
+        write!(
+            buffer,
+            "<Placemark><name>{}</name><description>{}</description><gx:Track>",
+            escape_xml(&self.vehicle_id),
+            escape_xml(&self.vehicle_type)
+        )?;
+        for when in &self.whens {
+            write!(buffer, "<when>{}</when>", when)?;
+        }
+        for coord in &self.coords {
+            write!(buffer, "<gx:coord>{}</gx:coord>", coord)?;
+        }
+        write!(
+            buffer,
+            r##"<ExtendedData><SchemaData schemaUrl="#vehicle"><gx:SimpleArrayData name="speed">"##
+        )?;
+        for speed in &self.speeds {
+            write!(buffer, "<gx:value>{}</gx:value>", speed)?;
+        }
+        write!(
+            buffer,
+            r#"</gx:SimpleArrayData><gx:SimpleArrayData name="direction">"#
+        )?;
+        for direction in &self.directions {
+            write!(buffer, "<gx:value>{}</gx:value>", escape_xml(direction))?;
+        }
+        writeln!(
+            buffer,
+            "</gx:SimpleArrayData></SchemaData></ExtendedData></gx:Track></Placemark>"
+        )?;
+        Ok(())
+    }
+}
+
+/// Encodes the exported rows batch by batch, so that the encoded bytes can be uploaded while the
+/// query runs. The batches must have the columns of the `events` table, with plain Utf8 strings.
+/// The GPX and KML formats expect the rows to be sorted by vehicle and timestamp, and render one
+/// track per vehicle.
+pub enum ExportEncoder {
+    Parquet(ArrowWriter<Vec<u8>>),
+    GeoParquet(crate::utils::geoparquet::GeoParquetWriter),
+    Csv {
+        buffer: Vec<u8>,
+        row_count: usize,
+    },
+    GeoJson {
+        buffer: Vec<u8>,
+        row_count: usize,
+    },
+    Gpx {
+        buffer: Vec<u8>,
+        vehicle_id: Option<String>,
+    },
+    Kml {
+        buffer: Vec<u8>,
+        track: Option<KmlTrack>,
+    },
+}
+
+impl ExportEncoder {
+    pub fn try_new(format: ExportFormat, schema: &Schema) -> anyhow::Result<Self> {
+        Ok(match format {
+            ExportFormat::Parquet => Self::Parquet(ArrowWriter::try_new(
+                Vec::new(),
+                Arc::new(schema.clone()),
+                None,
+            )?),
+            ExportFormat::GeoParquet => {
+                Self::GeoParquet(crate::utils::geoparquet::GeoParquetWriter::try_new(schema)?)
+            }
+            ExportFormat::Csv => Self::Csv {
+                buffer: Vec::new(),
+                row_count: 0,
+            },
+            ExportFormat::GeoJson => Self::GeoJson {
+                buffer: br#"{"type":"FeatureCollection","features":["#.to_vec(),
+                row_count: 0,
+            },
+            ExportFormat::Gpx => Self::Gpx {
+                buffer: GPX_HEADER.as_bytes().to_vec(),
+                vehicle_id: None,
+            },
+            ExportFormat::Kml => Self::Kml {
+                buffer: KML_HEADER.as_bytes().to_vec(),
+                track: None,
+            },
+        })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<()> {
+        match self {
+            Self::Parquet(writer) => writer.write(batch)?,
+            Self::GeoParquet(writer) => writer.write(batch)?,
+            Self::Csv { buffer, row_count } => {
+                let mut writer = WriterBuilder::new()
+                    .with_header(*row_count == 0)
+                    .build(buffer);
+                writer.write(batch)?;
+                *row_count += batch.num_rows();
+            }
+            Self::GeoJson { buffer, row_count } => {
+                let columns = ExportColumns::try_new(batch)?;
+                for i in 0..batch.num_rows() {
+                    if *row_count > 0 {
+                        buffer.push(b',');
+                    }
+                    let feature = serde_json::json!({
+                        "type": "Feature",
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [columns.lon.value(i), columns.lat.value(i)],
+                        },
+                        "properties": {
+                            "timestamp": columns.datetime(i).map(|datetime| datetime.to_rfc3339()),
+                            "vehicleId": columns.vehicle_id.value(i),
+                            "vehicleType": columns.vehicle_type.value(i),
+                            "speed": columns.speed.value(i),
+                            "direction": columns.direction.value(i),
+                            "geoHash": columns.geo_hash.value(i),
+                        },
+                    });
+                    serde_json::to_writer(&mut *buffer, &feature)?;
+                    *row_count += 1;
+                }
+            }
+            Self::Gpx { buffer, vehicle_id } => {
+                let columns = ExportColumns::try_new(batch)?;
+                for i in 0..batch.num_rows() {
+                    let row_vehicle_id = columns.vehicle_id.value(i);
+                    if vehicle_id.as_deref() != Some(row_vehicle_id) {
+                        if vehicle_id.is_some() {
+                            writeln!(buffer, "</trkseg></trk>")?;
+                        }
+                        writeln!(
+                            buffer,
+                            "<trk><name>{}</name><type>{}</type><trkseg>",
+                            escape_xml(row_vehicle_id),
+                            escape_xml(columns.vehicle_type.value(i))
+                        )?;
+                        *vehicle_id = Some(row_vehicle_id.to_string());
+                    }
+                    writeln!(
+                        buffer,
+                        r#"<trkpt lat="{}" lon="{}"><ele>{}</ele><time>{}</time><extensions><vehicle:speed>{}</vehicle:speed><vehicle:direction>{}</vehicle:direction></extensions></trkpt>"#,
+                        columns.lat.value(i),
+                        columns.lon.value(i),
+                        columns.alt.value(i),
+                        columns.time(i),
+                        columns.speed.value(i),
+                        escape_xml(columns.direction.value(i))
+                    )?;
+                }
+            }
+            Self::Kml { buffer, track } => {
+                let columns = ExportColumns::try_new(batch)?;
+                for i in 0..batch.num_rows() {
+                    let row_vehicle_id = columns.vehicle_id.value(i);
+                    if track
+                        .as_ref()
+                        .is_none_or(|track| track.vehicle_id != row_vehicle_id)
+                    {
+                        if let Some(previous) = track.take() {
+                            previous.render(buffer)?;
+                        }
+                        *track = Some(KmlTrack {
+                            vehicle_id: row_vehicle_id.to_string(),
+                            vehicle_type: columns.vehicle_type.value(i).to_string(),
+                            ..Default::default()
+                        });
+                    }
+                    if let Some(track) = track.as_mut() {
+                        track.whens.push(columns.time(i));
+                        track.coords.push(format!(
+                            "{} {} {}",
+                            columns.lon.value(i),
+                            columns.lat.value(i),
+                            columns.alt.value(i)
+                        ));
+                        track.speeds.push(columns.speed.value(i));
+                        track
+                            .directions
+                            .push(columns.direction.value(i).to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes the bytes encoded so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        match self {
+            Self::Parquet(writer) => std::mem::take(writer.inner_mut()),
+            Self::GeoParquet(writer) => writer.take_bytes(),
+            Self::Csv { buffer, .. }
+            | Self::GeoJson { buffer, .. }
+            | Self::Gpx { buffer, .. }
+            | Self::Kml { buffer, .. } => std::mem::take(buffer),
+        }
+    }
+
+    /// Returns the last bytes of the file.
+    pub fn finish(self) -> anyhow::Result<Vec<u8>> {
+        Ok(match self {
+            Self::Parquet(writer) => writer.into_inner()?,
+            Self::GeoParquet(writer) => writer.finish()?,
+            Self::Csv { buffer, .. } => buffer,
+            Self::GeoJson { mut buffer, .. } => {
+                buffer.extend_from_slice(b"]}");
+                buffer
+            }
+            Self::Gpx {
+                mut buffer,
+                vehicle_id,
+            } => {
+                if vehicle_id.is_some() {
+                    writeln!(buffer, "</trkseg></trk>")?;
+                }
+                writeln!(buffer, "</gpx>")?;
+                buffer
+            }
+            Self::Kml { mut buffer, track } => {
+                if let Some(track) = track {
+                    track.render(&mut buffer)?;
+                }
+                writeln!(buffer, "</Document>\n</kml>")?;
+                buffer
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::datatypes::{DataType, Field, TimeUnit};
+
+    fn batch(rows: &[(&str, &str, f64, f64)]) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("vehicleId", DataType::Utf8, false),
+            Field::new("vehicleType", DataType::Utf8, false),
+            Field::new("gps_lat", DataType::Float64, false),
+            Field::new("gps_lon", DataType::Float64, false),
+            Field::new("gps_alt", DataType::Float64, false),
+            Field::new("geoHash", DataType::Utf8, false),
+            Field::new("speed", DataType::Float64, false),
+            Field::new("direction", DataType::Utf8, false),
+        ]);
+        let timestamps = rows
+            .iter()
+            .map(|(_, timestamp, _, _)| {
+                timestamp
+                    .parse::<DateTime<Utc>>()
+                    .unwrap()
+                    .timestamp_millis()
+            })
+            .collect::<Vec<_>>();
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(TimestampMillisecondArray::from(timestamps)),
+                Arc::new(StringArray::from_iter_values(
+                    rows.iter().map(|(vehicle_id, _, _, _)| *vehicle_id),
+                )),
+                Arc::new(StringArray::from(vec!["car & co"; rows.len()])),
+                Arc::new(Float64Array::from_iter_values(
+                    rows.iter().map(|(_, _, _, lat)| *lat),
+                )),
+                Arc::new(Float64Array::from_iter_values(
+                    rows.iter().map(|(_, _, lon, _)| *lon),
+                )),
+                Arc::new(Float64Array::from(vec![10.0; rows.len()])),
+                Arc::new(StringArray::from(vec!["u150upk"; rows.len()])),
+                Arc::new(Float64Array::from(vec![42.5; rows.len()])),
+                Arc::new(StringArray::from(vec!["north"; rows.len()])),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// Encodes the batches one by one, like the export parts do.
+    fn encode(format: ExportFormat, batches: &[RecordBatch]) -> String {
+        let mut encoder = ExportEncoder::try_new(format, &batches[0].schema()).unwrap();
+        let mut bytes = vec![];
+        for batch in batches {
+            encoder.write(batch).unwrap();
+            bytes.extend(encoder.take_bytes());
+        }
+        bytes.extend(encoder.finish().unwrap());
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn renders_one_gpx_track_per_vehicle() {
+        let gpx = encode(
+            ExportFormat::Gpx,
+            &[
+                batch(&[
+                    ("a", "2024-01-01T10:00:00Z", 4.35, 50.85),
+                    ("a", "2024-01-01T10:00:01.500Z", 4.36, 50.86),
+                ]),
+                batch(&[("b", "2024-01-01T10:00:00Z", 4.37, 50.87)]),
+            ],
+        );
+
+        assert!(gpx.starts_with(GPX_HEADER));
+        assert_eq!(gpx.matches("<trk>").count(), 2);
+        assert_eq!(gpx.matches("</trkseg></trk>").count(), 2);
+        assert!(gpx.contains("<trk><name>a</name><type>car &amp; co</type><trkseg>"));
+        assert!(gpx.contains(
+            r#"<trkpt lat="50.86" lon="4.36"><ele>10</ele><time>2024-01-01T10:00:01.500Z</time><extensions><vehicle:speed>42.5</vehicle:speed><vehicle:direction>north</vehicle:direction></extensions></trkpt>"#
+        ));
+        assert!(gpx.ends_with("</trkseg></trk>\n</gpx>\n"));
+    }
+
+    #[test]
+    fn renders_the_kml_track_of_a_vehicle_split_across_batches() {
+        let kml = encode(
+            ExportFormat::Kml,
+            &[
+                batch(&[("a", "2024-01-01T10:00:00Z", 4.35, 50.85)]),
+                batch(&[
+                    ("a", "2024-01-01T10:00:01Z", 4.36, 50.86),
+                    ("b", "2024-01-01T10:00:00Z", 4.37, 50.87),
+                ]),
+            ],
+        );
+
+        assert!(kml.starts_with(KML_HEADER));
+        assert_eq!(kml.matches("<Placemark>").count(), 2);
+        assert!(kml.contains(
+            "<Placemark><name>a</name><description>car &amp; co</description><gx:Track><when>2024-01-01T10:00:00Z</when><when>2024-01-01T10:00:01Z</when><gx:coord>4.35 50.85 10</gx:coord><gx:coord>4.36 50.86 10</gx:coord>"
+        ));
+        assert!(kml.contains(
+            r#"<gx:SimpleArrayData name="speed"><gx:value>42.5</gx:value><gx:value>42.5</gx:value></gx:SimpleArrayData>"#
+        ));
+        assert!(kml.ends_with("</Placemark>\n</Document>\n</kml>\n"));
+    }
+
+    #[test]
+    fn writes_a_geojson_feature_collection_across_batches() {
+        let geojson = encode(
+            ExportFormat::GeoJson,
+            &[
+                batch(&[
+                    ("a", "2024-01-01T10:00:00Z", 4.35, 50.85),
+                    ("a", "2024-01-01T10:00:01Z", 4.36, 50.86),
+                ]),
+                batch(&[("b", "2024-01-01T10:00:00Z", 4.37, 50.87)]),
+            ],
+        );
+
+        let collection = geojson.parse::<geojson::FeatureCollection>().unwrap();
+        assert_eq!(collection.features.len(), 3);
+        let feature = &collection.features[2];
+        assert_eq!(
+            feature.geometry.as_ref().map(|geometry| &geometry.value),
+            Some(&geojson::Value::Point(vec![4.37, 50.87]))
+        );
+        assert_eq!(feature.property("vehicleId"), Some(&serde_json::json!("b")));
+        assert_eq!(
+            feature.property("timestamp"),
+            Some(&serde_json::json!("2024-01-01T10:00:00+00:00"))
+        );
+    }
+
+    #[test]
+    fn writes_an_empty_geojson_feature_collection() {
+        let encoder = ExportEncoder::try_new(
+            ExportFormat::GeoJson,
+            &batch(&[("a", "2024-01-01T10:00:00Z", 4.35, 50.85)]).schema(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(encoder.finish().unwrap()).unwrap(),
+            r#"{"type":"FeatureCollection","features":[]}"#
+        );
+    }
+}
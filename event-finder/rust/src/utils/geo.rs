@@ -1,4 +1,4 @@
-use geo::{Geometry, BoundingRect, Intersects, coord };
+use geo::{Geometry, BoundingRect, Intersects, coord, Coord, HaversineBearing, HaversineDestination, HaversineDistance, LineString, MultiPolygon, Point, Polygon };
 use geohash::{encode, decode_bbox};
 use std::collections::HashSet;
 
@@ -7,20 +7,17 @@ pub fn geohash_covering(geom: &Geometry<f64>, precision: usize) -> HashSet<Strin
     let min = bbox.min();
     let max = bbox.max();
 
-    // On prend le centre du bbox pour calculer la taille d’un geohash de niveau 5
-    let center = coord!( x: (min.x + max.x) / 2.0, y: (min.y + max.y) / 2.0 );
-    let sample_hash = encode(center, precision).unwrap();
-    let sample_bbox = decode_bbox(&sample_hash).unwrap();
-    let step_lon = sample_bbox.max().x - sample_bbox.min().x;
-    let step_lat = sample_bbox.max().y - sample_bbox.min().y;
+    let (step_lon, step_lat) = geohash_cell_size(min, max, precision);
 
     let mut hashes = HashSet::new();
 
+    // The last sample of each axis is clamped to the max of the bbox, so that a geometry smaller than
+    // a geohash cell but crossing its border still gets all its cells.
     let mut lat = min.y;
-    while lat <= max.y {
+    loop {
         let mut lon = min.x;
-        while lon <= max.x {
-            let hash = encode(coord!(x: lon, y: lat), precision).unwrap();
+        loop {
+            let hash = encode(coord!(x: lon.min(max.x), y: lat.min(max.y)), precision).unwrap();
             let gh_bbox = decode_bbox(&hash).unwrap();
             let gh_poly = geo::Rect::new(
                 coord! { x: gh_bbox.min().x, y: gh_bbox.min().y },
@@ -29,11 +26,210 @@ pub fn geohash_covering(geom: &Geometry<f64>, precision: usize) -> HashSet<Strin
             if geom.intersects(&gh_poly) {
                 hashes.insert(hash);
             }
+            if lon >= max.x {
+                break;
+            }
             lon += step_lon;
         }
+        if lat >= max.y {
+            break;
+        }
         lat += step_lat;
     }
     hashes
 }
 
+/// Returns the width and height in degrees of the geohash cells of the given precision.
+fn geohash_cell_size(min: Coord, max: Coord, precision: usize) -> (f64, f64) {
+    // On prend le centre du bbox pour calculer la taille d’un geohash de niveau 5
+    let center = coord!( x: (min.x + max.x) / 2.0, y: (min.y + max.y) / 2.0 );
+    let sample_hash = encode(center, precision).unwrap();
+    let sample_bbox = decode_bbox(&sample_hash).unwrap();
+    (
+        sample_bbox.max().x - sample_bbox.min().x,
+        sample_bbox.max().y - sample_bbox.min().y,
+    )
+}
+
+/// Returns the number of cells sampled by `geohash_covering`, without computing them, an upper bound of the size of
+/// the covering.
+pub fn geohash_covering_samples(geom: &Geometry<f64>, precision: usize) -> usize {
+    let bbox = geom.bounding_rect().unwrap();
+    let (step_lon, step_lat) = geohash_cell_size(bbox.min(), bbox.max(), precision);
+    let lon_samples = (bbox.width() / step_lon).ceil() as usize + 1;
+    let lat_samples = (bbox.height() / step_lat).ceil() as usize + 1;
+    lon_samples.saturating_mul(lat_samples)
+}
+
+/// Returns the geohashes surrounding the given ring of cells, excluding the ones already visited.
+pub fn geohash_ring(ring: &HashSet<String>, visited: &HashSet<String>) -> Result<HashSet<String>, geohash::GeohashError> {
+    let mut hashes = HashSet::new();
+    for hash in ring.iter() {
+        let n = geohash::neighbors(hash)?;
+        for neighbor in [n.n, n.ne, n.e, n.se, n.s, n.sw, n.w, n.nw] {
+            if !visited.contains(&neighbor) && !ring.contains(&neighbor) {
+                hashes.insert(neighbor);
+            }
+        }
+    }
+    Ok(hashes)
+}
+
+/// Returns the longest geohash precision, up to `max_precision`, whose cells are at least `distance_in_meters`
+/// wide and high up to the given latitude, so that two positions within that distance are either in the same cell
+/// or in neighboring cells.
+pub fn geohash_precision_for_distance(distance_in_meters: f64, max_abs_lat: f64, max_precision: usize) -> usize {
+    let mut precision = 1;
+    while precision < max_precision {
+        let bit_count = 5 * (precision as i32 + 1);
+        let cell_width_in_degrees = 360.0 / 2f64.powi((bit_count + 1) / 2);
+        let cell_height_in_degrees = 180.0 / 2f64.powi(bit_count / 2);
+        // the cells are narrower on their side closer to the pole
+        let lat = (max_abs_lat.abs() + cell_height_in_degrees).min(90.0);
+        let width = Point::new(0.0, lat).haversine_distance(&Point::new(cell_width_in_degrees, lat));
+        let height = Point::new(0.0, 0.0).haversine_distance(&Point::new(0.0, cell_height_in_degrees));
+        if width.min(height) < distance_in_meters {
+            break;
+        }
+        precision += 1;
+    }
+    precision
+}
+
+const BUFFER_SEGMENT_COUNT: usize = 32;
+
+/// Approximates the geodesic circle with a polygon that contains it.
+pub fn circle_polygon(center: Point<f64>, radius_in_meters: f64) -> Polygon<f64> {
+    // the vertices are pushed outwards so that the edges do not cut the circle
+    let radius = radius_in_meters / (std::f64::consts::PI / BUFFER_SEGMENT_COUNT as f64).cos();
+    let exterior: Vec<Coord<f64>> = (0..BUFFER_SEGMENT_COUNT)
+        .map(|i| {
+            let bearing = 360.0 * i as f64 / BUFFER_SEGMENT_COUNT as f64;
+            center.haversine_destination(bearing, radius).0
+        })
+        .collect();
+    Polygon::new(LineString::new(exterior), vec![])
+}
+
+/// Approximates the geodesic buffer around a line with the circles of its vertices and the rectangles of its segments.
+pub fn corridor_polygons(line: &LineString<f64>, buffer_in_meters: f64) -> MultiPolygon<f64> {
+    let mut polygons: Vec<Polygon<f64>> = line
+        .points()
+        .map(|vertex| circle_polygon(vertex, buffer_in_meters))
+        .collect();
+    for segment in line.lines() {
+        let start = Point::from(segment.start);
+        let end = Point::from(segment.end);
+        let bearing = start.haversine_bearing(end);
+        let exterior = vec![
+            start.haversine_destination(bearing - 90.0, buffer_in_meters).0,
+            end.haversine_destination(bearing - 90.0, buffer_in_meters).0,
+            end.haversine_destination(bearing + 90.0, buffer_in_meters).0,
+            start.haversine_destination(bearing + 90.0, buffer_in_meters).0,
+        ];
+        polygons.push(Polygon::new(LineString::new(exterior), vec![]));
+    }
+    MultiPolygon::new(polygons)
+}
 
+// Same radius as the generator, so that the grid matches its zones.
+const EARTH_RADIUS_IN_METERS: f64 = 6_378_137.0;
+
+/// A regular grid of cells laid out like the zones of the generator:
+/// columns grow eastwards and rows grow northwards from the origin of the map.
+#[derive(Debug, Clone, Copy)]
+pub struct MapGrid {
+    pub origin: Coord<f64>,
+    pub cell_width_in_degrees: f64,
+    pub cell_height_in_degrees: f64,
+}
+
+impl MapGrid {
+    pub fn new(origin: Coord<f64>, cell_width_in_meters: f64, cell_height_in_meters: f64) -> Self {
+        Self {
+            origin,
+            cell_width_in_degrees: (cell_width_in_meters / (EARTH_RADIUS_IN_METERS * origin.y.to_radians().cos())).to_degrees(),
+            cell_height_in_degrees: (cell_height_in_meters / EARTH_RADIUS_IN_METERS).to_degrees(),
+        }
+    }
+
+    pub fn cell_at(&self, lon: f64, lat: f64) -> (i64, i64) {
+        (
+            ((lon - self.origin.x) / self.cell_width_in_degrees).floor() as i64,
+            ((lat - self.origin.y) / self.cell_height_in_degrees).floor() as i64,
+        )
+    }
+
+    pub fn cell_polygon(&self, column: i64, row: i64) -> Polygon<f64> {
+        let min_x = self.origin.x + column as f64 * self.cell_width_in_degrees;
+        let min_y = self.origin.y + row as f64 * self.cell_height_in_degrees;
+        geo::Rect::new(
+            coord! { x: min_x, y: min_y },
+            coord! { x: min_x + self.cell_width_in_degrees, y: min_y + self.cell_height_in_degrees },
+        ).to_polygon()
+    }
+}
+
+/// The zones of the generator, numbered like the generator does: over the rows of each column, starting at 1.
+#[derive(Debug, Clone, Copy)]
+pub struct MapZones {
+    pub grid: MapGrid,
+    pub column_count: i64,
+    pub row_count: i64,
+    map_bounds: geo::Rect<f64>,
+}
+
+impl MapZones {
+    pub fn new(origin: Coord<f64>, map_width_in_meters: f64, map_height_in_meters: f64, zone_width_in_meters: f64, zone_height_in_meters: f64) -> Self {
+        let grid = MapGrid::new(origin, zone_width_in_meters, zone_height_in_meters);
+        let map_grid = MapGrid::new(origin, map_width_in_meters, map_height_in_meters);
+        Self {
+            grid,
+            column_count: (map_width_in_meters / zone_width_in_meters).ceil() as i64,
+            row_count: (map_height_in_meters / zone_height_in_meters).ceil() as i64,
+            map_bounds: map_grid.cell_polygon(0, 0).bounding_rect().unwrap(),
+        }
+    }
+
+    pub fn zone_count(&self) -> i64 {
+        self.column_count * self.row_count
+    }
+
+    pub fn zone_at(&self, lon: f64, lat: f64) -> Option<i64> {
+        let (column, row) = self.grid.cell_at(lon, lat);
+        if column < 0 || column >= self.column_count || row < 0 || row >= self.row_count {
+            return None;
+        }
+        Some(column * self.row_count + row + 1)
+    }
+
+    /// The bounds of the zone, clipped to the map like the generator does.
+    pub fn zone_polygon(&self, zone: i64) -> Polygon<f64> {
+        let column = (zone - 1) / self.row_count;
+        let row = (zone - 1) % self.row_count;
+        let cell = self.grid.cell_polygon(column, row).bounding_rect().unwrap();
+        geo::Rect::new(
+            coord! { x: cell.min().x, y: cell.min().y },
+            coord! { x: cell.max().x.min(self.map_bounds.max().x), y: cell.max().y.min(self.map_bounds.max().y) },
+        ).to_polygon()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_both_cells_of_a_small_area_crossing_a_cell_border() {
+        let cell = decode_bbox("u09tv").unwrap();
+        let east = geohash::neighbors("u09tv").unwrap().e;
+        // 50 meters west of the east border of the cell
+        let border = Point::new(cell.max().x, (cell.min().y + cell.max().y) / 2.0);
+        let center = border.haversine_destination(270.0, 50.0);
+        let circle = Geometry::Polygon(circle_polygon(center, 200.0));
+
+        let hashes = geohash_covering(&circle, 5);
+
+        assert_eq!(hashes, HashSet::from(["u09tv".to_string(), east]));
+    }
+}
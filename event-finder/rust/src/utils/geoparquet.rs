@@ -0,0 +1,629 @@
+use datafusion::arrow::array::{
+    ArrayRef, BinaryArray, BinaryBuilder, Float64Array, RecordBatch, StructArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
+use datafusion::common::DFSchema;
+use datafusion::common::cast::{as_binary_array, as_float64_array};
+use datafusion::functions::core::expr_fn::get_field;
+use datafusion::logical_expr::{
+    ColumnarValue, ScalarFunctionImplementation, ScalarUDF, Volatility, create_udf,
+};
+use datafusion::parquet::arrow::ArrowWriter;
+use datafusion::parquet::arrow::ParquetRecordBatchStreamBuilder;
+use datafusion::parquet::arrow::async_reader::ParquetObjectReader;
+use datafusion::parquet::format::KeyValue;
+use datafusion::prelude::*;
+use futures_util::StreamExt;
+use object_store::ObjectStore;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const GEOMETRY_COLUMN: &str = "geometry";
+pub const BBOX_COLUMN: &str = "bbox";
+const BBOX_FIELDS: [&str; 4] = ["xmin", "ymin", "xmax", "ymax"];
+
+/// The `geo` metadata of a GeoParquet file, see https://geoparquet.org/releases/v1.1.0/
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoMetadata {
+    pub primary_column: String,
+    pub columns: HashMap<String, GeoColumnMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoColumnMetadata {
+    pub encoding: String,
+    #[serde(default)]
+    pub geometry_types: Vec<String>,
+    #[serde(default)]
+    pub covering: Option<GeoCovering>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeoCovering {
+    pub bbox: BboxCovering,
+}
+
+/// The paths of the bbox fields, like `["bbox", "xmin"]`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BboxCovering {
+    pub xmin: Vec<String>,
+    pub ymin: Vec<String>,
+    pub xmax: Vec<String>,
+    pub ymax: Vec<String>,
+}
+
+impl BboxCovering {
+    /// The struct column holding the bbox, when its fields have the standard names.
+    fn column(&self) -> Option<&str> {
+        let paths = [&self.xmin, &self.ymin, &self.xmax, &self.ymax];
+        let column = paths[0].first()?;
+        paths
+            .iter()
+            .zip(BBOX_FIELDS)
+            .all(|(path, field)| path.len() == 2 && &path[0] == column && path[1] == field)
+            .then_some(column.as_str())
+    }
+}
+
+/// Encodes a point as WKB, in little endian.
+pub fn point_to_wkb(lon: f64, lat: f64) -> [u8; 21] {
+    let mut wkb = [0u8; 21];
+    wkb[0] = 1;
+    wkb[1..5].copy_from_slice(&1u32.to_le_bytes());
+    wkb[5..13].copy_from_slice(&lon.to_le_bytes());
+    wkb[13..21].copy_from_slice(&lat.to_le_bytes());
+    wkb
+}
+
+/// Decodes the coordinates of a WKB point (ISO or EWKB, with optional Z and M ordinates),
+/// or returns None for the other geometries.
+pub fn wkb_to_point(wkb: &[u8]) -> Option<Vec<f64>> {
+    let little_endian = match wkb.first()? {
+        0 => false,
+        1 => true,
+        _ => return None,
+    };
+    let read_u32 = |bytes: &[u8]| -> Option<u32> {
+        let bytes: [u8; 4] = bytes.try_into().ok()?;
+        Some(match little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    };
+    let read_f64 = |bytes: &[u8]| -> Option<f64> {
+        let bytes: [u8; 8] = bytes.try_into().ok()?;
+        Some(match little_endian {
+            true => f64::from_le_bytes(bytes),
+            false => f64::from_be_bytes(bytes),
+        })
+    };
+    let geometry_type = read_u32(wkb.get(1..5)?)?;
+    let mut offset = 5;
+    // EWKB flags: Z, M and SRID
+    let mut dimensions = 2
+        + usize::from(geometry_type & 0x8000_0000 != 0)
+        + usize::from(geometry_type & 0x4000_0000 != 0);
+    if geometry_type & 0x2000_0000 != 0 {
+        offset += 4;
+    }
+    match geometry_type & 0x0FFF_FFFF {
+        1 => {}
+        1001 | 2001 => dimensions = 3,
+        3001 => dimensions = 4,
+        _ => return None,
+    }
+    (0..dimensions)
+        .map(|i| read_f64(wkb.get(offset + i * 8..offset + (i + 1) * 8)?))
+        .collect()
+}
+
+/// The `geo` metadata of a GeoParquet file with a single point column, in WGS84,
+/// with a bbox covering column.
+pub fn geo_metadata(bbox: Option<[f64; 4]>) -> serde_json::Value {
+    let mut column = serde_json::json!({
+        "encoding": "WKB",
+        "geometry_types": ["Point"],
+        "covering": {
+            "bbox": {
+                "xmin": [BBOX_COLUMN, "xmin"],
+                "ymin": [BBOX_COLUMN, "ymin"],
+                "xmax": [BBOX_COLUMN, "xmax"],
+                "ymax": [BBOX_COLUMN, "ymax"],
+            },
+        },
+    });
+    if let Some(bbox) = bbox {
+        column["bbox"] = serde_json::json!(bbox);
+    }
+    serde_json::json!({
+        "version": "1.1.0",
+        "primary_column": GEOMETRY_COLUMN,
+        "columns": { GEOMETRY_COLUMN: column },
+    })
+}
+
+fn bbox_fields() -> Fields {
+    BBOX_FIELDS
+        .iter()
+        .map(|name| Field::new(*name, DataType::Float64, false))
+        .collect()
+}
+
+/// The bbox covering of points, with the fields of the given struct type.
+pub fn point_bbox_array(
+    data_type: &DataType,
+    lons: &Float64Array,
+    lats: &Float64Array,
+) -> anyhow::Result<ArrayRef> {
+    let DataType::Struct(fields) = data_type else {
+        anyhow::bail!("The bbox column must be a struct, not {}", data_type);
+    };
+    let mut columns: Vec<ArrayRef> = vec![];
+    for field in fields.iter() {
+        let values = match field.name().as_str() {
+            "xmin" | "xmax" => lons,
+            "ymin" | "ymax" => lats,
+            name => anyhow::bail!("Unknown bbox field '{}'", name),
+        };
+        columns.push(datafusion::arrow::compute::cast(values, field.data_type())?);
+    }
+    Ok(Arc::new(StructArray::try_new(
+        fields.clone(),
+        columns,
+        None,
+    )?))
+}
+
+/// Reads the `geo` metadata of the first parquet file of the store outside the excluded folder,
+/// or returns None when the files are not GeoParquet files.
+pub async fn read_geo_metadata(
+    store: &Arc<dyn ObjectStore>,
+    excluded: &object_store::path::Path,
+) -> anyhow::Result<Option<GeoMetadata>> {
+    let mut files = store.list(None);
+    let file = loop {
+        match files.next().await.transpose()? {
+            Some(file)
+                if file.location.as_ref().ends_with(".parquet")
+                    && !file.location.prefix_matches(excluded) =>
+            {
+                break file;
+            }
+            Some(_) => continue,
+            None => return Ok(None),
+        }
+    };
+    let reader =
+        ParquetObjectReader::new(store.clone(), file.location.clone()).with_file_size(file.size);
+    let builder = ParquetRecordBatchStreamBuilder::new(reader).await?;
+    let Some(geo) = builder
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|metadata| metadata.iter().find(|kv| kv.key == "geo"))
+        .and_then(|kv| kv.value.as_ref())
+    else {
+        return Ok(None);
+    };
+    let metadata: GeoMetadata = serde_json::from_str(geo)?;
+    let Some(column) = metadata.columns.get(&metadata.primary_column) else {
+        anyhow::bail!(
+            "The primary column '{}' of {} has no GeoParquet metadata",
+            metadata.primary_column,
+            file.location
+        );
+    };
+    if !column.encoding.eq_ignore_ascii_case("WKB")
+        || column
+            .geometry_types
+            .iter()
+            .any(|geometry_type| !geometry_type.starts_with("Point"))
+    {
+        anyhow::bail!(
+            "Only the WKB points are supported in GeoParquet files, not the {} {:?} of {}",
+            column.encoding,
+            column.geometry_types,
+            file.location
+        );
+    }
+    log::info!(
+        "Reading the GeoParquet column '{}' of the events, using {:?}",
+        metadata.primary_column,
+        file.location
+    );
+    Ok(Some(metadata))
+}
+
+fn point_ordinate_udf(name: &str, index: usize) -> ScalarUDF {
+    let fun: ScalarFunctionImplementation = Arc::new(move |args: &[ColumnarValue]| {
+        let args = ColumnarValue::values_to_arrays(args)?;
+        let geometries = as_binary_array(&args[0])?;
+        let result: Float64Array = geometries
+            .iter()
+            .map(|wkb| {
+                wkb.and_then(wkb_to_point)
+                    .and_then(|point| point.get(index).copied())
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    });
+    create_udf(
+        name,
+        vec![DataType::Binary],
+        DataType::Float64,
+        Volatility::Immutable,
+        fun,
+    )
+}
+
+/// Exposes the columns of a GeoParquet table like the ones of the `events` files:
+/// the geometry is named `geometry`, its bbox covering `bbox`, and the `gps_lon`, `gps_lat`
+/// and `gps_alt` columns are decoded from the geometry when the files do not have them.
+pub fn with_point_columns(df: DataFrame, metadata: &GeoMetadata) -> anyhow::Result<DataFrame> {
+    let covering = metadata
+        .columns
+        .get(&metadata.primary_column)
+        .and_then(|column| column.covering.as_ref());
+    let covering_column = covering.and_then(|covering| covering.bbox.column());
+    if covering.is_some() && covering_column.is_none() {
+        log::warn!(
+            "Ignoring the bbox covering of the GeoParquet files, which has unexpected fields"
+        );
+    }
+    let geometry = cast(ident(&metadata.primary_column), DataType::Binary);
+    let mut columns: Vec<Expr> = vec![];
+    for field in df.schema().fields() {
+        let name = field.name();
+        if *name == metadata.primary_column {
+            columns.push(geometry.clone().alias(GEOMETRY_COLUMN));
+        } else if Some(name.as_str()) == covering_column {
+            columns.push(ident(name).alias(BBOX_COLUMN));
+        } else if name != GEOMETRY_COLUMN && name != BBOX_COLUMN {
+            columns.push(ident(name));
+        }
+    }
+    for (index, name) in ["gps_lon", "gps_lat", "gps_alt"].iter().enumerate() {
+        if df.schema().field_with_unqualified_name(name).is_err() {
+            let udf = point_ordinate_udf(&format!("st_ordinate_{}", index), index);
+            columns.push(udf.call(vec![geometry.clone()]).alias(*name));
+        }
+    }
+    Ok(df.select(columns)?)
+}
+
+/// Selects the rows whose bbox covering intersects the bounds, before decoding their geometry,
+/// or returns None when there is no `bbox` column.
+/// Note that DataFusion does not prune the row groups with the statistics of struct columns yet.
+pub fn covering_predicate(schema: &DFSchema, bounds: geo::Rect) -> Option<Expr> {
+    match schema
+        .field_with_unqualified_name(BBOX_COLUMN)
+        .ok()?
+        .data_type()
+    {
+        DataType::Struct(_) => {}
+        _ => return None,
+    }
+    let field = |name: &str| get_field(ident(BBOX_COLUMN), name);
+    Some(
+        field("xmin")
+            .lt_eq(lit(bounds.max().x))
+            .and(field("xmax").gt_eq(lit(bounds.min().x)))
+            .and(field("ymin").lt_eq(lit(bounds.max().y)))
+            .and(field("ymax").gt_eq(lit(bounds.min().y))),
+    )
+}
+
+/// Writes record batches with `gps_lon` and `gps_lat` columns as GeoParquet,
+/// adding the WKB point of each row in the `geometry` column, and its bbox in the `bbox` column.
+pub struct GeoParquetWriter {
+    writer: ArrowWriter<Vec<u8>>,
+    schema: SchemaRef,
+    lon: usize,
+    lat: usize,
+    // min lon, min lat, max lon, max lat
+    bbox: Option<[f64; 4]>,
+}
+
+impl GeoParquetWriter {
+    pub fn try_new(schema: &Schema) -> anyhow::Result<Self> {
+        let mut fields: Vec<Field> = schema
+            .fields()
+            .iter()
+            .filter(|field| field.name() != GEOMETRY_COLUMN && field.name() != BBOX_COLUMN)
+            .map(|field| field.as_ref().clone())
+            .collect();
+        fields.push(Field::new(GEOMETRY_COLUMN, DataType::Binary, false));
+        fields.push(Field::new(
+            BBOX_COLUMN,
+            DataType::Struct(bbox_fields()),
+            false,
+        ));
+        let geo_schema = Arc::new(Schema::new(fields));
+        Ok(Self {
+            writer: ArrowWriter::try_new(Vec::new(), geo_schema.clone(), None)?,
+            schema: geo_schema,
+            lon: schema.index_of("gps_lon")?,
+            lat: schema.index_of("gps_lat")?,
+            bbox: None,
+        })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> anyhow::Result<()> {
+        let lons = as_float64_array(batch.column(self.lon))?;
+        let lats = as_float64_array(batch.column(self.lat))?;
+        let mut geometries = BinaryBuilder::with_capacity(batch.num_rows(), batch.num_rows() * 21);
+        for (lon, lat) in lons.values().iter().zip(lats.values().iter()) {
+            geometries.append_value(point_to_wkb(*lon, *lat));
+            if lon.is_nan() || lat.is_nan() {
+                continue;
+            }
+            self.bbox = Some(match self.bbox {
+                Some([min_lon, min_lat, max_lon, max_lat]) => [
+                    min_lon.min(*lon),
+                    min_lat.min(*lat),
+                    max_lon.max(*lon),
+                    max_lat.max(*lat),
+                ],
+                None => [*lon, *lat, *lon, *lat],
+            });
+        }
+        let geometries: BinaryArray = geometries.finish();
+        let mut columns: Vec<ArrayRef> = batch
+            .schema_ref()
+            .fields()
+            .iter()
+            .zip(batch.columns())
+            .filter(|(field, _)| field.name() != GEOMETRY_COLUMN && field.name() != BBOX_COLUMN)
+            .map(|(_, column)| column.clone())
+            .collect();
+        columns.push(Arc::new(geometries));
+        columns.push(point_bbox_array(
+            &DataType::Struct(bbox_fields()),
+            lons,
+            lats,
+        )?);
+        self.writer
+            .write(&RecordBatch::try_new(self.schema.clone(), columns)?)?;
+        Ok(())
+    }
+
+    /// Takes the bytes encoded so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        std::mem::take(self.writer.inner_mut())
+    }
+
+    /// Writes the footer, with the `geo` metadata covering all the written rows.
+    pub fn finish(mut self) -> anyhow::Result<Vec<u8>> {
+        self.writer.append_key_value_metadata(KeyValue::new(
+            "geo".to_string(),
+            geo_metadata(self.bbox).to_string(),
+        ));
+        Ok(self.writer.into_inner()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+
+    fn geoparquet_file() -> Vec<u8> {
+        let schema = Schema::new(vec![
+            Field::new("gps_lon", DataType::Float64, false),
+            Field::new("gps_lat", DataType::Float64, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Float64Array::from(vec![4.35])),
+                Arc::new(Float64Array::from(vec![50.85])),
+            ],
+        )
+        .unwrap();
+        let mut writer = GeoParquetWriter::try_new(&schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_the_geo_metadata_outside_of_the_exports() -> anyhow::Result<()> {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let exports = Path::from("exports");
+        store
+            .put(
+                &Path::from("exports/export-1/part-00000.parquet"),
+                geoparquet_file().into(),
+            )
+            .await?;
+
+        assert!(read_geo_metadata(&store, &exports).await?.is_none());
+
+        store
+            .put(
+                &Path::from("y=2024/m=01/d=01/events.parquet"),
+                geoparquet_file().into(),
+            )
+            .await?;
+        let metadata = read_geo_metadata(&store, &exports).await?;
+
+        assert_eq!(
+            metadata.map(|metadata| metadata.primary_column),
+            Some(GEOMETRY_COLUMN.to_string())
+        );
+        Ok(())
+    }
+
+    /// Encodes a WKB point with the given byte order, geometry type, SRID and ordinates.
+    fn wkb(
+        little_endian: bool,
+        geometry_type: u32,
+        srid: Option<u32>,
+        ordinates: &[f64],
+    ) -> Vec<u8> {
+        let u32_bytes = |value: u32| match little_endian {
+            true => value.to_le_bytes(),
+            false => value.to_be_bytes(),
+        };
+        let mut wkb = vec![u8::from(little_endian)];
+        wkb.extend(u32_bytes(geometry_type));
+        if let Some(srid) = srid {
+            wkb.extend(u32_bytes(srid));
+        }
+        for ordinate in ordinates {
+            wkb.extend(match little_endian {
+                true => ordinate.to_le_bytes(),
+                false => ordinate.to_be_bytes(),
+            });
+        }
+        wkb
+    }
+
+    #[test]
+    fn decodes_the_wkb_points() {
+        assert_eq!(
+            wkb_to_point(&point_to_wkb(4.35, 50.85)),
+            Some(vec![4.35, 50.85])
+        );
+        assert_eq!(
+            wkb_to_point(&wkb(false, 1, None, &[4.35, 50.85])),
+            Some(vec![4.35, 50.85])
+        );
+        // ISO Z, M and ZM points
+        assert_eq!(
+            wkb_to_point(&wkb(true, 1001, None, &[4.35, 50.85, 12.0])),
+            Some(vec![4.35, 50.85, 12.0])
+        );
+        assert_eq!(
+            wkb_to_point(&wkb(false, 2001, None, &[4.35, 50.85, 7.0])),
+            Some(vec![4.35, 50.85, 7.0])
+        );
+        assert_eq!(
+            wkb_to_point(&wkb(true, 3001, None, &[4.35, 50.85, 12.0, 7.0])),
+            Some(vec![4.35, 50.85, 12.0, 7.0])
+        );
+    }
+
+    #[test]
+    fn decodes_the_ewkb_points() {
+        assert_eq!(
+            wkb_to_point(&wkb(true, 0x2000_0001, Some(4326), &[4.35, 50.85])),
+            Some(vec![4.35, 50.85])
+        );
+        assert_eq!(
+            wkb_to_point(&wkb(false, 0xA000_0001, Some(4326), &[4.35, 50.85, 12.0])),
+            Some(vec![4.35, 50.85, 12.0])
+        );
+        assert_eq!(
+            wkb_to_point(&wkb(true, 0x4000_0001, None, &[4.35, 50.85, 7.0])),
+            Some(vec![4.35, 50.85, 7.0])
+        );
+        assert_eq!(
+            wkb_to_point(&wkb(
+                true,
+                0xE000_0001,
+                Some(4326),
+                &[4.35, 50.85, 12.0, 7.0]
+            )),
+            Some(vec![4.35, 50.85, 12.0, 7.0])
+        );
+    }
+
+    #[test]
+    fn rejects_the_other_geometries_and_the_invalid_buffers() {
+        assert_eq!(wkb_to_point(&point_to_wkb(4.35, 50.85)[..20]), None);
+        assert_eq!(wkb_to_point(&wkb(true, 1001, None, &[4.35, 50.85])), None);
+        assert_eq!(wkb_to_point(&wkb(true, 0x2000_0001, None, &[4.35])), None);
+        assert_eq!(
+            wkb_to_point(&wkb(true, 2, None, &[4.35, 50.85, 4.36, 50.86])),
+            None
+        );
+        assert_eq!(wkb_to_point(&[2, 1, 0, 0, 0]), None);
+        assert_eq!(wkb_to_point(&[]), None);
+    }
+
+    fn covering(paths: serde_json::Value) -> BboxCovering {
+        serde_json::from_value(paths).unwrap()
+    }
+
+    #[test]
+    fn finds_the_bbox_covering_column() {
+        let standard = covering(serde_json::json!({
+            "xmin": ["bounds", "xmin"],
+            "ymin": ["bounds", "ymin"],
+            "xmax": ["bounds", "xmax"],
+            "ymax": ["bounds", "ymax"],
+        }));
+        let other_fields = covering(serde_json::json!({
+            "xmin": ["bbox", "minx"],
+            "ymin": ["bbox", "miny"],
+            "xmax": ["bbox", "maxx"],
+            "ymax": ["bbox", "maxy"],
+        }));
+        let other_columns = covering(serde_json::json!({
+            "xmin": ["bbox", "xmin"],
+            "ymin": ["bbox", "ymin"],
+            "xmax": ["extent", "xmax"],
+            "ymax": ["extent", "ymax"],
+        }));
+
+        assert_eq!(standard.column(), Some("bounds"));
+        assert_eq!(other_fields.column(), None);
+        assert_eq!(other_columns.column(), None);
+    }
+
+    #[tokio::test]
+    async fn selects_the_rows_whose_bbox_intersects_the_bounds() -> anyhow::Result<()> {
+        let lons = Float64Array::from(vec![4.35, 5.5]);
+        let lats = Float64Array::from(vec![50.85, 50.85]);
+        let bbox_type = DataType::Struct(bbox_fields());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("gps_lon", DataType::Float64, false),
+            Field::new(BBOX_COLUMN, bbox_type.clone(), false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(lons.clone()),
+                point_bbox_array(&bbox_type, &lons, &lats)?,
+            ],
+        )?;
+        let ctx = SessionContext::new();
+        let df = ctx.read_batch(batch)?;
+        let bounds = geo::Rect::new(
+            geo::coord! { x: 4.0, y: 50.0 },
+            geo::coord! { x: 5.0, y: 51.0 },
+        );
+
+        let predicate = covering_predicate(df.schema(), bounds).unwrap();
+        let batches = df.filter(predicate)?.collect().await?;
+
+        assert_eq!(
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+            1
+        );
+        assert_eq!(as_float64_array(batches[0].column(0))?.value(0), 4.35);
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_the_bbox_columns_that_are_not_structs() -> anyhow::Result<()> {
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new(
+                BBOX_COLUMN,
+                DataType::Float64,
+                false,
+            )])),
+            vec![Arc::new(Float64Array::from(vec![4.35]))],
+        )?;
+        let df = SessionContext::new().read_batch(batch)?;
+        let bounds = geo::Rect::new(
+            geo::coord! { x: 4.0, y: 50.0 },
+            geo::coord! { x: 5.0, y: 51.0 },
+        );
+
+        assert!(covering_predicate(df.schema(), bounds).is_none());
+        Ok(())
+    }
+}
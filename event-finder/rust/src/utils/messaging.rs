@@ -1,14 +1,47 @@
 use crate::contexts::HasNatsClient;
 use futures_util::StreamExt;
+use log;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::future::Future;
 use tokio::task::JoinHandle;
-use log;
 
 pub fn message_loop<TContext, TMessage, F, Fut>(
     ctx: TContext,
     subject: String,
     handler: F,
 ) -> JoinHandle<anyhow::Result<()>>
+where
+    TContext: HasNatsClient + Clone + Send + Sync + 'static,
+    TMessage: crate::types::HasMessageType + Send + Sync + 'static,
+    F: Fn(TContext, TMessage) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    spawn_message_loop(ctx, subject, None, handler)
+}
+
+/// Same as `message_loop`, but each message is only processed by one of the members of the queue group.
+pub fn queue_message_loop<TContext, TMessage, F, Fut>(
+    ctx: TContext,
+    subject: String,
+    queue_group: String,
+    handler: F,
+) -> JoinHandle<anyhow::Result<()>>
+where
+    TContext: HasNatsClient + Clone + Send + Sync + 'static,
+    TMessage: crate::types::HasMessageType + Send + Sync + 'static,
+    F: Fn(TContext, TMessage) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    spawn_message_loop(ctx, subject, Some(queue_group), handler)
+}
+
+fn spawn_message_loop<TContext, TMessage, F, Fut>(
+    ctx: TContext,
+    subject: String,
+    queue_group: Option<String>,
+    handler: F,
+) -> JoinHandle<anyhow::Result<()>>
 where
     TContext: HasNatsClient + Clone + Send + Sync + 'static,
     TMessage: crate::types::HasMessageType + Send + Sync + 'static,
@@ -17,15 +50,37 @@ where
 {
     tokio::task::spawn(async move {
         let nats_client = ctx.get_nats_client().clone();
-        let mut sub = nats_client.subscribe(subject.clone()).await?;
+        let mut sub = match queue_group {
+            Some(queue_group) => {
+                nats_client
+                    .queue_subscribe(subject.clone(), queue_group)
+                    .await?
+            }
+            None => nats_client.subscribe(subject.clone()).await?,
+        };
 
         while let Some(msg) = sub.next().await {
-            let res = match serde_json::from_slice::<TMessage>(&msg.payload) {
-                Ok(req) => handler(ctx.clone(), req).await,
-                Err(e) => Err(anyhow::anyhow!(
-                    "Could not deserialize message as JSON: {}",
-                    e
-                )),
+            let accepted_encoding = crate::utils::compression::negotiate_encoding(&msg);
+            let proto_type = crate::utils::proto::proto_type(&msg);
+            // the replies are only encoded with protobuf for the senders that use it
+            let reply_with_proto_buf = ctx.is_proto_buf_enabled() && proto_type.is_some();
+            let max_payload = match nats_client.server_info().max_payload {
+                0 => crate::utils::compression::DEFAULT_MAX_PAYLOAD,
+                max_payload => max_payload,
+            };
+            let res = match crate::utils::compression::decode_payload(&msg, max_payload)
+                .and_then(|payload| decode_message::<TMessage>(proto_type, &payload))
+            {
+                Ok(req) => {
+                    crate::utils::compression::ACCEPTED_ENCODING
+                        .scope(
+                            accepted_encoding,
+                            crate::utils::proto::REPLY_WITH_PROTO_BUF
+                                .scope(reply_with_proto_buf, handler(ctx.clone(), req)),
+                        )
+                        .await
+                }
+                Err(e) => Err(e),
             };
 
             if let Err(e) = res {
@@ -35,3 +90,175 @@ where
         anyhow::Ok(())
     })
 }
+
+fn decode_message<TMessage: DeserializeOwned>(
+    proto_type: Option<&str>,
+    payload: &[u8],
+) -> anyhow::Result<TMessage> {
+    match proto_type {
+        Some(proto_type) => {
+            let msg = crate::utils::proto::decode_message(proto_type, payload)?;
+            serde_json::from_value(msg).map_err(|e| {
+                anyhow::anyhow!(
+                    "Could not deserialize '{}' protobuf message: {}",
+                    proto_type,
+                    e
+                )
+            })
+        }
+        None => serde_json::from_slice(payload)
+            .map_err(|e| anyhow::anyhow!("Could not deserialize message as JSON: {}", e)),
+    }
+}
+
+/// Serializes the message with protobuf when asked to and when it has a protobuf form, or with JSON.
+pub fn encode_message<TMessage: Serialize>(
+    msg: &TMessage,
+    use_proto_buf: bool,
+) -> anyhow::Result<(Option<async_nats::HeaderMap>, Vec<u8>)> {
+    if use_proto_buf && let Some((proto_type, payload)) = crate::utils::proto::encode_message(msg)?
+    {
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert(crate::utils::proto::PROTO_TYPE_HEADER, proto_type.as_str());
+        return Ok((Some(headers), payload));
+    }
+    Ok((None, serde_json::to_vec(msg)?))
+}
+
+/// Publishes a reply to the request being processed, encoded like the request.
+pub async fn publish_message<TMessage: Serialize>(
+    nats_client: &async_nats::Client,
+    subject: String,
+    msg: &TMessage,
+) -> anyhow::Result<()> {
+    let (headers, payload) = encode_message(msg, crate::utils::proto::reply_with_proto_buf())?;
+    publish_payload(nats_client, subject, headers, payload).await
+}
+
+/// Publishes an event, encoded like the request being processed but never compressed, as it is not
+/// only meant for the sender of the request.
+pub async fn publish_event<TMessage: Serialize>(
+    nats_client: &async_nats::Client,
+    subject: String,
+    msg: &TMessage,
+) -> anyhow::Result<()> {
+    match encode_message(msg, crate::utils::proto::reply_with_proto_buf())? {
+        (Some(headers), payload) => {
+            nats_client
+                .publish_with_headers(subject, headers, payload.into())
+                .await?
+        }
+        (None, payload) => nats_client.publish(subject, payload.into()).await?,
+    }
+    anyhow::Ok(())
+}
+
+/// Publishes the payload, compressed with the encoding accepted by the sender of the request being processed.
+pub async fn publish_payload(
+    nats_client: &async_nats::Client,
+    subject: String,
+    headers: Option<async_nats::HeaderMap>,
+    payload: Vec<u8>,
+) -> anyhow::Result<()> {
+    let (headers, payload) = match crate::utils::compression::accepted_encoding() {
+        Some(encoding) => {
+            let mut headers = headers.unwrap_or_default();
+            headers.insert(
+                crate::utils::compression::CONTENT_ENCODING_HEADER,
+                encoding.as_str(),
+            );
+            (Some(headers), encoding.compress(&payload)?)
+        }
+        None => (headers, payload),
+    };
+    match headers {
+        Some(headers) => {
+            nats_client
+                .publish_with_headers(subject, headers, payload.into())
+                .await?
+        }
+        None => nats_client.publish(subject, payload.into()).await?,
+    }
+    anyhow::Ok(())
+}
+
+pub fn create_response<TBody>(
+    request_id: &str,
+    result: anyhow::Result<TBody>,
+) -> crate::types::Response<TBody> {
+    match result {
+        Ok(body) => crate::types::Response::Success {
+            id: uuid::Uuid::new_v4().to_string(),
+            request_id: request_id.to_string(),
+            body,
+        },
+        Err(e) => crate::types::Response::Error {
+            id: uuid::Uuid::new_v4().to_string(),
+            request_id: request_id.to_string(),
+            code: crate::types::ResponseErrorCode::Exception,
+            body: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PingRequest, Request};
+
+    fn ping() -> Request<PingRequest> {
+        Request {
+            id: "request-1".to_string(),
+            msg_type: "request".to_string(),
+            reply_to: "inbox.1".to_string(),
+            parent_id: None,
+            expires_at: None,
+            timeout: Some(1000),
+            body: PingRequest {
+                msg_type: "ping".to_string(),
+                service_name: None,
+            },
+        }
+    }
+
+    fn message(headers: Option<async_nats::HeaderMap>, payload: Vec<u8>) -> async_nats::Message {
+        async_nats::Message {
+            subject: "messaging.control".into(),
+            reply: None,
+            length: payload.len(),
+            payload: payload.into(),
+            headers,
+            status: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn decodes_the_payloads_according_to_their_proto_type_header() -> anyhow::Result<()> {
+        for use_proto_buf in [true, false] {
+            let (headers, payload) = encode_message(&ping(), use_proto_buf)?;
+            let msg = message(headers, payload);
+            let proto_type = crate::utils::proto::proto_type(&msg);
+            assert_eq!(proto_type, use_proto_buf.then_some("request"));
+
+            let decoded: Request<PingRequest> = decode_message(proto_type, &msg.payload)?;
+
+            assert_eq!(
+                serde_json::to_value(decoded)?,
+                serde_json::to_value(ping())?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_the_payloads_not_matching_their_proto_type_header() -> anyhow::Result<()> {
+        let json = serde_json::to_vec(&ping())?;
+
+        assert!(decode_message::<Request<PingRequest>>(Some("request"), &json).is_err());
+        assert!(decode_message::<Request<PingRequest>>(Some("unknown"), &json).is_err());
+        assert!(decode_message::<Request<PingRequest>>(None, b"\x0a\x01a").is_err());
+        Ok(())
+    }
+}
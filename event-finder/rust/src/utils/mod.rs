@@ -1,3 +1,11 @@
+pub mod arrow_stream;
+pub mod compression;
+pub mod export;
 pub mod geo;
+pub mod geoparquet;
 pub mod messaging;
+pub mod moves;
+pub mod pagination;
+pub mod proto;
+pub mod stops;
 pub mod time;
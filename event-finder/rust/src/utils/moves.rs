@@ -0,0 +1,101 @@
+use prost::Message;
+
+/// A position received from the live move stream.
+#[derive(Debug, Clone)]
+pub struct VehiclePosition {
+    pub vehicle_id: String,
+    pub vehicle_type: String,
+    pub timestamp: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub alt: f64,
+    pub direction: String,
+    pub speed: f64,
+    // only known for the enriched move commands
+    pub geo_hash: Option<String>,
+    pub partition_key: Option<String>,
+}
+
+impl From<crate::types_proto::MoveCommand> for VehiclePosition {
+    fn from(cmd: crate::types_proto::MoveCommand) -> Self {
+        let gps = cmd.gps.unwrap_or_default();
+        Self {
+            vehicle_id: cmd.vehicle_id,
+            vehicle_type: cmd.vehicle_type,
+            timestamp: cmd.timestamp,
+            lat: gps.lat,
+            lon: gps.lon,
+            alt: gps.alt,
+            direction: cmd.direction,
+            speed: cmd.speed,
+            geo_hash: None,
+            partition_key: None,
+        }
+    }
+}
+
+impl From<crate::types::MoveCommand> for VehiclePosition {
+    fn from(cmd: crate::types::MoveCommand) -> Self {
+        Self {
+            vehicle_id: cmd.vehicle_id,
+            vehicle_type: cmd.vehicle_type,
+            timestamp: cmd.timestamp,
+            lat: cmd.gps.lat,
+            lon: cmd.gps.lon,
+            alt: cmd.gps.alt,
+            direction: cmd.direction,
+            speed: cmd.speed,
+            geo_hash: None,
+            partition_key: None,
+        }
+    }
+}
+
+pub fn move_subject(source: &str) -> anyhow::Result<String> {
+    match source {
+        "move" => Ok("commands.move".to_string()),
+        "enriched-move" => Ok("services.collectors.assigned.*.commands.move".to_string()),
+        _ => anyhow::bail!("Unknown geofences source: {}", source),
+    }
+}
+
+/// Decodes the position of a move command, encoded with protobuf or JSON like the other services do.
+/// Returns None for the other commands sharing the subject, such as the flush commands.
+pub fn decode_position(msg: &async_nats::Message) -> anyhow::Result<Option<VehiclePosition>> {
+    let proto_type = msg
+        .headers
+        .as_ref()
+        .and_then(|headers| headers.get("proto/type"))
+        .map(|value| value.as_str());
+    match proto_type {
+        Some("move") => Ok(Some(
+            crate::types_proto::MoveCommand::decode(msg.payload.clone())?.into(),
+        )),
+        Some("enriched-move") => {
+            let cmd = crate::types_proto::EnrichedMoveCommand::decode(msg.payload.clone())?;
+            Ok(cmd.command.map(|command| VehiclePosition {
+                geo_hash: Some(cmd.geo_hash),
+                partition_key: Some(cmd.partition_key),
+                ..command.into()
+            }))
+        }
+        Some(_) => Ok(None),
+        None => {
+            let value: serde_json::Value = serde_json::from_slice(&msg.payload)?;
+            match value.get("type").and_then(|msg_type| msg_type.as_str()) {
+                Some("move") => Ok(Some(
+                    serde_json::from_value::<crate::types::MoveCommand>(value)?.into(),
+                )),
+                Some("enriched-move") => {
+                    let cmd = serde_json::from_value::<crate::types::EnrichedMoveCommand>(value)?;
+                    Ok(Some(VehiclePosition {
+                        geo_hash: Some(cmd.geo_hash),
+                        partition_key: Some(cmd.partition_key),
+                        ..cmd.command.into()
+                    }))
+                }
+                _ => Ok(None),
+            }
+        }
+    }
+}
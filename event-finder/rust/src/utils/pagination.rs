@@ -0,0 +1,230 @@
+use crate::types::VehicleQueryOrder;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use datafusion::common::DFSchema;
+use datafusion::logical_expr::SortExpr;
+use datafusion::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Position of the last row returned by a page, used to resume an ordered query.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleQueryCursor {
+    pub order_by: VehicleQueryOrder,
+    pub timestamp: i64,
+    pub vehicle_id: String,
+}
+
+pub fn encode_cursor(cursor: &VehicleQueryCursor) -> anyhow::Result<String> {
+    let json = serde_json::to_vec(cursor)?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+pub fn decode_cursor(value: &str) -> anyhow::Result<VehicleQueryCursor> {
+    let json = URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| anyhow::format_err!("Invalid cursor: {}", e))?;
+    let cursor = serde_json::from_slice::<VehicleQueryCursor>(&json)
+        .map_err(|e| anyhow::format_err!("Invalid cursor: {}", e))?;
+    Ok(cursor)
+}
+
+/// Resolves the effective order of a query, which can be implied by its cursor.
+pub fn resolve_order(
+    order_by: Option<VehicleQueryOrder>,
+    cursor: Option<&VehicleQueryCursor>,
+) -> anyhow::Result<Option<VehicleQueryOrder>> {
+    match (order_by, cursor) {
+        (Some(order), Some(cursor)) if order != cursor.order_by => {
+            anyhow::bail!("The cursor was created for a different orderBy")
+        }
+        (Some(order), _) => Ok(Some(order)),
+        (None, Some(cursor)) => Ok(Some(cursor.order_by)),
+        (None, None) => Ok(None),
+    }
+}
+
+pub fn sort_exprs(order_by: VehicleQueryOrder) -> Vec<SortExpr> {
+    let timestamp = col("timestamp");
+    let vehicle_id = col(r#""vehicleId""#);
+    match order_by {
+        VehicleQueryOrder::TimestampAsc => {
+            vec![timestamp.sort(true, false), vehicle_id.sort(true, false)]
+        }
+        VehicleQueryOrder::TimestampDesc => {
+            vec![timestamp.sort(false, true), vehicle_id.sort(false, true)]
+        }
+        VehicleQueryOrder::VehicleIdTimestamp => {
+            vec![vehicle_id.sort(true, false), timestamp.sort(true, false)]
+        }
+    }
+}
+
+/// Builds the predicate selecting the rows that come strictly after the cursor.
+pub fn cursor_predicate(cursor: &VehicleQueryCursor, schema: &DFSchema) -> anyhow::Result<Expr> {
+    let timestamp = col("timestamp");
+    let vehicle_id = col(r#""vehicleId""#);
+    let last_timestamp = crate::query::timestamp_literal(
+        schema,
+        crate::utils::time::datetime_from_millis(cursor.timestamp)?,
+    )?;
+    let last_vehicle_id = lit(cursor.vehicle_id.clone());
+    let predicate = match cursor.order_by {
+        VehicleQueryOrder::TimestampAsc => {
+            timestamp.clone().gt(last_timestamp.clone()).or(timestamp
+                .eq(last_timestamp)
+                .and(vehicle_id.gt(last_vehicle_id)))
+        }
+        VehicleQueryOrder::TimestampDesc => {
+            timestamp.clone().lt(last_timestamp.clone()).or(timestamp
+                .eq(last_timestamp)
+                .and(vehicle_id.lt(last_vehicle_id)))
+        }
+        VehicleQueryOrder::VehicleIdTimestamp => {
+            vehicle_id.clone().gt(last_vehicle_id.clone()).or(vehicle_id
+                .eq(last_vehicle_id)
+                .and(timestamp.gt(last_timestamp)))
+        }
+    };
+    Ok(predicate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::TestEvent;
+
+    fn cursor(
+        order_by: VehicleQueryOrder,
+        timestamp: &str,
+        vehicle_id: &str,
+    ) -> VehicleQueryCursor {
+        VehicleQueryCursor {
+            order_by,
+            timestamp: timestamp
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+                .timestamp_millis(),
+            vehicle_id: vehicle_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trips_the_cursors() -> anyhow::Result<()> {
+        let original = cursor(
+            VehicleQueryOrder::TimestampDesc,
+            "2024-01-01T10:00:00.250Z",
+            "vehicle/1+2",
+        );
+
+        let encoded = encode_cursor(&original)?;
+        let decoded = decode_cursor(&encoded)?;
+
+        assert!(
+            !encoded.contains(['+', '/', '=']),
+            "not URL safe: {}",
+            encoded
+        );
+        assert_eq!(decoded.order_by, original.order_by);
+        assert_eq!(decoded.timestamp, original.timestamp);
+        assert_eq!(decoded.vehicle_id, original.vehicle_id);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_the_invalid_cursors() {
+        assert!(decode_cursor("not a cursor").is_err());
+        assert!(decode_cursor(&URL_SAFE_NO_PAD.encode(r#"{"timestamp":1}"#)).is_err());
+    }
+
+    #[test]
+    fn resolves_the_order_implied_by_the_cursor() -> anyhow::Result<()> {
+        let cursor = cursor(VehicleQueryOrder::TimestampAsc, "2024-01-01T10:00:00Z", "a");
+
+        assert_eq!(
+            resolve_order(None, Some(&cursor))?,
+            Some(VehicleQueryOrder::TimestampAsc)
+        );
+        assert_eq!(resolve_order(None, None)?, None);
+        assert!(resolve_order(Some(VehicleQueryOrder::TimestampDesc), Some(&cursor)).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resumes_after_the_last_row_of_the_page() -> anyhow::Result<()> {
+        let event = |vehicle_id, timestamp| TestEvent {
+            vehicle_id,
+            vehicle_type: "taxi",
+            timestamp,
+            lon: 4.35,
+            lat: 50.85,
+        };
+        let session = crate::query::tests::events_session(&[
+            event("a", "2024-01-01T10:00:00Z"),
+            event("b", "2024-01-01T10:00:00Z"),
+            event("c", "2024-01-01T10:00:00Z"),
+            event("a", "2024-01-01T10:00:01Z"),
+        ])?;
+        let mut filter =
+            crate::query::VehicleFilter::new("2024-01-01T10:00:00Z", "2024-01-01T11:00:00Z", None)?;
+        filter.vehicle_ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let next_page = async |cursor: &VehicleQueryCursor| -> anyhow::Result<Vec<String>> {
+            let df = crate::query::build_vehicle_dataframe(
+                session.table("events").await?,
+                &filter,
+                None,
+                &crate::query::VehicleSelection {
+                    check_rows: true,
+                    cursor: Some(cursor),
+                    order_by: Some(cursor.order_by),
+                    ..Default::default()
+                },
+            )?;
+            let batches = crate::query::plain_result_columns(df)?.collect().await?;
+            let mut rows = vec![];
+            for batch in batches {
+                let timestamps =
+                    datafusion::common::cast::as_timestamp_millisecond_array(batch.column(0))?;
+                let vehicle_ids = datafusion::common::cast::as_string_array(batch.column(1))?;
+                for i in 0..batch.num_rows() {
+                    let datetime = crate::utils::time::datetime_from_millis(timestamps.value(i))?;
+                    rows.push(format!(
+                        "{}@{}",
+                        vehicle_ids.value(i),
+                        datetime.format("%S")
+                    ));
+                }
+            }
+            Ok(rows)
+        };
+
+        assert_eq!(
+            next_page(&cursor(
+                VehicleQueryOrder::TimestampAsc,
+                "2024-01-01T10:00:00Z",
+                "b"
+            ))
+            .await?,
+            vec!["c@00", "a@01"]
+        );
+        assert_eq!(
+            next_page(&cursor(
+                VehicleQueryOrder::TimestampDesc,
+                "2024-01-01T10:00:00Z",
+                "b"
+            ))
+            .await?,
+            vec!["a@00"]
+        );
+        assert_eq!(
+            next_page(&cursor(
+                VehicleQueryOrder::VehicleIdTimestamp,
+                "2024-01-01T10:00:00Z",
+                "a"
+            ))
+            .await?,
+            vec!["a@01", "b@00", "c@00"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,440 @@
+use crate::types_proto as proto;
+use prost::Message;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+pub const PROTO_TYPE_HEADER: &str = "proto/type";
+
+tokio::task_local! {
+    /// Whether the replies to the request being processed must be encoded with protobuf.
+    pub static REPLY_WITH_PROTO_BUF: bool;
+}
+
+pub fn reply_with_proto_buf() -> bool {
+    REPLY_WITH_PROTO_BUF
+        .try_with(|enabled| *enabled)
+        .unwrap_or(false)
+}
+
+pub fn proto_type(msg: &async_nats::Message) -> Option<&str> {
+    msg.headers
+        .as_ref()
+        .and_then(|headers| headers.get(PROTO_TYPE_HEADER))
+        .map(|value| value.as_str())
+}
+
+/// Decodes a protobuf message to its JSON form, so that it can be deserialized like the JSON messages.
+pub fn decode_message(proto_type: &str, payload: &[u8]) -> anyhow::Result<Value> {
+    let mut value = match proto_type {
+        "request" => return decode_request(proto::Request::decode(payload)?),
+        "response-success" | "response-error" => {
+            return decode_response(proto_type, proto::Response::decode(payload)?);
+        }
+        "vehicle-query-request" => to_json(proto::VehicleQueryRequest::decode(payload)?)?,
+        "vehicle-query-response" => to_json(proto::VehicleQueryResponse::decode(payload)?)?,
+        "vehicle-query-result" => to_json(proto::VehicleQueryResult::decode(payload)?)?,
+        "vehicle-query-started" => {
+            let event = proto::VehicleQueryStartedEvent::decode(payload)?;
+            json!({ "query": decode_request(event.query.unwrap_or_default())? })
+        }
+        "vehicle-query-stopped" => {
+            let event = proto::VehicleQueryStoppedEvent::decode(payload)?;
+            let response = match event.response {
+                Some(response) => {
+                    let mut response = to_json(response)?;
+                    response["type"] = json!("vehicle-query-response");
+                    response
+                }
+                None => Value::Null,
+            };
+            json!({
+                "query": decode_request(event.query.unwrap_or_default())?,
+                "isSuccess": event.is_success,
+                "response": response,
+                "error": event.error,
+            })
+        }
+        "ping" => to_json(proto::PingRequest::decode(payload)?)?,
+        "pong" => to_json(proto::PingResponse::decode(payload)?)?,
+        _ => anyhow::bail!("Unsupported protobuf message type '{}'", proto_type),
+    };
+    // the GeoJSON geometries are sent as strings
+    for path in ["/geometry", "/circle/center", "/corridor/line"] {
+        if let Some(field) = value.pointer_mut(path)
+            && let Value::String(geometry) = field
+        {
+            *field = serde_json::from_str(geometry)?;
+        }
+    }
+    value["type"] = json!(proto_type);
+    Ok(value)
+}
+
+/// Encodes the JSON form of a message with protobuf, returning its protobuf type and payload,
+/// or None when the message has no protobuf form.
+pub fn encode_message<TMessage: Serialize>(
+    msg: &TMessage,
+) -> anyhow::Result<Option<(String, Vec<u8>)>> {
+    let mut value = serde_json::to_value(msg)?;
+    let Some(msg_type) = value["type"].as_str().map(str::to_string) else {
+        return Ok(None);
+    };
+    strip_nulls(&mut value);
+    for path in ["/geometry", "/circle/center", "/corridor/line"] {
+        if let Some(field) = value.pointer_mut(path)
+            && field.is_object()
+        {
+            *field = Value::String(field.to_string());
+        }
+    }
+    let payload = match msg_type.as_str() {
+        "request" => match encode_request(&value)? {
+            Some(request) => request.encode_to_vec(),
+            None => return Ok(None),
+        },
+        "response-success" | "response-error" => {
+            let (body_type, body) = match value.get("body") {
+                Some(body) if msg_type == "response-success" => match encode_message(body)? {
+                    Some(encoded) => encoded,
+                    None => return Ok(None),
+                },
+                _ => (String::new(), vec![]),
+            };
+            proto::Response {
+                id: string_field(&value, "id"),
+                request_id: string_field(&value, "requestId"),
+                code: value["code"].as_str().map(str::to_string),
+                error: value["error"].as_str().map(str::to_string),
+                body_type,
+                body,
+            }
+            .encode_to_vec()
+        }
+        "vehicle-query-request" => from_json::<proto::VehicleQueryRequest>(value)?.encode_to_vec(),
+        "vehicle-query-response" => {
+            from_json::<proto::VehicleQueryResponse>(value)?.encode_to_vec()
+        }
+        "vehicle-query-result" => from_json::<proto::VehicleQueryResult>(value)?.encode_to_vec(),
+        "vehicle-query-started" => {
+            let Some(query) = encode_request(&value["query"])? else {
+                return Ok(None);
+            };
+            proto::VehicleQueryStartedEvent { query: Some(query) }.encode_to_vec()
+        }
+        "vehicle-query-stopped" => {
+            let Some(query) = encode_request(&value["query"])? else {
+                return Ok(None);
+            };
+            let response = match value.get("response") {
+                Some(response) => Some(from_json(response.clone())?),
+                None => None,
+            };
+            proto::VehicleQueryStoppedEvent {
+                query: Some(query),
+                is_success: value["isSuccess"].as_bool().unwrap_or_default(),
+                response,
+                error: value["error"].as_str().map(str::to_string),
+            }
+            .encode_to_vec()
+        }
+        "ping" => from_json::<proto::PingRequest>(value)?.encode_to_vec(),
+        "pong" => from_json::<proto::PingResponse>(value)?.encode_to_vec(),
+        _ => return Ok(None),
+    };
+    Ok(Some((msg_type, payload)))
+}
+
+fn decode_request(request: proto::Request) -> anyhow::Result<Value> {
+    Ok(json!({
+        "type": "request",
+        "id": request.id,
+        "replyTo": request.reply_to,
+        "parentId": request.parent_id,
+        "expiresAt": request.expires_at,
+        "timeout": request.timeout,
+        "body": decode_message(&request.body_type, &request.body)?,
+    }))
+}
+
+fn decode_response(msg_type: &str, response: proto::Response) -> anyhow::Result<Value> {
+    let body = match response.body_type.as_str() {
+        "" => Value::Null,
+        body_type => decode_message(body_type, &response.body)?,
+    };
+    Ok(json!({
+        "type": msg_type,
+        "id": response.id,
+        "requestId": response.request_id,
+        "code": response.code,
+        "error": response.error,
+        "body": body,
+    }))
+}
+
+fn encode_request(value: &Value) -> anyhow::Result<Option<proto::Request>> {
+    let Some((body_type, body)) = encode_message(&value["body"])? else {
+        return Ok(None);
+    };
+    Ok(Some(proto::Request {
+        id: string_field(value, "id"),
+        reply_to: string_field(value, "replyTo"),
+        parent_id: value["parentId"].as_str().map(str::to_string),
+        expires_at: value["expiresAt"].as_str().map(str::to_string),
+        timeout: value["timeout"].as_u64(),
+        body_type,
+        body,
+    }))
+}
+
+fn string_field(value: &Value, name: &str) -> String {
+    value[name].as_str().unwrap_or_default().to_string()
+}
+
+fn to_json<TMessage: Serialize>(msg: TMessage) -> anyhow::Result<Value> {
+    Ok(serde_json::to_value(msg)?)
+}
+
+fn from_json<TMessage: DeserializeOwned>(value: Value) -> anyhow::Result<TMessage> {
+    Ok(serde_json::from_value(value)?)
+}
+
+// the missing fields get their default value, while null is rejected for the non optional ones
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|_, field| !field.is_null());
+            fields.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        PingRequest, PingResponse, Request, Response, ResponseErrorCode, ServiceIdentity,
+        VehicleQueryRequest, VehicleQueryResponse, VehicleQueryStartedEvent,
+        VehicleQueryStoppedEvent,
+    };
+    use std::collections::HashMap;
+
+    /// Encodes the message with protobuf and decodes it back, returning its protobuf type and JSON form.
+    fn round_trip<TMessage: Serialize>(msg: &TMessage) -> anyhow::Result<(String, Value)> {
+        let (proto_type, payload) =
+            encode_message(msg)?.ok_or_else(|| anyhow::format_err!("No protobuf form"))?;
+        let mut decoded = decode_message(&proto_type, &payload)?;
+        strip_nulls(&mut decoded);
+        Ok((proto_type, decoded))
+    }
+
+    fn json<TMessage: Serialize>(
+        proto_type: &str,
+        msg: &TMessage,
+    ) -> anyhow::Result<(String, Value)> {
+        let mut value = serde_json::to_value(msg)?;
+        strip_nulls(&mut value);
+        Ok((proto_type.to_string(), value))
+    }
+
+    fn query_request() -> Request<VehicleQueryRequest> {
+        serde_json::from_value(json!({
+            "id": "request-1",
+            "type": "request",
+            "replyTo": "inbox.1",
+            "expiresAt": "2024-01-01T10:05:00Z",
+            "timeout": 30000,
+            "body": {
+                "type": "vehicle-query-request",
+                "id": "q1",
+                "fromDate": "2024-01-01T10:00:00Z",
+                "toDate": "2024-01-01T11:00:00Z",
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [[[4.3, 50.8], [4.4, 50.8], [4.4, 50.9], [4.3, 50.8]]],
+                },
+                "circle": {
+                    "center": { "type": "Point", "coordinates": [4.35, 50.85] },
+                    "radiusInMeters": 500.0,
+                },
+                "vehicleTypes": ["taxi"],
+                "vehicleIds": ["a", "b"],
+                "minSpeed": 10.5,
+                "directions": ["north"],
+                "limit": 100,
+                "orderBy": "vehicleIdTimestamp",
+                "deduplicate": true,
+                "sampleIntervalInSecs": 60,
+                "sampleMethod": "last",
+                "format": "arrow",
+            },
+        }))
+        .unwrap()
+    }
+
+    fn query_response() -> VehicleQueryResponse {
+        VehicleQueryResponse {
+            msg_type: "vehicle-query-response".to_string(),
+            processed_files_count: 3,
+            processed_bytes: 4096,
+            processed_record_count: 1000,
+            selected_record_count: 100,
+            distinct_vehicle_count: 2,
+            elapsed_time_in_MS: 250,
+            timeout_expired: false,
+            limit_reached: true,
+            from_cache: false,
+            cursor: Some("cursor".to_string()),
+            duplicate_count: 1,
+        }
+    }
+
+    #[test]
+    fn round_trips_the_request_envelopes() -> anyhow::Result<()> {
+        let request = query_request();
+
+        let (proto_type, decoded) = round_trip(&request)?;
+
+        assert_eq!((proto_type, decoded.clone()), json("request", &request)?);
+        // as deserialized by the message loops
+        serde_json::from_value::<Request<VehicleQueryRequest>>(decoded)?;
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_the_response_envelopes() -> anyhow::Result<()> {
+        let success = Response::Success {
+            id: "response-1".to_string(),
+            request_id: "request-1".to_string(),
+            body: query_response(),
+        };
+        let error = Response::<VehicleQueryResponse>::Error {
+            id: "response-2".to_string(),
+            request_id: "request-1".to_string(),
+            code: ResponseErrorCode::Timeout,
+            body: None,
+            error: Some("The query timed out".to_string()),
+        };
+
+        assert_eq!(round_trip(&success)?, json("response-success", &success)?);
+        assert_eq!(round_trip(&error)?, json("response-error", &error)?);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_the_query_events() -> anyhow::Result<()> {
+        let started = VehicleQueryStartedEvent {
+            msg_type: "vehicle-query-started".to_string(),
+            query: query_request(),
+        };
+        let stopped = VehicleQueryStoppedEvent {
+            msg_type: "vehicle-query-stopped".to_string(),
+            query: query_request(),
+            is_success: true,
+            response: Some(query_response()),
+            error: None,
+        };
+        let failed = VehicleQueryStoppedEvent {
+            is_success: false,
+            response: None,
+            error: Some("Invalid cursor".to_string()),
+            ..stopped.clone()
+        };
+
+        assert_eq!(
+            round_trip(&started)?,
+            json("vehicle-query-started", &started)?
+        );
+        for event in [stopped, failed] {
+            assert_eq!(round_trip(&event)?, json("vehicle-query-stopped", &event)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_the_pings() -> anyhow::Result<()> {
+        let ping = Request {
+            id: "request-1".to_string(),
+            msg_type: "request".to_string(),
+            reply_to: "inbox.1".to_string(),
+            parent_id: None,
+            expires_at: None,
+            timeout: None,
+            body: PingRequest {
+                msg_type: "ping".to_string(),
+                service_name: Some("finder".to_string()),
+            },
+        };
+        let pong = Response::Success {
+            id: "response-1".to_string(),
+            request_id: "request-1".to_string(),
+            body: PingResponse {
+                msg_type: "pong".to_string(),
+                identity: ServiceIdentity {
+                    name: "finder".to_string(),
+                    instance: 2,
+                    runtime: "rust".to_string(),
+                },
+            },
+        };
+
+        assert_eq!(round_trip(&ping)?, json("request", &ping)?);
+        assert_eq!(round_trip(&pong)?, json("response-success", &pong)?);
+        Ok(())
+    }
+
+    #[test]
+    fn skips_the_messages_without_protobuf_form() -> anyhow::Result<()> {
+        assert!(encode_message(&json!({ "type": "vehicle-export-request" }))?.is_none());
+        assert!(encode_message(&json!({ "id": "untyped" }))?.is_none());
+        assert!(decode_message("vehicle-export-request", &[]).is_err());
+        Ok(())
+    }
+
+    /// Returns the label, type and number of the fields of each message of a proto file.
+    fn proto_fields(path: &str) -> anyhow::Result<HashMap<String, HashMap<String, String>>> {
+        let mut messages: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut message = None;
+        for line in std::fs::read_to_string(path)?.lines() {
+            let line = line.split("//").next().unwrap_or_default().trim();
+            if let Some(name) = line.strip_prefix("message ") {
+                let name = name.trim_end_matches('{').trim().to_string();
+                messages.entry(name.clone()).or_default();
+                message = Some(name);
+            } else if line.starts_with('}') {
+                message = None;
+            } else if let (Some(message), Some((declaration, number))) =
+                (&message, line.trim_end_matches(';').split_once('='))
+            {
+                let mut tokens = declaration.split_whitespace().collect::<Vec<_>>();
+                let name = tokens.pop().unwrap_or_default().to_string();
+                tokens.push(number.trim());
+                messages
+                    .get_mut(message)
+                    .unwrap()
+                    .insert(name, tokens.join(" "));
+            }
+        }
+        Ok(messages)
+    }
+
+    #[test]
+    fn has_the_same_fields_as_the_javascript_messages() -> anyhow::Result<()> {
+        let messages = proto_fields(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/proto/types.proto"
+        ))?;
+        let js_messages = proto_fields(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../shared/javascript/core-lib/src/proto/messages.proto"
+        ))?;
+
+        assert_eq!(messages.len(), 16);
+        for (name, fields) in messages.iter() {
+            assert_eq!(js_messages.get(name), Some(fields), "message {}", name);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+use geo::{HaversineDistance, point};
+
+// The speed of the events is expressed in meters per hour.
+pub const DEFAULT_MAX_SPEED: f64 = 1000.0;
+pub const DEFAULT_MIN_DURATION_IN_MIN: u32 = 5;
+
+/// The conditions under which a vehicle is considered stopped.
+/// A position extends a stop when its speed is at most `max_speed`,
+/// or when it is within `max_radius_in_meters` of the first position of the stop.
+#[derive(Debug, Clone, Copy)]
+pub struct StopCriteria {
+    pub max_speed: Option<f64>,
+    pub max_radius_in_meters: Option<f64>,
+    pub min_duration_in_ms: i64,
+}
+
+impl StopCriteria {
+    /// Uses the default max speed when neither a max speed nor a max radius are specified.
+    pub fn new(
+        max_speed: Option<f64>,
+        max_radius_in_meters: Option<f64>,
+        min_duration_in_min: Option<u32>,
+    ) -> anyhow::Result<Self> {
+        let criteria = Self {
+            max_speed: match (max_speed, max_radius_in_meters) {
+                (None, None) => Some(DEFAULT_MAX_SPEED),
+                (max_speed, _) => max_speed,
+            },
+            max_radius_in_meters,
+            min_duration_in_ms: i64::from(
+                min_duration_in_min.unwrap_or(DEFAULT_MIN_DURATION_IN_MIN),
+            ) * 60_000,
+        };
+        criteria.validate()?;
+        Ok(criteria)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.max_speed.is_none() && self.max_radius_in_meters.is_none() {
+            anyhow::bail!("A max speed or a max radius is required to detect the stops");
+        }
+        if self.min_duration_in_ms <= 0 {
+            anyhow::bail!("The min duration of a stop must be greater than 0");
+        }
+        Ok(())
+    }
+}
+
+/// A period during which a vehicle did not move.
+#[derive(Debug, Clone)]
+pub struct Stop {
+    pub start_time: i64,
+    pub end_time: i64,
+    // centroid of the positions
+    pub lat: f64,
+    pub lon: f64,
+    pub point_count: usize,
+}
+
+impl Stop {
+    pub fn duration_in_ms(&self) -> i64 {
+        self.end_time - self.start_time
+    }
+}
+
+struct StopCandidate {
+    start_time: i64,
+    end_time: i64,
+    anchor_lat: f64,
+    anchor_lon: f64,
+    sum_lat: f64,
+    sum_lon: f64,
+    point_count: usize,
+}
+
+impl StopCandidate {
+    fn new(timestamp: i64, lon: f64, lat: f64) -> Self {
+        Self {
+            start_time: timestamp,
+            end_time: timestamp,
+            anchor_lat: lat,
+            anchor_lon: lon,
+            sum_lat: lat,
+            sum_lon: lon,
+            point_count: 1,
+        }
+    }
+
+    fn push(&mut self, timestamp: i64, lon: f64, lat: f64) {
+        self.end_time = timestamp;
+        self.sum_lat += lat;
+        self.sum_lon += lon;
+        self.point_count += 1;
+    }
+
+    fn into_stop(self, min_duration_in_ms: i64) -> Option<Stop> {
+        if self.end_time - self.start_time < min_duration_in_ms {
+            return None;
+        }
+        Some(Stop {
+            start_time: self.start_time,
+            end_time: self.end_time,
+            lat: self.sum_lat / self.point_count as f64,
+            lon: self.sum_lon / self.point_count as f64,
+            point_count: self.point_count,
+        })
+    }
+}
+
+/// Detects the stops of a single vehicle, from its positions ordered by time.
+pub struct StopDetector {
+    criteria: StopCriteria,
+    candidate: Option<StopCandidate>,
+}
+
+impl StopDetector {
+    pub fn new(criteria: StopCriteria) -> Self {
+        Self {
+            criteria,
+            candidate: None,
+        }
+    }
+
+    /// Returns the stop ended by this position, if any.
+    pub fn push(&mut self, timestamp: i64, lon: f64, lat: f64, speed: f64) -> Option<Stop> {
+        let is_slow = self
+            .criteria
+            .max_speed
+            .is_some_and(|max_speed| speed <= max_speed);
+        if let Some(candidate) = self.candidate.as_mut() {
+            let is_near = self
+                .criteria
+                .max_radius_in_meters
+                .is_some_and(|max_radius| {
+                    point!(x: candidate.anchor_lon, y: candidate.anchor_lat)
+                        .haversine_distance(&point!(x: lon, y: lat))
+                        <= max_radius
+                });
+            if is_slow || is_near {
+                candidate.push(timestamp, lon, lat);
+                return None;
+            }
+        }
+        let stop = self
+            .candidate
+            .take()
+            .and_then(|candidate| candidate.into_stop(self.criteria.min_duration_in_ms));
+        // any position can start a stop when the radius is checked
+        if is_slow || self.criteria.max_radius_in_meters.is_some() {
+            self.candidate = Some(StopCandidate::new(timestamp, lon, lat));
+        }
+        stop
+    }
+
+    /// Returns the pending stop, once all the positions of the vehicle have been pushed.
+    pub fn finish(&mut self) -> Option<Stop> {
+        self.candidate
+            .take()
+            .and_then(|candidate| candidate.into_stop(self.criteria.min_duration_in_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60_000;
+    const MOVING: f64 = 30_000.0;
+
+    #[test]
+    fn uses_the_default_max_speed_and_min_duration() -> anyhow::Result<()> {
+        let criteria = StopCriteria::new(None, None, None)?;
+
+        assert_eq!(criteria.max_speed, Some(DEFAULT_MAX_SPEED));
+        assert_eq!(criteria.min_duration_in_ms, 5 * MINUTE);
+        assert_eq!(
+            StopCriteria::new(None, Some(50.0), None)?.max_speed,
+            None,
+            "the radius alone detects the stops"
+        );
+        assert!(StopCriteria::new(None, None, Some(0)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn detects_the_stops_below_the_max_speed() -> anyhow::Result<()> {
+        let mut detector = StopDetector::new(StopCriteria::new(Some(1000.0), None, Some(5))?);
+
+        assert!(detector.push(0, 4.35, 50.85, MOVING).is_none());
+        for minute in 1..=7 {
+            let lat = if minute % 2 == 0 { 50.8502 } else { 50.8500 };
+            assert!(detector.push(minute * MINUTE, 4.36, lat, 0.0).is_none());
+        }
+        let stop = detector.push(8 * MINUTE, 4.37, 50.86, MOVING).unwrap();
+
+        assert_eq!(stop.start_time, MINUTE);
+        assert_eq!(stop.end_time, 7 * MINUTE);
+        assert_eq!(stop.duration_in_ms(), 6 * MINUTE);
+        assert_eq!(stop.point_count, 7);
+        assert!((stop.lat - 50.85008571).abs() < 1e-6);
+        assert_eq!(stop.lon, 4.36);
+        assert!(detector.finish().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_the_stops_shorter_than_the_min_duration() -> anyhow::Result<()> {
+        let mut detector = StopDetector::new(StopCriteria::new(Some(1000.0), None, Some(5))?);
+
+        for minute in 0..4 {
+            assert!(detector.push(minute * MINUTE, 4.36, 50.85, 0.0).is_none());
+        }
+        assert!(detector.push(4 * MINUTE, 4.37, 50.86, MOVING).is_none());
+        assert!(detector.finish().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn detects_the_stops_within_the_radius_despite_the_gps_noise() -> anyhow::Result<()> {
+        let mut detector = StopDetector::new(StopCriteria::new(None, Some(50.0), Some(5))?);
+
+        // about 20 meters around the first position, with the speed of the noise
+        for minute in 0..=6 {
+            let lon = if minute % 2 == 0 { 4.35 } else { 4.3502 };
+            assert!(detector.push(minute * MINUTE, lon, 50.85, MOVING).is_none());
+        }
+        let stop = detector.push(7 * MINUTE, 4.37, 50.85, MOVING).unwrap();
+
+        assert_eq!(stop.start_time, 0);
+        assert_eq!(stop.end_time, 6 * MINUTE);
+        assert_eq!(stop.point_count, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn returns_the_pending_stop_when_finished() -> anyhow::Result<()> {
+        let mut detector = StopDetector::new(StopCriteria::new(Some(1000.0), None, Some(5))?);
+
+        for minute in 0..=5 {
+            assert!(detector.push(minute * MINUTE, 4.36, 50.85, 0.0).is_none());
+        }
+        let stop = detector.finish().unwrap();
+
+        assert_eq!(stop.duration_in_ms(), 5 * MINUTE);
+        assert!(detector.finish().is_none());
+        Ok(())
+    }
+}
@@ -20,3 +20,45 @@ pub fn round_datetime_modulo_minutes(dt: DateTime<Utc>, modulo: u32) -> (DateTim
 
     (lower, upper)
 }
+
+/// Converts a timestamp in milliseconds, such as the ones of the events, to a date.
+pub fn datetime_from_millis(timestamp: i64) -> anyhow::Result<DateTime<Utc>> {
+    Utc.timestamp_millis_opt(timestamp)
+        .single()
+        .ok_or_else(|| anyhow::format_err!("Invalid timestamp {}", timestamp))
+}
+
+/// Formats a timestamp in milliseconds as an RFC 3339 date.
+pub fn format_timestamp_millis(timestamp: i64) -> anyhow::Result<String> {
+    Ok(datetime_from_millis(timestamp)?.to_rfc3339())
+}
+
+/// Parses an RFC 3339 date, or an RFC 2822 date such as the ones sent by the collectors.
+pub fn parse_datetime(value: &str) -> anyhow::Result<DateTime<Utc>> {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(dt) => Ok(dt.with_timezone(&Utc)),
+        Err(_) => Ok(DateTime::parse_from_rfc2822(value)?.with_timezone(&Utc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_dates_of_the_collectors() {
+        let expected = Utc.with_ymd_and_hms(2024, 1, 1, 6, 50, 0).unwrap();
+        assert_eq!(parse_datetime("Mon, 01 Jan 2024 06:50:00 GMT").unwrap(), expected);
+        assert_eq!(parse_datetime("2024-01-01T06:50:00Z").unwrap(), expected);
+        assert!(parse_datetime("yesterday").is_err());
+    }
+
+    #[test]
+    fn formats_the_timestamps_in_milliseconds() {
+        assert_eq!(
+            format_timestamp_millis(1_704_091_800_000).unwrap(),
+            "2024-01-01T06:50:00+00:00"
+        );
+        assert!(format_timestamp_millis(i64::MAX).is_err());
+    }
+}
@@ -17,6 +17,7 @@ $root.ServiceIdentity = (function() {
      * @interface IServiceIdentity
      * @property {string|null} [name] ServiceIdentity name
      * @property {number|null} [instance] ServiceIdentity instance
+     * @property {string|null} [runtime] ServiceIdentity runtime
      */
 
     /**
@@ -50,6 +51,14 @@ $root.ServiceIdentity = (function() {
      */
     ServiceIdentity.prototype.instance = 0;
 
+    /**
+     * ServiceIdentity runtime.
+     * @member {string} runtime
+     * @memberof ServiceIdentity
+     * @instance
+     */
+    ServiceIdentity.prototype.runtime = "";
+
     /**
      * Creates a new ServiceIdentity instance using the specified properties.
      * @function create
@@ -78,6 +87,8 @@ $root.ServiceIdentity = (function() {
             writer.uint32(/* id 1, wireType 2 =*/10).string(message.name);
         if (message.instance != null && Object.hasOwnProperty.call(message, "instance"))
             writer.uint32(/* id 2, wireType 0 =*/16).int32(message.instance);
+        if (message.runtime != null && Object.hasOwnProperty.call(message, "runtime"))
+            writer.uint32(/* id 3, wireType 2 =*/26).string(message.runtime);
         return writer;
     };
 
@@ -122,6 +133,10 @@ $root.ServiceIdentity = (function() {
                     message.instance = reader.int32();
                     break;
                 }
+            case 3: {
+                    message.runtime = reader.string();
+                    break;
+                }
             default:
                 reader.skipType(tag & 7);
                 break;
@@ -163,6 +178,9 @@ $root.ServiceIdentity = (function() {
         if (message.instance != null && message.hasOwnProperty("instance"))
             if (!$util.isInteger(message.instance))
                 return "instance: integer expected";
+        if (message.runtime != null && message.hasOwnProperty("runtime"))
+            if (!$util.isString(message.runtime))
+                return "runtime: string expected";
         return null;
     };
 
@@ -182,6 +200,8 @@ $root.ServiceIdentity = (function() {
             message.name = String(object.name);
         if (object.instance != null)
             message.instance = object.instance | 0;
+        if (object.runtime != null)
+            message.runtime = String(object.runtime);
         return message;
     };
 
@@ -201,11 +221,14 @@ $root.ServiceIdentity = (function() {
         if (options.defaults) {
             object.name = "";
             object.instance = 0;
+            object.runtime = "";
         }
         if (message.name != null && message.hasOwnProperty("name"))
             object.name = message.name;
         if (message.instance != null && message.hasOwnProperty("instance"))
             object.instance = message.instance;
+        if (message.runtime != null && message.hasOwnProperty("runtime"))
+            object.runtime = message.runtime;
         return object;
     };
 
@@ -1753,4 +1776,3611 @@ $root.VehicleQueryResult = (function() {
     return VehicleQueryResult;
 })();
 
+$root.Request = (function() {
+
+    /**
+     * Properties of a Request.
+     * @exports IRequest
+     * @interface IRequest
+     * @property {string|null} [id] Request id
+     * @property {string|null} [replyTo] Request replyTo
+     * @property {string|null} [parentId] Request parentId
+     * @property {string|null} [expiresAt] Request expiresAt
+     * @property {number|Long|null} [timeout] Request timeout
+     * @property {string|null} [bodyType] Request bodyType
+     * @property {Uint8Array|null} [body] Request body
+     */
+
+    /**
+     * Constructs a new Request.
+     * @exports Request
+     * @classdesc Represents a Request.
+     * @implements IRequest
+     * @constructor
+     * @param {IRequest=} [properties] Properties to set
+     */
+    function Request(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * Request id.
+     * @member {string} id
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.id = "";
+
+    /**
+     * Request replyTo.
+     * @member {string} replyTo
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.replyTo = "";
+
+    /**
+     * Request parentId.
+     * @member {string|null|undefined} parentId
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.parentId = null;
+
+    /**
+     * Request expiresAt.
+     * @member {string|null|undefined} expiresAt
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.expiresAt = null;
+
+    /**
+     * Request timeout.
+     * @member {number|Long|null|undefined} timeout
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.timeout = null;
+
+    /**
+     * Request bodyType.
+     * @member {string} bodyType
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.bodyType = "";
+
+    /**
+     * Request body.
+     * @member {Uint8Array} body
+     * @memberof Request
+     * @instance
+     */
+    Request.prototype.body = $util.newBuffer([]);
+
+    // OneOf field names bound to virtual getters and setters
+    var $oneOfFields;
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(Request.prototype, "_parentId", {
+        get: $util.oneOfGetter($oneOfFields = ["parentId"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(Request.prototype, "_expiresAt", {
+        get: $util.oneOfGetter($oneOfFields = ["expiresAt"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(Request.prototype, "_timeout", {
+        get: $util.oneOfGetter($oneOfFields = ["timeout"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    /**
+     * Creates a new Request instance using the specified properties.
+     * @function create
+     * @memberof Request
+     * @static
+     * @param {IRequest=} [properties] Properties to set
+     * @returns {Request} Request instance
+     */
+    Request.create = function create(properties) {
+        return new Request(properties);
+    };
+
+    /**
+     * Encodes the specified Request message. Does not implicitly {@link Request.verify|verify} messages.
+     * @function encode
+     * @memberof Request
+     * @static
+     * @param {IRequest} message Request message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    Request.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.id != null && Object.hasOwnProperty.call(message, "id"))
+            writer.uint32(/* id 1, wireType 2 =*/10).string(message.id);
+        if (message.replyTo != null && Object.hasOwnProperty.call(message, "replyTo"))
+            writer.uint32(/* id 2, wireType 2 =*/18).string(message.replyTo);
+        if (message.parentId != null && Object.hasOwnProperty.call(message, "parentId"))
+            writer.uint32(/* id 3, wireType 2 =*/26).string(message.parentId);
+        if (message.expiresAt != null && Object.hasOwnProperty.call(message, "expiresAt"))
+            writer.uint32(/* id 4, wireType 2 =*/34).string(message.expiresAt);
+        if (message.timeout != null && Object.hasOwnProperty.call(message, "timeout"))
+            writer.uint32(/* id 5, wireType 0 =*/40).uint64(message.timeout);
+        if (message.bodyType != null && Object.hasOwnProperty.call(message, "bodyType"))
+            writer.uint32(/* id 6, wireType 2 =*/50).string(message.bodyType);
+        if (message.body != null && Object.hasOwnProperty.call(message, "body"))
+            writer.uint32(/* id 7, wireType 2 =*/58).bytes(message.body);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified Request message, length delimited. Does not implicitly {@link Request.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof Request
+     * @static
+     * @param {IRequest} message Request message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    Request.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a Request message from the specified reader or buffer.
+     * @function decode
+     * @memberof Request
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {Request} Request
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    Request.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.Request();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.id = reader.string();
+                    break;
+                }
+            case 2: {
+                    message.replyTo = reader.string();
+                    break;
+                }
+            case 3: {
+                    message.parentId = reader.string();
+                    break;
+                }
+            case 4: {
+                    message.expiresAt = reader.string();
+                    break;
+                }
+            case 5: {
+                    message.timeout = reader.uint64();
+                    break;
+                }
+            case 6: {
+                    message.bodyType = reader.string();
+                    break;
+                }
+            case 7: {
+                    message.body = reader.bytes();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a Request message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof Request
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {Request} Request
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    Request.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a Request message.
+     * @function verify
+     * @memberof Request
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    Request.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        var properties = {};
+        if (message.id != null && message.hasOwnProperty("id"))
+            if (!$util.isString(message.id))
+                return "id: string expected";
+        if (message.replyTo != null && message.hasOwnProperty("replyTo"))
+            if (!$util.isString(message.replyTo))
+                return "replyTo: string expected";
+        if (message.parentId != null && message.hasOwnProperty("parentId")) {
+            properties._parentId = 1;
+            if (!$util.isString(message.parentId))
+                return "parentId: string expected";
+        }
+        if (message.expiresAt != null && message.hasOwnProperty("expiresAt")) {
+            properties._expiresAt = 1;
+            if (!$util.isString(message.expiresAt))
+                return "expiresAt: string expected";
+        }
+        if (message.timeout != null && message.hasOwnProperty("timeout")) {
+            properties._timeout = 1;
+            if (!$util.isInteger(message.timeout) && !(message.timeout && $util.isInteger(message.timeout.low) && $util.isInteger(message.timeout.high)))
+                return "timeout: integer|Long expected";
+        }
+        if (message.bodyType != null && message.hasOwnProperty("bodyType"))
+            if (!$util.isString(message.bodyType))
+                return "bodyType: string expected";
+        if (message.body != null && message.hasOwnProperty("body"))
+            if (!(message.body && typeof message.body.length === "number" || $util.isString(message.body)))
+                return "body: buffer expected";
+        return null;
+    };
+
+    /**
+     * Creates a Request message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof Request
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {Request} Request
+     */
+    Request.fromObject = function fromObject(object) {
+        if (object instanceof $root.Request)
+            return object;
+        var message = new $root.Request();
+        if (object.id != null)
+            message.id = String(object.id);
+        if (object.replyTo != null)
+            message.replyTo = String(object.replyTo);
+        if (object.parentId != null)
+            message.parentId = String(object.parentId);
+        if (object.expiresAt != null)
+            message.expiresAt = String(object.expiresAt);
+        if (object.timeout != null)
+            if ($util.Long)
+                (message.timeout = $util.Long.fromValue(object.timeout)).unsigned = true;
+            else if (typeof object.timeout === "string")
+                message.timeout = parseInt(object.timeout, 10);
+            else if (typeof object.timeout === "number")
+                message.timeout = object.timeout;
+            else if (typeof object.timeout === "object")
+                message.timeout = new $util.LongBits(object.timeout.low >>> 0, object.timeout.high >>> 0).toNumber(true);
+        if (object.bodyType != null)
+            message.bodyType = String(object.bodyType);
+        if (object.body != null)
+            if (typeof object.body === "string")
+                $util.base64.decode(object.body, message.body = $util.newBuffer($util.base64.length(object.body)), 0);
+            else if (object.body.length >= 0)
+                message.body = object.body;
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a Request message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof Request
+     * @static
+     * @param {Request} message Request
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    Request.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults) {
+            object.id = "";
+            object.replyTo = "";
+            object.bodyType = "";
+            if (options.bytes === String)
+                object.body = "";
+            else {
+                object.body = [];
+                if (options.bytes !== Array)
+                    object.body = $util.newBuffer(object.body);
+            }
+        }
+        if (message.id != null && message.hasOwnProperty("id"))
+            object.id = message.id;
+        if (message.replyTo != null && message.hasOwnProperty("replyTo"))
+            object.replyTo = message.replyTo;
+        if (message.parentId != null && message.hasOwnProperty("parentId")) {
+            object.parentId = message.parentId;
+            if (options.oneofs)
+                object._parentId = "parentId";
+        }
+        if (message.expiresAt != null && message.hasOwnProperty("expiresAt")) {
+            object.expiresAt = message.expiresAt;
+            if (options.oneofs)
+                object._expiresAt = "expiresAt";
+        }
+        if (message.timeout != null && message.hasOwnProperty("timeout")) {
+            if (typeof message.timeout === "number")
+                object.timeout = options.longs === String ? String(message.timeout) : message.timeout;
+            else
+                object.timeout = options.longs === String ? $util.Long.prototype.toString.call(message.timeout) : options.longs === Number ? new $util.LongBits(message.timeout.low >>> 0, message.timeout.high >>> 0).toNumber(true) : message.timeout;
+            if (options.oneofs)
+                object._timeout = "timeout";
+        }
+        if (message.bodyType != null && message.hasOwnProperty("bodyType"))
+            object.bodyType = message.bodyType;
+        if (message.body != null && message.hasOwnProperty("body"))
+            object.body = options.bytes === String ? $util.base64.encode(message.body, 0, message.body.length) : options.bytes === Array ? Array.prototype.slice.call(message.body) : message.body;
+        return object;
+    };
+
+    /**
+     * Converts this Request to JSON.
+     * @function toJSON
+     * @memberof Request
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    Request.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for Request
+     * @function getTypeUrl
+     * @memberof Request
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    Request.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/Request";
+    };
+
+    return Request;
+})();
+
+$root.Response = (function() {
+
+    /**
+     * Properties of a Response.
+     * @exports IResponse
+     * @interface IResponse
+     * @property {string|null} [id] Response id
+     * @property {string|null} [requestId] Response requestId
+     * @property {string|null} [code] Response code
+     * @property {string|null} [error] Response error
+     * @property {string|null} [bodyType] Response bodyType
+     * @property {Uint8Array|null} [body] Response body
+     */
+
+    /**
+     * Constructs a new Response.
+     * @exports Response
+     * @classdesc Represents a Response.
+     * @implements IResponse
+     * @constructor
+     * @param {IResponse=} [properties] Properties to set
+     */
+    function Response(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * Response id.
+     * @member {string} id
+     * @memberof Response
+     * @instance
+     */
+    Response.prototype.id = "";
+
+    /**
+     * Response requestId.
+     * @member {string} requestId
+     * @memberof Response
+     * @instance
+     */
+    Response.prototype.requestId = "";
+
+    /**
+     * Response code.
+     * @member {string|null|undefined} code
+     * @memberof Response
+     * @instance
+     */
+    Response.prototype.code = null;
+
+    /**
+     * Response error.
+     * @member {string|null|undefined} error
+     * @memberof Response
+     * @instance
+     */
+    Response.prototype.error = null;
+
+    /**
+     * Response bodyType.
+     * @member {string} bodyType
+     * @memberof Response
+     * @instance
+     */
+    Response.prototype.bodyType = "";
+
+    /**
+     * Response body.
+     * @member {Uint8Array} body
+     * @memberof Response
+     * @instance
+     */
+    Response.prototype.body = $util.newBuffer([]);
+
+    // OneOf field names bound to virtual getters and setters
+    var $oneOfFields;
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(Response.prototype, "_code", {
+        get: $util.oneOfGetter($oneOfFields = ["code"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(Response.prototype, "_error", {
+        get: $util.oneOfGetter($oneOfFields = ["error"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    /**
+     * Creates a new Response instance using the specified properties.
+     * @function create
+     * @memberof Response
+     * @static
+     * @param {IResponse=} [properties] Properties to set
+     * @returns {Response} Response instance
+     */
+    Response.create = function create(properties) {
+        return new Response(properties);
+    };
+
+    /**
+     * Encodes the specified Response message. Does not implicitly {@link Response.verify|verify} messages.
+     * @function encode
+     * @memberof Response
+     * @static
+     * @param {IResponse} message Response message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    Response.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.id != null && Object.hasOwnProperty.call(message, "id"))
+            writer.uint32(/* id 1, wireType 2 =*/10).string(message.id);
+        if (message.requestId != null && Object.hasOwnProperty.call(message, "requestId"))
+            writer.uint32(/* id 2, wireType 2 =*/18).string(message.requestId);
+        if (message.code != null && Object.hasOwnProperty.call(message, "code"))
+            writer.uint32(/* id 3, wireType 2 =*/26).string(message.code);
+        if (message.error != null && Object.hasOwnProperty.call(message, "error"))
+            writer.uint32(/* id 4, wireType 2 =*/34).string(message.error);
+        if (message.bodyType != null && Object.hasOwnProperty.call(message, "bodyType"))
+            writer.uint32(/* id 5, wireType 2 =*/42).string(message.bodyType);
+        if (message.body != null && Object.hasOwnProperty.call(message, "body"))
+            writer.uint32(/* id 6, wireType 2 =*/50).bytes(message.body);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified Response message, length delimited. Does not implicitly {@link Response.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof Response
+     * @static
+     * @param {IResponse} message Response message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    Response.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a Response message from the specified reader or buffer.
+     * @function decode
+     * @memberof Response
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {Response} Response
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    Response.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.Response();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.id = reader.string();
+                    break;
+                }
+            case 2: {
+                    message.requestId = reader.string();
+                    break;
+                }
+            case 3: {
+                    message.code = reader.string();
+                    break;
+                }
+            case 4: {
+                    message.error = reader.string();
+                    break;
+                }
+            case 5: {
+                    message.bodyType = reader.string();
+                    break;
+                }
+            case 6: {
+                    message.body = reader.bytes();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a Response message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof Response
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {Response} Response
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    Response.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a Response message.
+     * @function verify
+     * @memberof Response
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    Response.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        var properties = {};
+        if (message.id != null && message.hasOwnProperty("id"))
+            if (!$util.isString(message.id))
+                return "id: string expected";
+        if (message.requestId != null && message.hasOwnProperty("requestId"))
+            if (!$util.isString(message.requestId))
+                return "requestId: string expected";
+        if (message.code != null && message.hasOwnProperty("code")) {
+            properties._code = 1;
+            if (!$util.isString(message.code))
+                return "code: string expected";
+        }
+        if (message.error != null && message.hasOwnProperty("error")) {
+            properties._error = 1;
+            if (!$util.isString(message.error))
+                return "error: string expected";
+        }
+        if (message.bodyType != null && message.hasOwnProperty("bodyType"))
+            if (!$util.isString(message.bodyType))
+                return "bodyType: string expected";
+        if (message.body != null && message.hasOwnProperty("body"))
+            if (!(message.body && typeof message.body.length === "number" || $util.isString(message.body)))
+                return "body: buffer expected";
+        return null;
+    };
+
+    /**
+     * Creates a Response message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof Response
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {Response} Response
+     */
+    Response.fromObject = function fromObject(object) {
+        if (object instanceof $root.Response)
+            return object;
+        var message = new $root.Response();
+        if (object.id != null)
+            message.id = String(object.id);
+        if (object.requestId != null)
+            message.requestId = String(object.requestId);
+        if (object.code != null)
+            message.code = String(object.code);
+        if (object.error != null)
+            message.error = String(object.error);
+        if (object.bodyType != null)
+            message.bodyType = String(object.bodyType);
+        if (object.body != null)
+            if (typeof object.body === "string")
+                $util.base64.decode(object.body, message.body = $util.newBuffer($util.base64.length(object.body)), 0);
+            else if (object.body.length >= 0)
+                message.body = object.body;
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a Response message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof Response
+     * @static
+     * @param {Response} message Response
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    Response.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults) {
+            object.id = "";
+            object.requestId = "";
+            object.bodyType = "";
+            if (options.bytes === String)
+                object.body = "";
+            else {
+                object.body = [];
+                if (options.bytes !== Array)
+                    object.body = $util.newBuffer(object.body);
+            }
+        }
+        if (message.id != null && message.hasOwnProperty("id"))
+            object.id = message.id;
+        if (message.requestId != null && message.hasOwnProperty("requestId"))
+            object.requestId = message.requestId;
+        if (message.code != null && message.hasOwnProperty("code")) {
+            object.code = message.code;
+            if (options.oneofs)
+                object._code = "code";
+        }
+        if (message.error != null && message.hasOwnProperty("error")) {
+            object.error = message.error;
+            if (options.oneofs)
+                object._error = "error";
+        }
+        if (message.bodyType != null && message.hasOwnProperty("bodyType"))
+            object.bodyType = message.bodyType;
+        if (message.body != null && message.hasOwnProperty("body"))
+            object.body = options.bytes === String ? $util.base64.encode(message.body, 0, message.body.length) : options.bytes === Array ? Array.prototype.slice.call(message.body) : message.body;
+        return object;
+    };
+
+    /**
+     * Converts this Response to JSON.
+     * @function toJSON
+     * @memberof Response
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    Response.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for Response
+     * @function getTypeUrl
+     * @memberof Response
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    Response.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/Response";
+    };
+
+    return Response;
+})();
+
+$root.CircleArea = (function() {
+
+    /**
+     * Properties of a CircleArea.
+     * @exports ICircleArea
+     * @interface ICircleArea
+     * @property {string|null} [center] CircleArea center
+     * @property {number|null} [radiusInMeters] CircleArea radiusInMeters
+     */
+
+    /**
+     * Constructs a new CircleArea.
+     * @exports CircleArea
+     * @classdesc Represents a CircleArea.
+     * @implements ICircleArea
+     * @constructor
+     * @param {ICircleArea=} [properties] Properties to set
+     */
+    function CircleArea(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * CircleArea center.
+     * @member {string} center
+     * @memberof CircleArea
+     * @instance
+     */
+    CircleArea.prototype.center = "";
+
+    /**
+     * CircleArea radiusInMeters.
+     * @member {number} radiusInMeters
+     * @memberof CircleArea
+     * @instance
+     */
+    CircleArea.prototype.radiusInMeters = 0;
+
+    /**
+     * Creates a new CircleArea instance using the specified properties.
+     * @function create
+     * @memberof CircleArea
+     * @static
+     * @param {ICircleArea=} [properties] Properties to set
+     * @returns {CircleArea} CircleArea instance
+     */
+    CircleArea.create = function create(properties) {
+        return new CircleArea(properties);
+    };
+
+    /**
+     * Encodes the specified CircleArea message. Does not implicitly {@link CircleArea.verify|verify} messages.
+     * @function encode
+     * @memberof CircleArea
+     * @static
+     * @param {ICircleArea} message CircleArea message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    CircleArea.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.center != null && Object.hasOwnProperty.call(message, "center"))
+            writer.uint32(/* id 1, wireType 2 =*/10).string(message.center);
+        if (message.radiusInMeters != null && Object.hasOwnProperty.call(message, "radiusInMeters"))
+            writer.uint32(/* id 2, wireType 1 =*/17).double(message.radiusInMeters);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified CircleArea message, length delimited. Does not implicitly {@link CircleArea.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof CircleArea
+     * @static
+     * @param {ICircleArea} message CircleArea message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    CircleArea.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a CircleArea message from the specified reader or buffer.
+     * @function decode
+     * @memberof CircleArea
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {CircleArea} CircleArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    CircleArea.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.CircleArea();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.center = reader.string();
+                    break;
+                }
+            case 2: {
+                    message.radiusInMeters = reader.double();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a CircleArea message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof CircleArea
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {CircleArea} CircleArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    CircleArea.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a CircleArea message.
+     * @function verify
+     * @memberof CircleArea
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    CircleArea.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        if (message.center != null && message.hasOwnProperty("center"))
+            if (!$util.isString(message.center))
+                return "center: string expected";
+        if (message.radiusInMeters != null && message.hasOwnProperty("radiusInMeters"))
+            if (typeof message.radiusInMeters !== "number")
+                return "radiusInMeters: number expected";
+        return null;
+    };
+
+    /**
+     * Creates a CircleArea message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof CircleArea
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {CircleArea} CircleArea
+     */
+    CircleArea.fromObject = function fromObject(object) {
+        if (object instanceof $root.CircleArea)
+            return object;
+        var message = new $root.CircleArea();
+        if (object.center != null)
+            message.center = String(object.center);
+        if (object.radiusInMeters != null)
+            message.radiusInMeters = Number(object.radiusInMeters);
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a CircleArea message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof CircleArea
+     * @static
+     * @param {CircleArea} message CircleArea
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    CircleArea.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults) {
+            object.center = "";
+            object.radiusInMeters = 0;
+        }
+        if (message.center != null && message.hasOwnProperty("center"))
+            object.center = message.center;
+        if (message.radiusInMeters != null && message.hasOwnProperty("radiusInMeters"))
+            object.radiusInMeters = options.json && !isFinite(message.radiusInMeters) ? String(message.radiusInMeters) : message.radiusInMeters;
+        return object;
+    };
+
+    /**
+     * Converts this CircleArea to JSON.
+     * @function toJSON
+     * @memberof CircleArea
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    CircleArea.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for CircleArea
+     * @function getTypeUrl
+     * @memberof CircleArea
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    CircleArea.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/CircleArea";
+    };
+
+    return CircleArea;
+})();
+
+$root.CorridorArea = (function() {
+
+    /**
+     * Properties of a CorridorArea.
+     * @exports ICorridorArea
+     * @interface ICorridorArea
+     * @property {string|null} [line] CorridorArea line
+     * @property {number|null} [bufferInMeters] CorridorArea bufferInMeters
+     */
+
+    /**
+     * Constructs a new CorridorArea.
+     * @exports CorridorArea
+     * @classdesc Represents a CorridorArea.
+     * @implements ICorridorArea
+     * @constructor
+     * @param {ICorridorArea=} [properties] Properties to set
+     */
+    function CorridorArea(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * CorridorArea line.
+     * @member {string} line
+     * @memberof CorridorArea
+     * @instance
+     */
+    CorridorArea.prototype.line = "";
+
+    /**
+     * CorridorArea bufferInMeters.
+     * @member {number} bufferInMeters
+     * @memberof CorridorArea
+     * @instance
+     */
+    CorridorArea.prototype.bufferInMeters = 0;
+
+    /**
+     * Creates a new CorridorArea instance using the specified properties.
+     * @function create
+     * @memberof CorridorArea
+     * @static
+     * @param {ICorridorArea=} [properties] Properties to set
+     * @returns {CorridorArea} CorridorArea instance
+     */
+    CorridorArea.create = function create(properties) {
+        return new CorridorArea(properties);
+    };
+
+    /**
+     * Encodes the specified CorridorArea message. Does not implicitly {@link CorridorArea.verify|verify} messages.
+     * @function encode
+     * @memberof CorridorArea
+     * @static
+     * @param {ICorridorArea} message CorridorArea message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    CorridorArea.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.line != null && Object.hasOwnProperty.call(message, "line"))
+            writer.uint32(/* id 1, wireType 2 =*/10).string(message.line);
+        if (message.bufferInMeters != null && Object.hasOwnProperty.call(message, "bufferInMeters"))
+            writer.uint32(/* id 2, wireType 1 =*/17).double(message.bufferInMeters);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified CorridorArea message, length delimited. Does not implicitly {@link CorridorArea.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof CorridorArea
+     * @static
+     * @param {ICorridorArea} message CorridorArea message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    CorridorArea.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a CorridorArea message from the specified reader or buffer.
+     * @function decode
+     * @memberof CorridorArea
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {CorridorArea} CorridorArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    CorridorArea.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.CorridorArea();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.line = reader.string();
+                    break;
+                }
+            case 2: {
+                    message.bufferInMeters = reader.double();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a CorridorArea message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof CorridorArea
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {CorridorArea} CorridorArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    CorridorArea.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a CorridorArea message.
+     * @function verify
+     * @memberof CorridorArea
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    CorridorArea.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        if (message.line != null && message.hasOwnProperty("line"))
+            if (!$util.isString(message.line))
+                return "line: string expected";
+        if (message.bufferInMeters != null && message.hasOwnProperty("bufferInMeters"))
+            if (typeof message.bufferInMeters !== "number")
+                return "bufferInMeters: number expected";
+        return null;
+    };
+
+    /**
+     * Creates a CorridorArea message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof CorridorArea
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {CorridorArea} CorridorArea
+     */
+    CorridorArea.fromObject = function fromObject(object) {
+        if (object instanceof $root.CorridorArea)
+            return object;
+        var message = new $root.CorridorArea();
+        if (object.line != null)
+            message.line = String(object.line);
+        if (object.bufferInMeters != null)
+            message.bufferInMeters = Number(object.bufferInMeters);
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a CorridorArea message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof CorridorArea
+     * @static
+     * @param {CorridorArea} message CorridorArea
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    CorridorArea.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults) {
+            object.line = "";
+            object.bufferInMeters = 0;
+        }
+        if (message.line != null && message.hasOwnProperty("line"))
+            object.line = message.line;
+        if (message.bufferInMeters != null && message.hasOwnProperty("bufferInMeters"))
+            object.bufferInMeters = options.json && !isFinite(message.bufferInMeters) ? String(message.bufferInMeters) : message.bufferInMeters;
+        return object;
+    };
+
+    /**
+     * Converts this CorridorArea to JSON.
+     * @function toJSON
+     * @memberof CorridorArea
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    CorridorArea.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for CorridorArea
+     * @function getTypeUrl
+     * @memberof CorridorArea
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    CorridorArea.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/CorridorArea";
+    };
+
+    return CorridorArea;
+})();
+
+$root.VehicleQueryRequest = (function() {
+
+    /**
+     * Properties of a VehicleQueryRequest.
+     * @exports IVehicleQueryRequest
+     * @interface IVehicleQueryRequest
+     * @property {string|null} [id] VehicleQueryRequest id
+     * @property {string|null} [fromDate] VehicleQueryRequest fromDate
+     * @property {string|null} [toDate] VehicleQueryRequest toDate
+     * @property {string|null} [geometry] VehicleQueryRequest geometry
+     * @property {ICircleArea|null} [circle] VehicleQueryRequest circle
+     * @property {ICorridorArea|null} [corridor] VehicleQueryRequest corridor
+     * @property {Array.<string>|null} [vehicleTypes] VehicleQueryRequest vehicleTypes
+     * @property {Array.<string>|null} [vehicleIds] VehicleQueryRequest vehicleIds
+     * @property {number|null} [minSpeed] VehicleQueryRequest minSpeed
+     * @property {number|null} [maxSpeed] VehicleQueryRequest maxSpeed
+     * @property {Array.<string>|null} [directions] VehicleQueryRequest directions
+     * @property {number|Long|null} [limit] VehicleQueryRequest limit
+     * @property {number|Long|null} [timeout] VehicleQueryRequest timeout
+     * @property {string|null} [ttl] VehicleQueryRequest ttl
+     * @property {boolean|null} [parallelize] VehicleQueryRequest parallelize
+     * @property {boolean|null} [useChunking] VehicleQueryRequest useChunking
+     * @property {string|null} [orderBy] VehicleQueryRequest orderBy
+     * @property {string|null} [cursor] VehicleQueryRequest cursor
+     * @property {boolean|null} [deduplicate] VehicleQueryRequest deduplicate
+     * @property {number|null} [sampleIntervalInSecs] VehicleQueryRequest sampleIntervalInSecs
+     * @property {string|null} [sampleMethod] VehicleQueryRequest sampleMethod
+     * @property {string|null} [format] VehicleQueryRequest format
+     */
+
+    /**
+     * Constructs a new VehicleQueryRequest.
+     * @exports VehicleQueryRequest
+     * @classdesc Represents a VehicleQueryRequest.
+     * @implements IVehicleQueryRequest
+     * @constructor
+     * @param {IVehicleQueryRequest=} [properties] Properties to set
+     */
+    function VehicleQueryRequest(properties) {
+        this.vehicleTypes = [];
+        this.vehicleIds = [];
+        this.directions = [];
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * VehicleQueryRequest id.
+     * @member {string} id
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.id = "";
+
+    /**
+     * VehicleQueryRequest fromDate.
+     * @member {string} fromDate
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.fromDate = "";
+
+    /**
+     * VehicleQueryRequest toDate.
+     * @member {string} toDate
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.toDate = "";
+
+    /**
+     * VehicleQueryRequest geometry.
+     * @member {string|null|undefined} geometry
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.geometry = null;
+
+    /**
+     * VehicleQueryRequest circle.
+     * @member {ICircleArea|null|undefined} circle
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.circle = null;
+
+    /**
+     * VehicleQueryRequest corridor.
+     * @member {ICorridorArea|null|undefined} corridor
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.corridor = null;
+
+    /**
+     * VehicleQueryRequest vehicleTypes.
+     * @member {Array.<string>} vehicleTypes
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.vehicleTypes = $util.emptyArray;
+
+    /**
+     * VehicleQueryRequest vehicleIds.
+     * @member {Array.<string>} vehicleIds
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.vehicleIds = $util.emptyArray;
+
+    /**
+     * VehicleQueryRequest minSpeed.
+     * @member {number|null|undefined} minSpeed
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.minSpeed = null;
+
+    /**
+     * VehicleQueryRequest maxSpeed.
+     * @member {number|null|undefined} maxSpeed
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.maxSpeed = null;
+
+    /**
+     * VehicleQueryRequest directions.
+     * @member {Array.<string>} directions
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.directions = $util.emptyArray;
+
+    /**
+     * VehicleQueryRequest limit.
+     * @member {number|Long|null|undefined} limit
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.limit = null;
+
+    /**
+     * VehicleQueryRequest timeout.
+     * @member {number|Long|null|undefined} timeout
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.timeout = null;
+
+    /**
+     * VehicleQueryRequest ttl.
+     * @member {string|null|undefined} ttl
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.ttl = null;
+
+    /**
+     * VehicleQueryRequest parallelize.
+     * @member {boolean|null|undefined} parallelize
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.parallelize = null;
+
+    /**
+     * VehicleQueryRequest useChunking.
+     * @member {boolean|null|undefined} useChunking
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.useChunking = null;
+
+    /**
+     * VehicleQueryRequest orderBy.
+     * @member {string|null|undefined} orderBy
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.orderBy = null;
+
+    /**
+     * VehicleQueryRequest cursor.
+     * @member {string|null|undefined} cursor
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.cursor = null;
+
+    /**
+     * VehicleQueryRequest deduplicate.
+     * @member {boolean|null|undefined} deduplicate
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.deduplicate = null;
+
+    /**
+     * VehicleQueryRequest sampleIntervalInSecs.
+     * @member {number|null|undefined} sampleIntervalInSecs
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.sampleIntervalInSecs = null;
+
+    /**
+     * VehicleQueryRequest sampleMethod.
+     * @member {string|null|undefined} sampleMethod
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.sampleMethod = null;
+
+    /**
+     * VehicleQueryRequest format.
+     * @member {string|null|undefined} format
+     * @memberof VehicleQueryRequest
+     * @instance
+     */
+    VehicleQueryRequest.prototype.format = null;
+
+    // OneOf field names bound to virtual getters and setters
+    var $oneOfFields;
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_geometry", {
+        get: $util.oneOfGetter($oneOfFields = ["geometry"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_minSpeed", {
+        get: $util.oneOfGetter($oneOfFields = ["minSpeed"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_maxSpeed", {
+        get: $util.oneOfGetter($oneOfFields = ["maxSpeed"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_limit", {
+        get: $util.oneOfGetter($oneOfFields = ["limit"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_timeout", {
+        get: $util.oneOfGetter($oneOfFields = ["timeout"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_ttl", {
+        get: $util.oneOfGetter($oneOfFields = ["ttl"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_parallelize", {
+        get: $util.oneOfGetter($oneOfFields = ["parallelize"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_useChunking", {
+        get: $util.oneOfGetter($oneOfFields = ["useChunking"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_orderBy", {
+        get: $util.oneOfGetter($oneOfFields = ["orderBy"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_cursor", {
+        get: $util.oneOfGetter($oneOfFields = ["cursor"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_deduplicate", {
+        get: $util.oneOfGetter($oneOfFields = ["deduplicate"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_sampleIntervalInSecs", {
+        get: $util.oneOfGetter($oneOfFields = ["sampleIntervalInSecs"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_sampleMethod", {
+        get: $util.oneOfGetter($oneOfFields = ["sampleMethod"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryRequest.prototype, "_format", {
+        get: $util.oneOfGetter($oneOfFields = ["format"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    /**
+     * Creates a new VehicleQueryRequest instance using the specified properties.
+     * @function create
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {IVehicleQueryRequest=} [properties] Properties to set
+     * @returns {VehicleQueryRequest} VehicleQueryRequest instance
+     */
+    VehicleQueryRequest.create = function create(properties) {
+        return new VehicleQueryRequest(properties);
+    };
+
+    /**
+     * Encodes the specified VehicleQueryRequest message. Does not implicitly {@link VehicleQueryRequest.verify|verify} messages.
+     * @function encode
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {IVehicleQueryRequest} message VehicleQueryRequest message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryRequest.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.id != null && Object.hasOwnProperty.call(message, "id"))
+            writer.uint32(/* id 1, wireType 2 =*/10).string(message.id);
+        if (message.fromDate != null && Object.hasOwnProperty.call(message, "fromDate"))
+            writer.uint32(/* id 2, wireType 2 =*/18).string(message.fromDate);
+        if (message.toDate != null && Object.hasOwnProperty.call(message, "toDate"))
+            writer.uint32(/* id 3, wireType 2 =*/26).string(message.toDate);
+        if (message.geometry != null && Object.hasOwnProperty.call(message, "geometry"))
+            writer.uint32(/* id 4, wireType 2 =*/34).string(message.geometry);
+        if (message.circle != null && Object.hasOwnProperty.call(message, "circle"))
+            $root.CircleArea.encode(message.circle, writer.uint32(/* id 5, wireType 2 =*/42).fork()).ldelim();
+        if (message.corridor != null && Object.hasOwnProperty.call(message, "corridor"))
+            $root.CorridorArea.encode(message.corridor, writer.uint32(/* id 6, wireType 2 =*/50).fork()).ldelim();
+        if (message.vehicleTypes != null && message.vehicleTypes.length)
+            for (var i = 0; i < message.vehicleTypes.length; ++i)
+                writer.uint32(/* id 7, wireType 2 =*/58).string(message.vehicleTypes[i]);
+        if (message.vehicleIds != null && message.vehicleIds.length)
+            for (var i = 0; i < message.vehicleIds.length; ++i)
+                writer.uint32(/* id 8, wireType 2 =*/66).string(message.vehicleIds[i]);
+        if (message.minSpeed != null && Object.hasOwnProperty.call(message, "minSpeed"))
+            writer.uint32(/* id 9, wireType 1 =*/73).double(message.minSpeed);
+        if (message.maxSpeed != null && Object.hasOwnProperty.call(message, "maxSpeed"))
+            writer.uint32(/* id 10, wireType 1 =*/81).double(message.maxSpeed);
+        if (message.directions != null && message.directions.length)
+            for (var i = 0; i < message.directions.length; ++i)
+                writer.uint32(/* id 11, wireType 2 =*/90).string(message.directions[i]);
+        if (message.limit != null && Object.hasOwnProperty.call(message, "limit"))
+            writer.uint32(/* id 12, wireType 0 =*/96).uint64(message.limit);
+        if (message.timeout != null && Object.hasOwnProperty.call(message, "timeout"))
+            writer.uint32(/* id 13, wireType 0 =*/104).uint64(message.timeout);
+        if (message.ttl != null && Object.hasOwnProperty.call(message, "ttl"))
+            writer.uint32(/* id 14, wireType 2 =*/114).string(message.ttl);
+        if (message.parallelize != null && Object.hasOwnProperty.call(message, "parallelize"))
+            writer.uint32(/* id 15, wireType 0 =*/120).bool(message.parallelize);
+        if (message.useChunking != null && Object.hasOwnProperty.call(message, "useChunking"))
+            writer.uint32(/* id 16, wireType 0 =*/128).bool(message.useChunking);
+        if (message.orderBy != null && Object.hasOwnProperty.call(message, "orderBy"))
+            writer.uint32(/* id 17, wireType 2 =*/138).string(message.orderBy);
+        if (message.cursor != null && Object.hasOwnProperty.call(message, "cursor"))
+            writer.uint32(/* id 18, wireType 2 =*/146).string(message.cursor);
+        if (message.deduplicate != null && Object.hasOwnProperty.call(message, "deduplicate"))
+            writer.uint32(/* id 19, wireType 0 =*/152).bool(message.deduplicate);
+        if (message.sampleIntervalInSecs != null && Object.hasOwnProperty.call(message, "sampleIntervalInSecs"))
+            writer.uint32(/* id 20, wireType 0 =*/160).uint32(message.sampleIntervalInSecs);
+        if (message.sampleMethod != null && Object.hasOwnProperty.call(message, "sampleMethod"))
+            writer.uint32(/* id 21, wireType 2 =*/170).string(message.sampleMethod);
+        if (message.format != null && Object.hasOwnProperty.call(message, "format"))
+            writer.uint32(/* id 22, wireType 2 =*/178).string(message.format);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified VehicleQueryRequest message, length delimited. Does not implicitly {@link VehicleQueryRequest.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {IVehicleQueryRequest} message VehicleQueryRequest message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryRequest.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a VehicleQueryRequest message from the specified reader or buffer.
+     * @function decode
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {VehicleQueryRequest} VehicleQueryRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryRequest.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.VehicleQueryRequest();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.id = reader.string();
+                    break;
+                }
+            case 2: {
+                    message.fromDate = reader.string();
+                    break;
+                }
+            case 3: {
+                    message.toDate = reader.string();
+                    break;
+                }
+            case 4: {
+                    message.geometry = reader.string();
+                    break;
+                }
+            case 5: {
+                    message.circle = $root.CircleArea.decode(reader, reader.uint32());
+                    break;
+                }
+            case 6: {
+                    message.corridor = $root.CorridorArea.decode(reader, reader.uint32());
+                    break;
+                }
+            case 7: {
+                    if (!(message.vehicleTypes && message.vehicleTypes.length))
+                        message.vehicleTypes = [];
+                    message.vehicleTypes.push(reader.string());
+                    break;
+                }
+            case 8: {
+                    if (!(message.vehicleIds && message.vehicleIds.length))
+                        message.vehicleIds = [];
+                    message.vehicleIds.push(reader.string());
+                    break;
+                }
+            case 9: {
+                    message.minSpeed = reader.double();
+                    break;
+                }
+            case 10: {
+                    message.maxSpeed = reader.double();
+                    break;
+                }
+            case 11: {
+                    if (!(message.directions && message.directions.length))
+                        message.directions = [];
+                    message.directions.push(reader.string());
+                    break;
+                }
+            case 12: {
+                    message.limit = reader.uint64();
+                    break;
+                }
+            case 13: {
+                    message.timeout = reader.uint64();
+                    break;
+                }
+            case 14: {
+                    message.ttl = reader.string();
+                    break;
+                }
+            case 15: {
+                    message.parallelize = reader.bool();
+                    break;
+                }
+            case 16: {
+                    message.useChunking = reader.bool();
+                    break;
+                }
+            case 17: {
+                    message.orderBy = reader.string();
+                    break;
+                }
+            case 18: {
+                    message.cursor = reader.string();
+                    break;
+                }
+            case 19: {
+                    message.deduplicate = reader.bool();
+                    break;
+                }
+            case 20: {
+                    message.sampleIntervalInSecs = reader.uint32();
+                    break;
+                }
+            case 21: {
+                    message.sampleMethod = reader.string();
+                    break;
+                }
+            case 22: {
+                    message.format = reader.string();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a VehicleQueryRequest message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {VehicleQueryRequest} VehicleQueryRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryRequest.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a VehicleQueryRequest message.
+     * @function verify
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    VehicleQueryRequest.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        var properties = {};
+        if (message.id != null && message.hasOwnProperty("id"))
+            if (!$util.isString(message.id))
+                return "id: string expected";
+        if (message.fromDate != null && message.hasOwnProperty("fromDate"))
+            if (!$util.isString(message.fromDate))
+                return "fromDate: string expected";
+        if (message.toDate != null && message.hasOwnProperty("toDate"))
+            if (!$util.isString(message.toDate))
+                return "toDate: string expected";
+        if (message.geometry != null && message.hasOwnProperty("geometry")) {
+            properties._geometry = 1;
+            if (!$util.isString(message.geometry))
+                return "geometry: string expected";
+        }
+        if (message.circle != null && message.hasOwnProperty("circle")) {
+            var error = $root.CircleArea.verify(message.circle);
+            if (error)
+                return "circle." + error;
+        }
+        if (message.corridor != null && message.hasOwnProperty("corridor")) {
+            var error = $root.CorridorArea.verify(message.corridor);
+            if (error)
+                return "corridor." + error;
+        }
+        if (message.vehicleTypes != null && message.hasOwnProperty("vehicleTypes")) {
+            if (!Array.isArray(message.vehicleTypes))
+                return "vehicleTypes: array expected";
+            for (var i = 0; i < message.vehicleTypes.length; ++i)
+                if (!$util.isString(message.vehicleTypes[i]))
+                    return "vehicleTypes: string[] expected";
+        }
+        if (message.vehicleIds != null && message.hasOwnProperty("vehicleIds")) {
+            if (!Array.isArray(message.vehicleIds))
+                return "vehicleIds: array expected";
+            for (var i = 0; i < message.vehicleIds.length; ++i)
+                if (!$util.isString(message.vehicleIds[i]))
+                    return "vehicleIds: string[] expected";
+        }
+        if (message.minSpeed != null && message.hasOwnProperty("minSpeed")) {
+            properties._minSpeed = 1;
+            if (typeof message.minSpeed !== "number")
+                return "minSpeed: number expected";
+        }
+        if (message.maxSpeed != null && message.hasOwnProperty("maxSpeed")) {
+            properties._maxSpeed = 1;
+            if (typeof message.maxSpeed !== "number")
+                return "maxSpeed: number expected";
+        }
+        if (message.directions != null && message.hasOwnProperty("directions")) {
+            if (!Array.isArray(message.directions))
+                return "directions: array expected";
+            for (var i = 0; i < message.directions.length; ++i)
+                if (!$util.isString(message.directions[i]))
+                    return "directions: string[] expected";
+        }
+        if (message.limit != null && message.hasOwnProperty("limit")) {
+            properties._limit = 1;
+            if (!$util.isInteger(message.limit) && !(message.limit && $util.isInteger(message.limit.low) && $util.isInteger(message.limit.high)))
+                return "limit: integer|Long expected";
+        }
+        if (message.timeout != null && message.hasOwnProperty("timeout")) {
+            properties._timeout = 1;
+            if (!$util.isInteger(message.timeout) && !(message.timeout && $util.isInteger(message.timeout.low) && $util.isInteger(message.timeout.high)))
+                return "timeout: integer|Long expected";
+        }
+        if (message.ttl != null && message.hasOwnProperty("ttl")) {
+            properties._ttl = 1;
+            if (!$util.isString(message.ttl))
+                return "ttl: string expected";
+        }
+        if (message.parallelize != null && message.hasOwnProperty("parallelize")) {
+            properties._parallelize = 1;
+            if (typeof message.parallelize !== "boolean")
+                return "parallelize: boolean expected";
+        }
+        if (message.useChunking != null && message.hasOwnProperty("useChunking")) {
+            properties._useChunking = 1;
+            if (typeof message.useChunking !== "boolean")
+                return "useChunking: boolean expected";
+        }
+        if (message.orderBy != null && message.hasOwnProperty("orderBy")) {
+            properties._orderBy = 1;
+            if (!$util.isString(message.orderBy))
+                return "orderBy: string expected";
+        }
+        if (message.cursor != null && message.hasOwnProperty("cursor")) {
+            properties._cursor = 1;
+            if (!$util.isString(message.cursor))
+                return "cursor: string expected";
+        }
+        if (message.deduplicate != null && message.hasOwnProperty("deduplicate")) {
+            properties._deduplicate = 1;
+            if (typeof message.deduplicate !== "boolean")
+                return "deduplicate: boolean expected";
+        }
+        if (message.sampleIntervalInSecs != null && message.hasOwnProperty("sampleIntervalInSecs")) {
+            properties._sampleIntervalInSecs = 1;
+            if (!$util.isInteger(message.sampleIntervalInSecs))
+                return "sampleIntervalInSecs: integer expected";
+        }
+        if (message.sampleMethod != null && message.hasOwnProperty("sampleMethod")) {
+            properties._sampleMethod = 1;
+            if (!$util.isString(message.sampleMethod))
+                return "sampleMethod: string expected";
+        }
+        if (message.format != null && message.hasOwnProperty("format")) {
+            properties._format = 1;
+            if (!$util.isString(message.format))
+                return "format: string expected";
+        }
+        return null;
+    };
+
+    /**
+     * Creates a VehicleQueryRequest message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {VehicleQueryRequest} VehicleQueryRequest
+     */
+    VehicleQueryRequest.fromObject = function fromObject(object) {
+        if (object instanceof $root.VehicleQueryRequest)
+            return object;
+        var message = new $root.VehicleQueryRequest();
+        if (object.id != null)
+            message.id = String(object.id);
+        if (object.fromDate != null)
+            message.fromDate = String(object.fromDate);
+        if (object.toDate != null)
+            message.toDate = String(object.toDate);
+        if (object.geometry != null)
+            message.geometry = String(object.geometry);
+        if (object.circle != null) {
+            if (typeof object.circle !== "object")
+                throw TypeError(".VehicleQueryRequest.circle: object expected");
+            message.circle = $root.CircleArea.fromObject(object.circle);
+        }
+        if (object.corridor != null) {
+            if (typeof object.corridor !== "object")
+                throw TypeError(".VehicleQueryRequest.corridor: object expected");
+            message.corridor = $root.CorridorArea.fromObject(object.corridor);
+        }
+        if (object.vehicleTypes) {
+            if (!Array.isArray(object.vehicleTypes))
+                throw TypeError(".VehicleQueryRequest.vehicleTypes: array expected");
+            message.vehicleTypes = [];
+            for (var i = 0; i < object.vehicleTypes.length; ++i)
+                message.vehicleTypes[i] = String(object.vehicleTypes[i]);
+        }
+        if (object.vehicleIds) {
+            if (!Array.isArray(object.vehicleIds))
+                throw TypeError(".VehicleQueryRequest.vehicleIds: array expected");
+            message.vehicleIds = [];
+            for (var i = 0; i < object.vehicleIds.length; ++i)
+                message.vehicleIds[i] = String(object.vehicleIds[i]);
+        }
+        if (object.minSpeed != null)
+            message.minSpeed = Number(object.minSpeed);
+        if (object.maxSpeed != null)
+            message.maxSpeed = Number(object.maxSpeed);
+        if (object.directions) {
+            if (!Array.isArray(object.directions))
+                throw TypeError(".VehicleQueryRequest.directions: array expected");
+            message.directions = [];
+            for (var i = 0; i < object.directions.length; ++i)
+                message.directions[i] = String(object.directions[i]);
+        }
+        if (object.limit != null)
+            if ($util.Long)
+                (message.limit = $util.Long.fromValue(object.limit)).unsigned = true;
+            else if (typeof object.limit === "string")
+                message.limit = parseInt(object.limit, 10);
+            else if (typeof object.limit === "number")
+                message.limit = object.limit;
+            else if (typeof object.limit === "object")
+                message.limit = new $util.LongBits(object.limit.low >>> 0, object.limit.high >>> 0).toNumber(true);
+        if (object.timeout != null)
+            if ($util.Long)
+                (message.timeout = $util.Long.fromValue(object.timeout)).unsigned = true;
+            else if (typeof object.timeout === "string")
+                message.timeout = parseInt(object.timeout, 10);
+            else if (typeof object.timeout === "number")
+                message.timeout = object.timeout;
+            else if (typeof object.timeout === "object")
+                message.timeout = new $util.LongBits(object.timeout.low >>> 0, object.timeout.high >>> 0).toNumber(true);
+        if (object.ttl != null)
+            message.ttl = String(object.ttl);
+        if (object.parallelize != null)
+            message.parallelize = Boolean(object.parallelize);
+        if (object.useChunking != null)
+            message.useChunking = Boolean(object.useChunking);
+        if (object.orderBy != null)
+            message.orderBy = String(object.orderBy);
+        if (object.cursor != null)
+            message.cursor = String(object.cursor);
+        if (object.deduplicate != null)
+            message.deduplicate = Boolean(object.deduplicate);
+        if (object.sampleIntervalInSecs != null)
+            message.sampleIntervalInSecs = object.sampleIntervalInSecs >>> 0;
+        if (object.sampleMethod != null)
+            message.sampleMethod = String(object.sampleMethod);
+        if (object.format != null)
+            message.format = String(object.format);
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a VehicleQueryRequest message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {VehicleQueryRequest} message VehicleQueryRequest
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    VehicleQueryRequest.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.arrays || options.defaults) {
+            object.vehicleTypes = [];
+            object.vehicleIds = [];
+            object.directions = [];
+        }
+        if (options.defaults) {
+            object.id = "";
+            object.fromDate = "";
+            object.toDate = "";
+            object.circle = null;
+            object.corridor = null;
+        }
+        if (message.id != null && message.hasOwnProperty("id"))
+            object.id = message.id;
+        if (message.fromDate != null && message.hasOwnProperty("fromDate"))
+            object.fromDate = message.fromDate;
+        if (message.toDate != null && message.hasOwnProperty("toDate"))
+            object.toDate = message.toDate;
+        if (message.geometry != null && message.hasOwnProperty("geometry")) {
+            object.geometry = message.geometry;
+            if (options.oneofs)
+                object._geometry = "geometry";
+        }
+        if (message.circle != null && message.hasOwnProperty("circle"))
+            object.circle = $root.CircleArea.toObject(message.circle, options);
+        if (message.corridor != null && message.hasOwnProperty("corridor"))
+            object.corridor = $root.CorridorArea.toObject(message.corridor, options);
+        if (message.vehicleTypes && message.vehicleTypes.length) {
+            object.vehicleTypes = [];
+            for (var j = 0; j < message.vehicleTypes.length; ++j)
+                object.vehicleTypes[j] = message.vehicleTypes[j];
+        }
+        if (message.vehicleIds && message.vehicleIds.length) {
+            object.vehicleIds = [];
+            for (var j = 0; j < message.vehicleIds.length; ++j)
+                object.vehicleIds[j] = message.vehicleIds[j];
+        }
+        if (message.minSpeed != null && message.hasOwnProperty("minSpeed")) {
+            object.minSpeed = options.json && !isFinite(message.minSpeed) ? String(message.minSpeed) : message.minSpeed;
+            if (options.oneofs)
+                object._minSpeed = "minSpeed";
+        }
+        if (message.maxSpeed != null && message.hasOwnProperty("maxSpeed")) {
+            object.maxSpeed = options.json && !isFinite(message.maxSpeed) ? String(message.maxSpeed) : message.maxSpeed;
+            if (options.oneofs)
+                object._maxSpeed = "maxSpeed";
+        }
+        if (message.directions && message.directions.length) {
+            object.directions = [];
+            for (var j = 0; j < message.directions.length; ++j)
+                object.directions[j] = message.directions[j];
+        }
+        if (message.limit != null && message.hasOwnProperty("limit")) {
+            if (typeof message.limit === "number")
+                object.limit = options.longs === String ? String(message.limit) : message.limit;
+            else
+                object.limit = options.longs === String ? $util.Long.prototype.toString.call(message.limit) : options.longs === Number ? new $util.LongBits(message.limit.low >>> 0, message.limit.high >>> 0).toNumber(true) : message.limit;
+            if (options.oneofs)
+                object._limit = "limit";
+        }
+        if (message.timeout != null && message.hasOwnProperty("timeout")) {
+            if (typeof message.timeout === "number")
+                object.timeout = options.longs === String ? String(message.timeout) : message.timeout;
+            else
+                object.timeout = options.longs === String ? $util.Long.prototype.toString.call(message.timeout) : options.longs === Number ? new $util.LongBits(message.timeout.low >>> 0, message.timeout.high >>> 0).toNumber(true) : message.timeout;
+            if (options.oneofs)
+                object._timeout = "timeout";
+        }
+        if (message.ttl != null && message.hasOwnProperty("ttl")) {
+            object.ttl = message.ttl;
+            if (options.oneofs)
+                object._ttl = "ttl";
+        }
+        if (message.parallelize != null && message.hasOwnProperty("parallelize")) {
+            object.parallelize = message.parallelize;
+            if (options.oneofs)
+                object._parallelize = "parallelize";
+        }
+        if (message.useChunking != null && message.hasOwnProperty("useChunking")) {
+            object.useChunking = message.useChunking;
+            if (options.oneofs)
+                object._useChunking = "useChunking";
+        }
+        if (message.orderBy != null && message.hasOwnProperty("orderBy")) {
+            object.orderBy = message.orderBy;
+            if (options.oneofs)
+                object._orderBy = "orderBy";
+        }
+        if (message.cursor != null && message.hasOwnProperty("cursor")) {
+            object.cursor = message.cursor;
+            if (options.oneofs)
+                object._cursor = "cursor";
+        }
+        if (message.deduplicate != null && message.hasOwnProperty("deduplicate")) {
+            object.deduplicate = message.deduplicate;
+            if (options.oneofs)
+                object._deduplicate = "deduplicate";
+        }
+        if (message.sampleIntervalInSecs != null && message.hasOwnProperty("sampleIntervalInSecs")) {
+            object.sampleIntervalInSecs = message.sampleIntervalInSecs;
+            if (options.oneofs)
+                object._sampleIntervalInSecs = "sampleIntervalInSecs";
+        }
+        if (message.sampleMethod != null && message.hasOwnProperty("sampleMethod")) {
+            object.sampleMethod = message.sampleMethod;
+            if (options.oneofs)
+                object._sampleMethod = "sampleMethod";
+        }
+        if (message.format != null && message.hasOwnProperty("format")) {
+            object.format = message.format;
+            if (options.oneofs)
+                object._format = "format";
+        }
+        return object;
+    };
+
+    /**
+     * Converts this VehicleQueryRequest to JSON.
+     * @function toJSON
+     * @memberof VehicleQueryRequest
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    VehicleQueryRequest.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for VehicleQueryRequest
+     * @function getTypeUrl
+     * @memberof VehicleQueryRequest
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    VehicleQueryRequest.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/VehicleQueryRequest";
+    };
+
+    return VehicleQueryRequest;
+})();
+
+$root.VehicleQueryResponse = (function() {
+
+    /**
+     * Properties of a VehicleQueryResponse.
+     * @exports IVehicleQueryResponse
+     * @interface IVehicleQueryResponse
+     * @property {number|Long|null} [processedFilesCount] VehicleQueryResponse processedFilesCount
+     * @property {number|Long|null} [processedBytes] VehicleQueryResponse processedBytes
+     * @property {number|Long|null} [processedRecordCount] VehicleQueryResponse processedRecordCount
+     * @property {number|Long|null} [selectedRecordCount] VehicleQueryResponse selectedRecordCount
+     * @property {number|Long|null} [distinctVehicleCount] VehicleQueryResponse distinctVehicleCount
+     * @property {number|Long|null} [elapsedTimeInMS] VehicleQueryResponse elapsedTimeInMS
+     * @property {boolean|null} [timeoutExpired] VehicleQueryResponse timeoutExpired
+     * @property {boolean|null} [limitReached] VehicleQueryResponse limitReached
+     * @property {boolean|null} [fromCache] VehicleQueryResponse fromCache
+     * @property {string|null} [cursor] VehicleQueryResponse cursor
+     * @property {number|Long|null} [duplicateCount] VehicleQueryResponse duplicateCount
+     */
+
+    /**
+     * Constructs a new VehicleQueryResponse.
+     * @exports VehicleQueryResponse
+     * @classdesc Represents a VehicleQueryResponse.
+     * @implements IVehicleQueryResponse
+     * @constructor
+     * @param {IVehicleQueryResponse=} [properties] Properties to set
+     */
+    function VehicleQueryResponse(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * VehicleQueryResponse processedFilesCount.
+     * @member {number|Long} processedFilesCount
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.processedFilesCount = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    /**
+     * VehicleQueryResponse processedBytes.
+     * @member {number|Long} processedBytes
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.processedBytes = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    /**
+     * VehicleQueryResponse processedRecordCount.
+     * @member {number|Long} processedRecordCount
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.processedRecordCount = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    /**
+     * VehicleQueryResponse selectedRecordCount.
+     * @member {number|Long} selectedRecordCount
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.selectedRecordCount = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    /**
+     * VehicleQueryResponse distinctVehicleCount.
+     * @member {number|Long} distinctVehicleCount
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.distinctVehicleCount = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    /**
+     * VehicleQueryResponse elapsedTimeInMS.
+     * @member {number|Long} elapsedTimeInMS
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.elapsedTimeInMS = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    /**
+     * VehicleQueryResponse timeoutExpired.
+     * @member {boolean} timeoutExpired
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.timeoutExpired = false;
+
+    /**
+     * VehicleQueryResponse limitReached.
+     * @member {boolean} limitReached
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.limitReached = false;
+
+    /**
+     * VehicleQueryResponse fromCache.
+     * @member {boolean} fromCache
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.fromCache = false;
+
+    /**
+     * VehicleQueryResponse cursor.
+     * @member {string|null|undefined} cursor
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.cursor = null;
+
+    /**
+     * VehicleQueryResponse duplicateCount.
+     * @member {number|Long} duplicateCount
+     * @memberof VehicleQueryResponse
+     * @instance
+     */
+    VehicleQueryResponse.prototype.duplicateCount = $util.Long ? $util.Long.fromBits(0,0,true) : 0;
+
+    // OneOf field names bound to virtual getters and setters
+    var $oneOfFields;
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryResponse.prototype, "_cursor", {
+        get: $util.oneOfGetter($oneOfFields = ["cursor"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    /**
+     * Creates a new VehicleQueryResponse instance using the specified properties.
+     * @function create
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {IVehicleQueryResponse=} [properties] Properties to set
+     * @returns {VehicleQueryResponse} VehicleQueryResponse instance
+     */
+    VehicleQueryResponse.create = function create(properties) {
+        return new VehicleQueryResponse(properties);
+    };
+
+    /**
+     * Encodes the specified VehicleQueryResponse message. Does not implicitly {@link VehicleQueryResponse.verify|verify} messages.
+     * @function encode
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {IVehicleQueryResponse} message VehicleQueryResponse message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryResponse.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.processedFilesCount != null && Object.hasOwnProperty.call(message, "processedFilesCount"))
+            writer.uint32(/* id 1, wireType 0 =*/8).uint64(message.processedFilesCount);
+        if (message.processedBytes != null && Object.hasOwnProperty.call(message, "processedBytes"))
+            writer.uint32(/* id 2, wireType 0 =*/16).uint64(message.processedBytes);
+        if (message.processedRecordCount != null && Object.hasOwnProperty.call(message, "processedRecordCount"))
+            writer.uint32(/* id 3, wireType 0 =*/24).uint64(message.processedRecordCount);
+        if (message.selectedRecordCount != null && Object.hasOwnProperty.call(message, "selectedRecordCount"))
+            writer.uint32(/* id 4, wireType 0 =*/32).uint64(message.selectedRecordCount);
+        if (message.distinctVehicleCount != null && Object.hasOwnProperty.call(message, "distinctVehicleCount"))
+            writer.uint32(/* id 5, wireType 0 =*/40).uint64(message.distinctVehicleCount);
+        if (message.elapsedTimeInMS != null && Object.hasOwnProperty.call(message, "elapsedTimeInMS"))
+            writer.uint32(/* id 6, wireType 0 =*/48).uint64(message.elapsedTimeInMS);
+        if (message.timeoutExpired != null && Object.hasOwnProperty.call(message, "timeoutExpired"))
+            writer.uint32(/* id 7, wireType 0 =*/56).bool(message.timeoutExpired);
+        if (message.limitReached != null && Object.hasOwnProperty.call(message, "limitReached"))
+            writer.uint32(/* id 8, wireType 0 =*/64).bool(message.limitReached);
+        if (message.fromCache != null && Object.hasOwnProperty.call(message, "fromCache"))
+            writer.uint32(/* id 9, wireType 0 =*/72).bool(message.fromCache);
+        if (message.cursor != null && Object.hasOwnProperty.call(message, "cursor"))
+            writer.uint32(/* id 10, wireType 2 =*/82).string(message.cursor);
+        if (message.duplicateCount != null && Object.hasOwnProperty.call(message, "duplicateCount"))
+            writer.uint32(/* id 11, wireType 0 =*/88).uint64(message.duplicateCount);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified VehicleQueryResponse message, length delimited. Does not implicitly {@link VehicleQueryResponse.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {IVehicleQueryResponse} message VehicleQueryResponse message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryResponse.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a VehicleQueryResponse message from the specified reader or buffer.
+     * @function decode
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {VehicleQueryResponse} VehicleQueryResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryResponse.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.VehicleQueryResponse();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.processedFilesCount = reader.uint64();
+                    break;
+                }
+            case 2: {
+                    message.processedBytes = reader.uint64();
+                    break;
+                }
+            case 3: {
+                    message.processedRecordCount = reader.uint64();
+                    break;
+                }
+            case 4: {
+                    message.selectedRecordCount = reader.uint64();
+                    break;
+                }
+            case 5: {
+                    message.distinctVehicleCount = reader.uint64();
+                    break;
+                }
+            case 6: {
+                    message.elapsedTimeInMS = reader.uint64();
+                    break;
+                }
+            case 7: {
+                    message.timeoutExpired = reader.bool();
+                    break;
+                }
+            case 8: {
+                    message.limitReached = reader.bool();
+                    break;
+                }
+            case 9: {
+                    message.fromCache = reader.bool();
+                    break;
+                }
+            case 10: {
+                    message.cursor = reader.string();
+                    break;
+                }
+            case 11: {
+                    message.duplicateCount = reader.uint64();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a VehicleQueryResponse message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {VehicleQueryResponse} VehicleQueryResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryResponse.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a VehicleQueryResponse message.
+     * @function verify
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    VehicleQueryResponse.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        var properties = {};
+        if (message.processedFilesCount != null && message.hasOwnProperty("processedFilesCount"))
+            if (!$util.isInteger(message.processedFilesCount) && !(message.processedFilesCount && $util.isInteger(message.processedFilesCount.low) && $util.isInteger(message.processedFilesCount.high)))
+                return "processedFilesCount: integer|Long expected";
+        if (message.processedBytes != null && message.hasOwnProperty("processedBytes"))
+            if (!$util.isInteger(message.processedBytes) && !(message.processedBytes && $util.isInteger(message.processedBytes.low) && $util.isInteger(message.processedBytes.high)))
+                return "processedBytes: integer|Long expected";
+        if (message.processedRecordCount != null && message.hasOwnProperty("processedRecordCount"))
+            if (!$util.isInteger(message.processedRecordCount) && !(message.processedRecordCount && $util.isInteger(message.processedRecordCount.low) && $util.isInteger(message.processedRecordCount.high)))
+                return "processedRecordCount: integer|Long expected";
+        if (message.selectedRecordCount != null && message.hasOwnProperty("selectedRecordCount"))
+            if (!$util.isInteger(message.selectedRecordCount) && !(message.selectedRecordCount && $util.isInteger(message.selectedRecordCount.low) && $util.isInteger(message.selectedRecordCount.high)))
+                return "selectedRecordCount: integer|Long expected";
+        if (message.distinctVehicleCount != null && message.hasOwnProperty("distinctVehicleCount"))
+            if (!$util.isInteger(message.distinctVehicleCount) && !(message.distinctVehicleCount && $util.isInteger(message.distinctVehicleCount.low) && $util.isInteger(message.distinctVehicleCount.high)))
+                return "distinctVehicleCount: integer|Long expected";
+        if (message.elapsedTimeInMS != null && message.hasOwnProperty("elapsedTimeInMS"))
+            if (!$util.isInteger(message.elapsedTimeInMS) && !(message.elapsedTimeInMS && $util.isInteger(message.elapsedTimeInMS.low) && $util.isInteger(message.elapsedTimeInMS.high)))
+                return "elapsedTimeInMS: integer|Long expected";
+        if (message.timeoutExpired != null && message.hasOwnProperty("timeoutExpired"))
+            if (typeof message.timeoutExpired !== "boolean")
+                return "timeoutExpired: boolean expected";
+        if (message.limitReached != null && message.hasOwnProperty("limitReached"))
+            if (typeof message.limitReached !== "boolean")
+                return "limitReached: boolean expected";
+        if (message.fromCache != null && message.hasOwnProperty("fromCache"))
+            if (typeof message.fromCache !== "boolean")
+                return "fromCache: boolean expected";
+        if (message.cursor != null && message.hasOwnProperty("cursor")) {
+            properties._cursor = 1;
+            if (!$util.isString(message.cursor))
+                return "cursor: string expected";
+        }
+        if (message.duplicateCount != null && message.hasOwnProperty("duplicateCount"))
+            if (!$util.isInteger(message.duplicateCount) && !(message.duplicateCount && $util.isInteger(message.duplicateCount.low) && $util.isInteger(message.duplicateCount.high)))
+                return "duplicateCount: integer|Long expected";
+        return null;
+    };
+
+    /**
+     * Creates a VehicleQueryResponse message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {VehicleQueryResponse} VehicleQueryResponse
+     */
+    VehicleQueryResponse.fromObject = function fromObject(object) {
+        if (object instanceof $root.VehicleQueryResponse)
+            return object;
+        var message = new $root.VehicleQueryResponse();
+        if (object.processedFilesCount != null)
+            if ($util.Long)
+                (message.processedFilesCount = $util.Long.fromValue(object.processedFilesCount)).unsigned = true;
+            else if (typeof object.processedFilesCount === "string")
+                message.processedFilesCount = parseInt(object.processedFilesCount, 10);
+            else if (typeof object.processedFilesCount === "number")
+                message.processedFilesCount = object.processedFilesCount;
+            else if (typeof object.processedFilesCount === "object")
+                message.processedFilesCount = new $util.LongBits(object.processedFilesCount.low >>> 0, object.processedFilesCount.high >>> 0).toNumber(true);
+        if (object.processedBytes != null)
+            if ($util.Long)
+                (message.processedBytes = $util.Long.fromValue(object.processedBytes)).unsigned = true;
+            else if (typeof object.processedBytes === "string")
+                message.processedBytes = parseInt(object.processedBytes, 10);
+            else if (typeof object.processedBytes === "number")
+                message.processedBytes = object.processedBytes;
+            else if (typeof object.processedBytes === "object")
+                message.processedBytes = new $util.LongBits(object.processedBytes.low >>> 0, object.processedBytes.high >>> 0).toNumber(true);
+        if (object.processedRecordCount != null)
+            if ($util.Long)
+                (message.processedRecordCount = $util.Long.fromValue(object.processedRecordCount)).unsigned = true;
+            else if (typeof object.processedRecordCount === "string")
+                message.processedRecordCount = parseInt(object.processedRecordCount, 10);
+            else if (typeof object.processedRecordCount === "number")
+                message.processedRecordCount = object.processedRecordCount;
+            else if (typeof object.processedRecordCount === "object")
+                message.processedRecordCount = new $util.LongBits(object.processedRecordCount.low >>> 0, object.processedRecordCount.high >>> 0).toNumber(true);
+        if (object.selectedRecordCount != null)
+            if ($util.Long)
+                (message.selectedRecordCount = $util.Long.fromValue(object.selectedRecordCount)).unsigned = true;
+            else if (typeof object.selectedRecordCount === "string")
+                message.selectedRecordCount = parseInt(object.selectedRecordCount, 10);
+            else if (typeof object.selectedRecordCount === "number")
+                message.selectedRecordCount = object.selectedRecordCount;
+            else if (typeof object.selectedRecordCount === "object")
+                message.selectedRecordCount = new $util.LongBits(object.selectedRecordCount.low >>> 0, object.selectedRecordCount.high >>> 0).toNumber(true);
+        if (object.distinctVehicleCount != null)
+            if ($util.Long)
+                (message.distinctVehicleCount = $util.Long.fromValue(object.distinctVehicleCount)).unsigned = true;
+            else if (typeof object.distinctVehicleCount === "string")
+                message.distinctVehicleCount = parseInt(object.distinctVehicleCount, 10);
+            else if (typeof object.distinctVehicleCount === "number")
+                message.distinctVehicleCount = object.distinctVehicleCount;
+            else if (typeof object.distinctVehicleCount === "object")
+                message.distinctVehicleCount = new $util.LongBits(object.distinctVehicleCount.low >>> 0, object.distinctVehicleCount.high >>> 0).toNumber(true);
+        if (object.elapsedTimeInMS != null)
+            if ($util.Long)
+                (message.elapsedTimeInMS = $util.Long.fromValue(object.elapsedTimeInMS)).unsigned = true;
+            else if (typeof object.elapsedTimeInMS === "string")
+                message.elapsedTimeInMS = parseInt(object.elapsedTimeInMS, 10);
+            else if (typeof object.elapsedTimeInMS === "number")
+                message.elapsedTimeInMS = object.elapsedTimeInMS;
+            else if (typeof object.elapsedTimeInMS === "object")
+                message.elapsedTimeInMS = new $util.LongBits(object.elapsedTimeInMS.low >>> 0, object.elapsedTimeInMS.high >>> 0).toNumber(true);
+        if (object.timeoutExpired != null)
+            message.timeoutExpired = Boolean(object.timeoutExpired);
+        if (object.limitReached != null)
+            message.limitReached = Boolean(object.limitReached);
+        if (object.fromCache != null)
+            message.fromCache = Boolean(object.fromCache);
+        if (object.cursor != null)
+            message.cursor = String(object.cursor);
+        if (object.duplicateCount != null)
+            if ($util.Long)
+                (message.duplicateCount = $util.Long.fromValue(object.duplicateCount)).unsigned = true;
+            else if (typeof object.duplicateCount === "string")
+                message.duplicateCount = parseInt(object.duplicateCount, 10);
+            else if (typeof object.duplicateCount === "number")
+                message.duplicateCount = object.duplicateCount;
+            else if (typeof object.duplicateCount === "object")
+                message.duplicateCount = new $util.LongBits(object.duplicateCount.low >>> 0, object.duplicateCount.high >>> 0).toNumber(true);
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a VehicleQueryResponse message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {VehicleQueryResponse} message VehicleQueryResponse
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    VehicleQueryResponse.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults) {
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.processedFilesCount = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.processedFilesCount = options.longs === String ? "0" : 0;
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.processedBytes = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.processedBytes = options.longs === String ? "0" : 0;
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.processedRecordCount = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.processedRecordCount = options.longs === String ? "0" : 0;
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.selectedRecordCount = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.selectedRecordCount = options.longs === String ? "0" : 0;
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.distinctVehicleCount = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.distinctVehicleCount = options.longs === String ? "0" : 0;
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.elapsedTimeInMS = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.elapsedTimeInMS = options.longs === String ? "0" : 0;
+            object.timeoutExpired = false;
+            object.limitReached = false;
+            object.fromCache = false;
+            if ($util.Long) {
+                var long = new $util.Long(0, 0, true);
+                object.duplicateCount = options.longs === String ? long.toString() : options.longs === Number ? long.toNumber() : long;
+            } else
+                object.duplicateCount = options.longs === String ? "0" : 0;
+        }
+        if (message.processedFilesCount != null && message.hasOwnProperty("processedFilesCount"))
+            if (typeof message.processedFilesCount === "number")
+                object.processedFilesCount = options.longs === String ? String(message.processedFilesCount) : message.processedFilesCount;
+            else
+                object.processedFilesCount = options.longs === String ? $util.Long.prototype.toString.call(message.processedFilesCount) : options.longs === Number ? new $util.LongBits(message.processedFilesCount.low >>> 0, message.processedFilesCount.high >>> 0).toNumber(true) : message.processedFilesCount;
+        if (message.processedBytes != null && message.hasOwnProperty("processedBytes"))
+            if (typeof message.processedBytes === "number")
+                object.processedBytes = options.longs === String ? String(message.processedBytes) : message.processedBytes;
+            else
+                object.processedBytes = options.longs === String ? $util.Long.prototype.toString.call(message.processedBytes) : options.longs === Number ? new $util.LongBits(message.processedBytes.low >>> 0, message.processedBytes.high >>> 0).toNumber(true) : message.processedBytes;
+        if (message.processedRecordCount != null && message.hasOwnProperty("processedRecordCount"))
+            if (typeof message.processedRecordCount === "number")
+                object.processedRecordCount = options.longs === String ? String(message.processedRecordCount) : message.processedRecordCount;
+            else
+                object.processedRecordCount = options.longs === String ? $util.Long.prototype.toString.call(message.processedRecordCount) : options.longs === Number ? new $util.LongBits(message.processedRecordCount.low >>> 0, message.processedRecordCount.high >>> 0).toNumber(true) : message.processedRecordCount;
+        if (message.selectedRecordCount != null && message.hasOwnProperty("selectedRecordCount"))
+            if (typeof message.selectedRecordCount === "number")
+                object.selectedRecordCount = options.longs === String ? String(message.selectedRecordCount) : message.selectedRecordCount;
+            else
+                object.selectedRecordCount = options.longs === String ? $util.Long.prototype.toString.call(message.selectedRecordCount) : options.longs === Number ? new $util.LongBits(message.selectedRecordCount.low >>> 0, message.selectedRecordCount.high >>> 0).toNumber(true) : message.selectedRecordCount;
+        if (message.distinctVehicleCount != null && message.hasOwnProperty("distinctVehicleCount"))
+            if (typeof message.distinctVehicleCount === "number")
+                object.distinctVehicleCount = options.longs === String ? String(message.distinctVehicleCount) : message.distinctVehicleCount;
+            else
+                object.distinctVehicleCount = options.longs === String ? $util.Long.prototype.toString.call(message.distinctVehicleCount) : options.longs === Number ? new $util.LongBits(message.distinctVehicleCount.low >>> 0, message.distinctVehicleCount.high >>> 0).toNumber(true) : message.distinctVehicleCount;
+        if (message.elapsedTimeInMS != null && message.hasOwnProperty("elapsedTimeInMS"))
+            if (typeof message.elapsedTimeInMS === "number")
+                object.elapsedTimeInMS = options.longs === String ? String(message.elapsedTimeInMS) : message.elapsedTimeInMS;
+            else
+                object.elapsedTimeInMS = options.longs === String ? $util.Long.prototype.toString.call(message.elapsedTimeInMS) : options.longs === Number ? new $util.LongBits(message.elapsedTimeInMS.low >>> 0, message.elapsedTimeInMS.high >>> 0).toNumber(true) : message.elapsedTimeInMS;
+        if (message.timeoutExpired != null && message.hasOwnProperty("timeoutExpired"))
+            object.timeoutExpired = message.timeoutExpired;
+        if (message.limitReached != null && message.hasOwnProperty("limitReached"))
+            object.limitReached = message.limitReached;
+        if (message.fromCache != null && message.hasOwnProperty("fromCache"))
+            object.fromCache = message.fromCache;
+        if (message.cursor != null && message.hasOwnProperty("cursor")) {
+            object.cursor = message.cursor;
+            if (options.oneofs)
+                object._cursor = "cursor";
+        }
+        if (message.duplicateCount != null && message.hasOwnProperty("duplicateCount"))
+            if (typeof message.duplicateCount === "number")
+                object.duplicateCount = options.longs === String ? String(message.duplicateCount) : message.duplicateCount;
+            else
+                object.duplicateCount = options.longs === String ? $util.Long.prototype.toString.call(message.duplicateCount) : options.longs === Number ? new $util.LongBits(message.duplicateCount.low >>> 0, message.duplicateCount.high >>> 0).toNumber(true) : message.duplicateCount;
+        return object;
+    };
+
+    /**
+     * Converts this VehicleQueryResponse to JSON.
+     * @function toJSON
+     * @memberof VehicleQueryResponse
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    VehicleQueryResponse.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for VehicleQueryResponse
+     * @function getTypeUrl
+     * @memberof VehicleQueryResponse
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    VehicleQueryResponse.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/VehicleQueryResponse";
+    };
+
+    return VehicleQueryResponse;
+})();
+
+$root.VehicleQueryStartedEvent = (function() {
+
+    /**
+     * Properties of a VehicleQueryStartedEvent.
+     * @exports IVehicleQueryStartedEvent
+     * @interface IVehicleQueryStartedEvent
+     * @property {IRequest|null} [query] VehicleQueryStartedEvent query
+     */
+
+    /**
+     * Constructs a new VehicleQueryStartedEvent.
+     * @exports VehicleQueryStartedEvent
+     * @classdesc Represents a VehicleQueryStartedEvent.
+     * @implements IVehicleQueryStartedEvent
+     * @constructor
+     * @param {IVehicleQueryStartedEvent=} [properties] Properties to set
+     */
+    function VehicleQueryStartedEvent(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * VehicleQueryStartedEvent query.
+     * @member {IRequest|null|undefined} query
+     * @memberof VehicleQueryStartedEvent
+     * @instance
+     */
+    VehicleQueryStartedEvent.prototype.query = null;
+
+    /**
+     * Creates a new VehicleQueryStartedEvent instance using the specified properties.
+     * @function create
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {IVehicleQueryStartedEvent=} [properties] Properties to set
+     * @returns {VehicleQueryStartedEvent} VehicleQueryStartedEvent instance
+     */
+    VehicleQueryStartedEvent.create = function create(properties) {
+        return new VehicleQueryStartedEvent(properties);
+    };
+
+    /**
+     * Encodes the specified VehicleQueryStartedEvent message. Does not implicitly {@link VehicleQueryStartedEvent.verify|verify} messages.
+     * @function encode
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {IVehicleQueryStartedEvent} message VehicleQueryStartedEvent message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryStartedEvent.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.query != null && Object.hasOwnProperty.call(message, "query"))
+            $root.Request.encode(message.query, writer.uint32(/* id 1, wireType 2 =*/10).fork()).ldelim();
+        return writer;
+    };
+
+    /**
+     * Encodes the specified VehicleQueryStartedEvent message, length delimited. Does not implicitly {@link VehicleQueryStartedEvent.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {IVehicleQueryStartedEvent} message VehicleQueryStartedEvent message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryStartedEvent.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a VehicleQueryStartedEvent message from the specified reader or buffer.
+     * @function decode
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {VehicleQueryStartedEvent} VehicleQueryStartedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryStartedEvent.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.VehicleQueryStartedEvent();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.query = $root.Request.decode(reader, reader.uint32());
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a VehicleQueryStartedEvent message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {VehicleQueryStartedEvent} VehicleQueryStartedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryStartedEvent.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a VehicleQueryStartedEvent message.
+     * @function verify
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    VehicleQueryStartedEvent.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        if (message.query != null && message.hasOwnProperty("query")) {
+            var error = $root.Request.verify(message.query);
+            if (error)
+                return "query." + error;
+        }
+        return null;
+    };
+
+    /**
+     * Creates a VehicleQueryStartedEvent message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {VehicleQueryStartedEvent} VehicleQueryStartedEvent
+     */
+    VehicleQueryStartedEvent.fromObject = function fromObject(object) {
+        if (object instanceof $root.VehicleQueryStartedEvent)
+            return object;
+        var message = new $root.VehicleQueryStartedEvent();
+        if (object.query != null) {
+            if (typeof object.query !== "object")
+                throw TypeError(".VehicleQueryStartedEvent.query: object expected");
+            message.query = $root.Request.fromObject(object.query);
+        }
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a VehicleQueryStartedEvent message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {VehicleQueryStartedEvent} message VehicleQueryStartedEvent
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    VehicleQueryStartedEvent.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults)
+            object.query = null;
+        if (message.query != null && message.hasOwnProperty("query"))
+            object.query = $root.Request.toObject(message.query, options);
+        return object;
+    };
+
+    /**
+     * Converts this VehicleQueryStartedEvent to JSON.
+     * @function toJSON
+     * @memberof VehicleQueryStartedEvent
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    VehicleQueryStartedEvent.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for VehicleQueryStartedEvent
+     * @function getTypeUrl
+     * @memberof VehicleQueryStartedEvent
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    VehicleQueryStartedEvent.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/VehicleQueryStartedEvent";
+    };
+
+    return VehicleQueryStartedEvent;
+})();
+
+$root.VehicleQueryStoppedEvent = (function() {
+
+    /**
+     * Properties of a VehicleQueryStoppedEvent.
+     * @exports IVehicleQueryStoppedEvent
+     * @interface IVehicleQueryStoppedEvent
+     * @property {IRequest|null} [query] VehicleQueryStoppedEvent query
+     * @property {boolean|null} [isSuccess] VehicleQueryStoppedEvent isSuccess
+     * @property {IVehicleQueryResponse|null} [response] VehicleQueryStoppedEvent response
+     * @property {string|null} [error] VehicleQueryStoppedEvent error
+     */
+
+    /**
+     * Constructs a new VehicleQueryStoppedEvent.
+     * @exports VehicleQueryStoppedEvent
+     * @classdesc Represents a VehicleQueryStoppedEvent.
+     * @implements IVehicleQueryStoppedEvent
+     * @constructor
+     * @param {IVehicleQueryStoppedEvent=} [properties] Properties to set
+     */
+    function VehicleQueryStoppedEvent(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * VehicleQueryStoppedEvent query.
+     * @member {IRequest|null|undefined} query
+     * @memberof VehicleQueryStoppedEvent
+     * @instance
+     */
+    VehicleQueryStoppedEvent.prototype.query = null;
+
+    /**
+     * VehicleQueryStoppedEvent isSuccess.
+     * @member {boolean} isSuccess
+     * @memberof VehicleQueryStoppedEvent
+     * @instance
+     */
+    VehicleQueryStoppedEvent.prototype.isSuccess = false;
+
+    /**
+     * VehicleQueryStoppedEvent response.
+     * @member {IVehicleQueryResponse|null|undefined} response
+     * @memberof VehicleQueryStoppedEvent
+     * @instance
+     */
+    VehicleQueryStoppedEvent.prototype.response = null;
+
+    /**
+     * VehicleQueryStoppedEvent error.
+     * @member {string|null|undefined} error
+     * @memberof VehicleQueryStoppedEvent
+     * @instance
+     */
+    VehicleQueryStoppedEvent.prototype.error = null;
+
+    // OneOf field names bound to virtual getters and setters
+    var $oneOfFields;
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(VehicleQueryStoppedEvent.prototype, "_error", {
+        get: $util.oneOfGetter($oneOfFields = ["error"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    /**
+     * Creates a new VehicleQueryStoppedEvent instance using the specified properties.
+     * @function create
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {IVehicleQueryStoppedEvent=} [properties] Properties to set
+     * @returns {VehicleQueryStoppedEvent} VehicleQueryStoppedEvent instance
+     */
+    VehicleQueryStoppedEvent.create = function create(properties) {
+        return new VehicleQueryStoppedEvent(properties);
+    };
+
+    /**
+     * Encodes the specified VehicleQueryStoppedEvent message. Does not implicitly {@link VehicleQueryStoppedEvent.verify|verify} messages.
+     * @function encode
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {IVehicleQueryStoppedEvent} message VehicleQueryStoppedEvent message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryStoppedEvent.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.query != null && Object.hasOwnProperty.call(message, "query"))
+            $root.Request.encode(message.query, writer.uint32(/* id 1, wireType 2 =*/10).fork()).ldelim();
+        if (message.isSuccess != null && Object.hasOwnProperty.call(message, "isSuccess"))
+            writer.uint32(/* id 2, wireType 0 =*/16).bool(message.isSuccess);
+        if (message.response != null && Object.hasOwnProperty.call(message, "response"))
+            $root.VehicleQueryResponse.encode(message.response, writer.uint32(/* id 3, wireType 2 =*/26).fork()).ldelim();
+        if (message.error != null && Object.hasOwnProperty.call(message, "error"))
+            writer.uint32(/* id 4, wireType 2 =*/34).string(message.error);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified VehicleQueryStoppedEvent message, length delimited. Does not implicitly {@link VehicleQueryStoppedEvent.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {IVehicleQueryStoppedEvent} message VehicleQueryStoppedEvent message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    VehicleQueryStoppedEvent.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a VehicleQueryStoppedEvent message from the specified reader or buffer.
+     * @function decode
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {VehicleQueryStoppedEvent} VehicleQueryStoppedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryStoppedEvent.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.VehicleQueryStoppedEvent();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.query = $root.Request.decode(reader, reader.uint32());
+                    break;
+                }
+            case 2: {
+                    message.isSuccess = reader.bool();
+                    break;
+                }
+            case 3: {
+                    message.response = $root.VehicleQueryResponse.decode(reader, reader.uint32());
+                    break;
+                }
+            case 4: {
+                    message.error = reader.string();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a VehicleQueryStoppedEvent message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {VehicleQueryStoppedEvent} VehicleQueryStoppedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    VehicleQueryStoppedEvent.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a VehicleQueryStoppedEvent message.
+     * @function verify
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    VehicleQueryStoppedEvent.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        var properties = {};
+        if (message.query != null && message.hasOwnProperty("query")) {
+            var error = $root.Request.verify(message.query);
+            if (error)
+                return "query." + error;
+        }
+        if (message.isSuccess != null && message.hasOwnProperty("isSuccess"))
+            if (typeof message.isSuccess !== "boolean")
+                return "isSuccess: boolean expected";
+        if (message.response != null && message.hasOwnProperty("response")) {
+            var error = $root.VehicleQueryResponse.verify(message.response);
+            if (error)
+                return "response." + error;
+        }
+        if (message.error != null && message.hasOwnProperty("error")) {
+            properties._error = 1;
+            if (!$util.isString(message.error))
+                return "error: string expected";
+        }
+        return null;
+    };
+
+    /**
+     * Creates a VehicleQueryStoppedEvent message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {VehicleQueryStoppedEvent} VehicleQueryStoppedEvent
+     */
+    VehicleQueryStoppedEvent.fromObject = function fromObject(object) {
+        if (object instanceof $root.VehicleQueryStoppedEvent)
+            return object;
+        var message = new $root.VehicleQueryStoppedEvent();
+        if (object.query != null) {
+            if (typeof object.query !== "object")
+                throw TypeError(".VehicleQueryStoppedEvent.query: object expected");
+            message.query = $root.Request.fromObject(object.query);
+        }
+        if (object.isSuccess != null)
+            message.isSuccess = Boolean(object.isSuccess);
+        if (object.response != null) {
+            if (typeof object.response !== "object")
+                throw TypeError(".VehicleQueryStoppedEvent.response: object expected");
+            message.response = $root.VehicleQueryResponse.fromObject(object.response);
+        }
+        if (object.error != null)
+            message.error = String(object.error);
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a VehicleQueryStoppedEvent message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {VehicleQueryStoppedEvent} message VehicleQueryStoppedEvent
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    VehicleQueryStoppedEvent.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults) {
+            object.query = null;
+            object.isSuccess = false;
+            object.response = null;
+        }
+        if (message.query != null && message.hasOwnProperty("query"))
+            object.query = $root.Request.toObject(message.query, options);
+        if (message.isSuccess != null && message.hasOwnProperty("isSuccess"))
+            object.isSuccess = message.isSuccess;
+        if (message.response != null && message.hasOwnProperty("response"))
+            object.response = $root.VehicleQueryResponse.toObject(message.response, options);
+        if (message.error != null && message.hasOwnProperty("error")) {
+            object.error = message.error;
+            if (options.oneofs)
+                object._error = "error";
+        }
+        return object;
+    };
+
+    /**
+     * Converts this VehicleQueryStoppedEvent to JSON.
+     * @function toJSON
+     * @memberof VehicleQueryStoppedEvent
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    VehicleQueryStoppedEvent.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for VehicleQueryStoppedEvent
+     * @function getTypeUrl
+     * @memberof VehicleQueryStoppedEvent
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    VehicleQueryStoppedEvent.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/VehicleQueryStoppedEvent";
+    };
+
+    return VehicleQueryStoppedEvent;
+})();
+
+$root.PingRequest = (function() {
+
+    /**
+     * Properties of a PingRequest.
+     * @exports IPingRequest
+     * @interface IPingRequest
+     * @property {string|null} [serviceName] PingRequest serviceName
+     */
+
+    /**
+     * Constructs a new PingRequest.
+     * @exports PingRequest
+     * @classdesc Represents a PingRequest.
+     * @implements IPingRequest
+     * @constructor
+     * @param {IPingRequest=} [properties] Properties to set
+     */
+    function PingRequest(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * PingRequest serviceName.
+     * @member {string|null|undefined} serviceName
+     * @memberof PingRequest
+     * @instance
+     */
+    PingRequest.prototype.serviceName = null;
+
+    // OneOf field names bound to virtual getters and setters
+    var $oneOfFields;
+
+    // Virtual OneOf for proto3 optional field
+    Object.defineProperty(PingRequest.prototype, "_serviceName", {
+        get: $util.oneOfGetter($oneOfFields = ["serviceName"]),
+        set: $util.oneOfSetter($oneOfFields)
+    });
+
+    /**
+     * Creates a new PingRequest instance using the specified properties.
+     * @function create
+     * @memberof PingRequest
+     * @static
+     * @param {IPingRequest=} [properties] Properties to set
+     * @returns {PingRequest} PingRequest instance
+     */
+    PingRequest.create = function create(properties) {
+        return new PingRequest(properties);
+    };
+
+    /**
+     * Encodes the specified PingRequest message. Does not implicitly {@link PingRequest.verify|verify} messages.
+     * @function encode
+     * @memberof PingRequest
+     * @static
+     * @param {IPingRequest} message PingRequest message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    PingRequest.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.serviceName != null && Object.hasOwnProperty.call(message, "serviceName"))
+            writer.uint32(/* id 1, wireType 2 =*/10).string(message.serviceName);
+        return writer;
+    };
+
+    /**
+     * Encodes the specified PingRequest message, length delimited. Does not implicitly {@link PingRequest.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof PingRequest
+     * @static
+     * @param {IPingRequest} message PingRequest message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    PingRequest.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a PingRequest message from the specified reader or buffer.
+     * @function decode
+     * @memberof PingRequest
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {PingRequest} PingRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    PingRequest.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.PingRequest();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.serviceName = reader.string();
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a PingRequest message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof PingRequest
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {PingRequest} PingRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    PingRequest.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a PingRequest message.
+     * @function verify
+     * @memberof PingRequest
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    PingRequest.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        var properties = {};
+        if (message.serviceName != null && message.hasOwnProperty("serviceName")) {
+            properties._serviceName = 1;
+            if (!$util.isString(message.serviceName))
+                return "serviceName: string expected";
+        }
+        return null;
+    };
+
+    /**
+     * Creates a PingRequest message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof PingRequest
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {PingRequest} PingRequest
+     */
+    PingRequest.fromObject = function fromObject(object) {
+        if (object instanceof $root.PingRequest)
+            return object;
+        var message = new $root.PingRequest();
+        if (object.serviceName != null)
+            message.serviceName = String(object.serviceName);
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a PingRequest message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof PingRequest
+     * @static
+     * @param {PingRequest} message PingRequest
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    PingRequest.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (message.serviceName != null && message.hasOwnProperty("serviceName")) {
+            object.serviceName = message.serviceName;
+            if (options.oneofs)
+                object._serviceName = "serviceName";
+        }
+        return object;
+    };
+
+    /**
+     * Converts this PingRequest to JSON.
+     * @function toJSON
+     * @memberof PingRequest
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    PingRequest.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for PingRequest
+     * @function getTypeUrl
+     * @memberof PingRequest
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    PingRequest.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/PingRequest";
+    };
+
+    return PingRequest;
+})();
+
+$root.PingResponse = (function() {
+
+    /**
+     * Properties of a PingResponse.
+     * @exports IPingResponse
+     * @interface IPingResponse
+     * @property {IServiceIdentity|null} [identity] PingResponse identity
+     */
+
+    /**
+     * Constructs a new PingResponse.
+     * @exports PingResponse
+     * @classdesc Represents a PingResponse.
+     * @implements IPingResponse
+     * @constructor
+     * @param {IPingResponse=} [properties] Properties to set
+     */
+    function PingResponse(properties) {
+        if (properties)
+            for (var keys = Object.keys(properties), i = 0; i < keys.length; ++i)
+                if (properties[keys[i]] != null)
+                    this[keys[i]] = properties[keys[i]];
+    }
+
+    /**
+     * PingResponse identity.
+     * @member {IServiceIdentity|null|undefined} identity
+     * @memberof PingResponse
+     * @instance
+     */
+    PingResponse.prototype.identity = null;
+
+    /**
+     * Creates a new PingResponse instance using the specified properties.
+     * @function create
+     * @memberof PingResponse
+     * @static
+     * @param {IPingResponse=} [properties] Properties to set
+     * @returns {PingResponse} PingResponse instance
+     */
+    PingResponse.create = function create(properties) {
+        return new PingResponse(properties);
+    };
+
+    /**
+     * Encodes the specified PingResponse message. Does not implicitly {@link PingResponse.verify|verify} messages.
+     * @function encode
+     * @memberof PingResponse
+     * @static
+     * @param {IPingResponse} message PingResponse message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    PingResponse.encode = function encode(message, writer) {
+        if (!writer)
+            writer = $Writer.create();
+        if (message.identity != null && Object.hasOwnProperty.call(message, "identity"))
+            $root.ServiceIdentity.encode(message.identity, writer.uint32(/* id 1, wireType 2 =*/10).fork()).ldelim();
+        return writer;
+    };
+
+    /**
+     * Encodes the specified PingResponse message, length delimited. Does not implicitly {@link PingResponse.verify|verify} messages.
+     * @function encodeDelimited
+     * @memberof PingResponse
+     * @static
+     * @param {IPingResponse} message PingResponse message or plain object to encode
+     * @param {$protobuf.Writer} [writer] Writer to encode to
+     * @returns {$protobuf.Writer} Writer
+     */
+    PingResponse.encodeDelimited = function encodeDelimited(message, writer) {
+        return this.encode(message, writer).ldelim();
+    };
+
+    /**
+     * Decodes a PingResponse message from the specified reader or buffer.
+     * @function decode
+     * @memberof PingResponse
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @param {number} [length] Message length if known beforehand
+     * @returns {PingResponse} PingResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    PingResponse.decode = function decode(reader, length, error) {
+        if (!(reader instanceof $Reader))
+            reader = $Reader.create(reader);
+        var end = length === undefined ? reader.len : reader.pos + length, message = new $root.PingResponse();
+        while (reader.pos < end) {
+            var tag = reader.uint32();
+            if (tag === error)
+                break;
+            switch (tag >>> 3) {
+            case 1: {
+                    message.identity = $root.ServiceIdentity.decode(reader, reader.uint32());
+                    break;
+                }
+            default:
+                reader.skipType(tag & 7);
+                break;
+            }
+        }
+        return message;
+    };
+
+    /**
+     * Decodes a PingResponse message from the specified reader or buffer, length delimited.
+     * @function decodeDelimited
+     * @memberof PingResponse
+     * @static
+     * @param {$protobuf.Reader|Uint8Array} reader Reader or buffer to decode from
+     * @returns {PingResponse} PingResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    PingResponse.decodeDelimited = function decodeDelimited(reader) {
+        if (!(reader instanceof $Reader))
+            reader = new $Reader(reader);
+        return this.decode(reader, reader.uint32());
+    };
+
+    /**
+     * Verifies a PingResponse message.
+     * @function verify
+     * @memberof PingResponse
+     * @static
+     * @param {Object.<string,*>} message Plain object to verify
+     * @returns {string|null} `null` if valid, otherwise the reason why it is not
+     */
+    PingResponse.verify = function verify(message) {
+        if (typeof message !== "object" || message === null)
+            return "object expected";
+        if (message.identity != null && message.hasOwnProperty("identity")) {
+            var error = $root.ServiceIdentity.verify(message.identity);
+            if (error)
+                return "identity." + error;
+        }
+        return null;
+    };
+
+    /**
+     * Creates a PingResponse message from a plain object. Also converts values to their respective internal types.
+     * @function fromObject
+     * @memberof PingResponse
+     * @static
+     * @param {Object.<string,*>} object Plain object
+     * @returns {PingResponse} PingResponse
+     */
+    PingResponse.fromObject = function fromObject(object) {
+        if (object instanceof $root.PingResponse)
+            return object;
+        var message = new $root.PingResponse();
+        if (object.identity != null) {
+            if (typeof object.identity !== "object")
+                throw TypeError(".PingResponse.identity: object expected");
+            message.identity = $root.ServiceIdentity.fromObject(object.identity);
+        }
+        return message;
+    };
+
+    /**
+     * Creates a plain object from a PingResponse message. Also converts values to other types if specified.
+     * @function toObject
+     * @memberof PingResponse
+     * @static
+     * @param {PingResponse} message PingResponse
+     * @param {$protobuf.IConversionOptions} [options] Conversion options
+     * @returns {Object.<string,*>} Plain object
+     */
+    PingResponse.toObject = function toObject(message, options) {
+        if (!options)
+            options = {};
+        var object = {};
+        if (options.defaults)
+            object.identity = null;
+        if (message.identity != null && message.hasOwnProperty("identity"))
+            object.identity = $root.ServiceIdentity.toObject(message.identity, options);
+        return object;
+    };
+
+    /**
+     * Converts this PingResponse to JSON.
+     * @function toJSON
+     * @memberof PingResponse
+     * @instance
+     * @returns {Object.<string,*>} JSON object
+     */
+    PingResponse.prototype.toJSON = function toJSON() {
+        return this.constructor.toObject(this, $protobuf.util.toJSONOptions);
+    };
+
+    /**
+     * Gets the default type url for PingResponse
+     * @function getTypeUrl
+     * @memberof PingResponse
+     * @static
+     * @param {string} [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns {string} The default type url
+     */
+    PingResponse.getTypeUrl = function getTypeUrl(typeUrlPrefix) {
+        if (typeUrlPrefix === undefined) {
+            typeUrlPrefix = "type.googleapis.com";
+        }
+        return typeUrlPrefix + "/PingResponse";
+    };
+
+    return PingResponse;
+})();
+
 module.exports = $root;
@@ -8,6 +8,9 @@ export interface IServiceIdentity {
 
     /** ServiceIdentity instance */
     instance?: (number|null);
+
+    /** ServiceIdentity runtime */
+    runtime?: (string|null);
 }
 
 /** Represents a ServiceIdentity. */
@@ -25,6 +28,9 @@ export class ServiceIdentity implements IServiceIdentity {
     /** ServiceIdentity instance. */
     public instance: number;
 
+    /** ServiceIdentity runtime. */
+    public runtime: string;
+
     /**
      * Creates a new ServiceIdentity instance using the specified properties.
      * @param [properties] Properties to set
@@ -707,3 +713,1255 @@ export class VehicleQueryResult implements IVehicleQueryResult {
      */
     public static getTypeUrl(typeUrlPrefix?: string): string;
 }
+
+/** Properties of a Request. */
+export interface IRequest {
+
+    /** Request id */
+    id?: (string|null);
+
+    /** Request replyTo */
+    replyTo?: (string|null);
+
+    /** Request parentId */
+    parentId?: (string|null);
+
+    /** Request expiresAt */
+    expiresAt?: (string|null);
+
+    /** Request timeout */
+    timeout?: (number|Long|null);
+
+    /** Request bodyType */
+    bodyType?: (string|null);
+
+    /** Request body */
+    body?: (Uint8Array|null);
+}
+
+/** Represents a Request. */
+export class Request implements IRequest {
+
+    /**
+     * Constructs a new Request.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IRequest);
+
+    /** Request id. */
+    public id: string;
+
+    /** Request replyTo. */
+    public replyTo: string;
+
+    /** Request parentId. */
+    public parentId?: (string|null);
+
+    /** Request expiresAt. */
+    public expiresAt?: (string|null);
+
+    /** Request timeout. */
+    public timeout?: (number|Long|null);
+
+    /** Request bodyType. */
+    public bodyType: string;
+
+    /** Request body. */
+    public body: Uint8Array;
+
+    /**
+     * Creates a new Request instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns Request instance
+     */
+    public static create(properties?: IRequest): Request;
+
+    /**
+     * Encodes the specified Request message. Does not implicitly {@link Request.verify|verify} messages.
+     * @param message Request message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IRequest, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified Request message, length delimited. Does not implicitly {@link Request.verify|verify} messages.
+     * @param message Request message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IRequest, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a Request message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns Request
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): Request;
+
+    /**
+     * Decodes a Request message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns Request
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): Request;
+
+    /**
+     * Verifies a Request message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a Request message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns Request
+     */
+    public static fromObject(object: { [k: string]: any }): Request;
+
+    /**
+     * Creates a plain object from a Request message. Also converts values to other types if specified.
+     * @param message Request
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: Request, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this Request to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for Request
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a Response. */
+export interface IResponse {
+
+    /** Response id */
+    id?: (string|null);
+
+    /** Response requestId */
+    requestId?: (string|null);
+
+    /** Response code */
+    code?: (string|null);
+
+    /** Response error */
+    error?: (string|null);
+
+    /** Response bodyType */
+    bodyType?: (string|null);
+
+    /** Response body */
+    body?: (Uint8Array|null);
+}
+
+/** Represents a Response. */
+export class Response implements IResponse {
+
+    /**
+     * Constructs a new Response.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IResponse);
+
+    /** Response id. */
+    public id: string;
+
+    /** Response requestId. */
+    public requestId: string;
+
+    /** Response code. */
+    public code?: (string|null);
+
+    /** Response error. */
+    public error?: (string|null);
+
+    /** Response bodyType. */
+    public bodyType: string;
+
+    /** Response body. */
+    public body: Uint8Array;
+
+    /**
+     * Creates a new Response instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns Response instance
+     */
+    public static create(properties?: IResponse): Response;
+
+    /**
+     * Encodes the specified Response message. Does not implicitly {@link Response.verify|verify} messages.
+     * @param message Response message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IResponse, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified Response message, length delimited. Does not implicitly {@link Response.verify|verify} messages.
+     * @param message Response message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IResponse, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a Response message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns Response
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): Response;
+
+    /**
+     * Decodes a Response message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns Response
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): Response;
+
+    /**
+     * Verifies a Response message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a Response message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns Response
+     */
+    public static fromObject(object: { [k: string]: any }): Response;
+
+    /**
+     * Creates a plain object from a Response message. Also converts values to other types if specified.
+     * @param message Response
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: Response, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this Response to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for Response
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a CircleArea. */
+export interface ICircleArea {
+
+    /** CircleArea center */
+    center?: (string|null);
+
+    /** CircleArea radiusInMeters */
+    radiusInMeters?: (number|null);
+}
+
+/** Represents a CircleArea. */
+export class CircleArea implements ICircleArea {
+
+    /**
+     * Constructs a new CircleArea.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: ICircleArea);
+
+    /** CircleArea center. */
+    public center: string;
+
+    /** CircleArea radiusInMeters. */
+    public radiusInMeters: number;
+
+    /**
+     * Creates a new CircleArea instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns CircleArea instance
+     */
+    public static create(properties?: ICircleArea): CircleArea;
+
+    /**
+     * Encodes the specified CircleArea message. Does not implicitly {@link CircleArea.verify|verify} messages.
+     * @param message CircleArea message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: ICircleArea, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified CircleArea message, length delimited. Does not implicitly {@link CircleArea.verify|verify} messages.
+     * @param message CircleArea message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: ICircleArea, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a CircleArea message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns CircleArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): CircleArea;
+
+    /**
+     * Decodes a CircleArea message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns CircleArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): CircleArea;
+
+    /**
+     * Verifies a CircleArea message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a CircleArea message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns CircleArea
+     */
+    public static fromObject(object: { [k: string]: any }): CircleArea;
+
+    /**
+     * Creates a plain object from a CircleArea message. Also converts values to other types if specified.
+     * @param message CircleArea
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: CircleArea, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this CircleArea to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for CircleArea
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a CorridorArea. */
+export interface ICorridorArea {
+
+    /** CorridorArea line */
+    line?: (string|null);
+
+    /** CorridorArea bufferInMeters */
+    bufferInMeters?: (number|null);
+}
+
+/** Represents a CorridorArea. */
+export class CorridorArea implements ICorridorArea {
+
+    /**
+     * Constructs a new CorridorArea.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: ICorridorArea);
+
+    /** CorridorArea line. */
+    public line: string;
+
+    /** CorridorArea bufferInMeters. */
+    public bufferInMeters: number;
+
+    /**
+     * Creates a new CorridorArea instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns CorridorArea instance
+     */
+    public static create(properties?: ICorridorArea): CorridorArea;
+
+    /**
+     * Encodes the specified CorridorArea message. Does not implicitly {@link CorridorArea.verify|verify} messages.
+     * @param message CorridorArea message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: ICorridorArea, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified CorridorArea message, length delimited. Does not implicitly {@link CorridorArea.verify|verify} messages.
+     * @param message CorridorArea message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: ICorridorArea, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a CorridorArea message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns CorridorArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): CorridorArea;
+
+    /**
+     * Decodes a CorridorArea message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns CorridorArea
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): CorridorArea;
+
+    /**
+     * Verifies a CorridorArea message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a CorridorArea message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns CorridorArea
+     */
+    public static fromObject(object: { [k: string]: any }): CorridorArea;
+
+    /**
+     * Creates a plain object from a CorridorArea message. Also converts values to other types if specified.
+     * @param message CorridorArea
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: CorridorArea, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this CorridorArea to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for CorridorArea
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a VehicleQueryRequest. */
+export interface IVehicleQueryRequest {
+
+    /** VehicleQueryRequest id */
+    id?: (string|null);
+
+    /** VehicleQueryRequest fromDate */
+    fromDate?: (string|null);
+
+    /** VehicleQueryRequest toDate */
+    toDate?: (string|null);
+
+    /** VehicleQueryRequest geometry */
+    geometry?: (string|null);
+
+    /** VehicleQueryRequest circle */
+    circle?: (ICircleArea|null);
+
+    /** VehicleQueryRequest corridor */
+    corridor?: (ICorridorArea|null);
+
+    /** VehicleQueryRequest vehicleTypes */
+    vehicleTypes?: (string[]|null);
+
+    /** VehicleQueryRequest vehicleIds */
+    vehicleIds?: (string[]|null);
+
+    /** VehicleQueryRequest minSpeed */
+    minSpeed?: (number|null);
+
+    /** VehicleQueryRequest maxSpeed */
+    maxSpeed?: (number|null);
+
+    /** VehicleQueryRequest directions */
+    directions?: (string[]|null);
+
+    /** VehicleQueryRequest limit */
+    limit?: (number|Long|null);
+
+    /** VehicleQueryRequest timeout */
+    timeout?: (number|Long|null);
+
+    /** VehicleQueryRequest ttl */
+    ttl?: (string|null);
+
+    /** VehicleQueryRequest parallelize */
+    parallelize?: (boolean|null);
+
+    /** VehicleQueryRequest useChunking */
+    useChunking?: (boolean|null);
+
+    /** VehicleQueryRequest orderBy */
+    orderBy?: (string|null);
+
+    /** VehicleQueryRequest cursor */
+    cursor?: (string|null);
+
+    /** VehicleQueryRequest deduplicate */
+    deduplicate?: (boolean|null);
+
+    /** VehicleQueryRequest sampleIntervalInSecs */
+    sampleIntervalInSecs?: (number|null);
+
+    /** VehicleQueryRequest sampleMethod */
+    sampleMethod?: (string|null);
+
+    /** VehicleQueryRequest format */
+    format?: (string|null);
+}
+
+/** Represents a VehicleQueryRequest. */
+export class VehicleQueryRequest implements IVehicleQueryRequest {
+
+    /**
+     * Constructs a new VehicleQueryRequest.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IVehicleQueryRequest);
+
+    /** VehicleQueryRequest id. */
+    public id: string;
+
+    /** VehicleQueryRequest fromDate. */
+    public fromDate: string;
+
+    /** VehicleQueryRequest toDate. */
+    public toDate: string;
+
+    /** VehicleQueryRequest geometry. */
+    public geometry?: (string|null);
+
+    /** VehicleQueryRequest circle. */
+    public circle?: (ICircleArea|null);
+
+    /** VehicleQueryRequest corridor. */
+    public corridor?: (ICorridorArea|null);
+
+    /** VehicleQueryRequest vehicleTypes. */
+    public vehicleTypes: string[];
+
+    /** VehicleQueryRequest vehicleIds. */
+    public vehicleIds: string[];
+
+    /** VehicleQueryRequest minSpeed. */
+    public minSpeed?: (number|null);
+
+    /** VehicleQueryRequest maxSpeed. */
+    public maxSpeed?: (number|null);
+
+    /** VehicleQueryRequest directions. */
+    public directions: string[];
+
+    /** VehicleQueryRequest limit. */
+    public limit?: (number|Long|null);
+
+    /** VehicleQueryRequest timeout. */
+    public timeout?: (number|Long|null);
+
+    /** VehicleQueryRequest ttl. */
+    public ttl?: (string|null);
+
+    /** VehicleQueryRequest parallelize. */
+    public parallelize?: (boolean|null);
+
+    /** VehicleQueryRequest useChunking. */
+    public useChunking?: (boolean|null);
+
+    /** VehicleQueryRequest orderBy. */
+    public orderBy?: (string|null);
+
+    /** VehicleQueryRequest cursor. */
+    public cursor?: (string|null);
+
+    /** VehicleQueryRequest deduplicate. */
+    public deduplicate?: (boolean|null);
+
+    /** VehicleQueryRequest sampleIntervalInSecs. */
+    public sampleIntervalInSecs?: (number|null);
+
+    /** VehicleQueryRequest sampleMethod. */
+    public sampleMethod?: (string|null);
+
+    /** VehicleQueryRequest format. */
+    public format?: (string|null);
+
+    /**
+     * Creates a new VehicleQueryRequest instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns VehicleQueryRequest instance
+     */
+    public static create(properties?: IVehicleQueryRequest): VehicleQueryRequest;
+
+    /**
+     * Encodes the specified VehicleQueryRequest message. Does not implicitly {@link VehicleQueryRequest.verify|verify} messages.
+     * @param message VehicleQueryRequest message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IVehicleQueryRequest, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified VehicleQueryRequest message, length delimited. Does not implicitly {@link VehicleQueryRequest.verify|verify} messages.
+     * @param message VehicleQueryRequest message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IVehicleQueryRequest, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a VehicleQueryRequest message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns VehicleQueryRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): VehicleQueryRequest;
+
+    /**
+     * Decodes a VehicleQueryRequest message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns VehicleQueryRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): VehicleQueryRequest;
+
+    /**
+     * Verifies a VehicleQueryRequest message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a VehicleQueryRequest message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns VehicleQueryRequest
+     */
+    public static fromObject(object: { [k: string]: any }): VehicleQueryRequest;
+
+    /**
+     * Creates a plain object from a VehicleQueryRequest message. Also converts values to other types if specified.
+     * @param message VehicleQueryRequest
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: VehicleQueryRequest, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this VehicleQueryRequest to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for VehicleQueryRequest
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a VehicleQueryResponse. */
+export interface IVehicleQueryResponse {
+
+    /** VehicleQueryResponse processedFilesCount */
+    processedFilesCount?: (number|Long|null);
+
+    /** VehicleQueryResponse processedBytes */
+    processedBytes?: (number|Long|null);
+
+    /** VehicleQueryResponse processedRecordCount */
+    processedRecordCount?: (number|Long|null);
+
+    /** VehicleQueryResponse selectedRecordCount */
+    selectedRecordCount?: (number|Long|null);
+
+    /** VehicleQueryResponse distinctVehicleCount */
+    distinctVehicleCount?: (number|Long|null);
+
+    /** VehicleQueryResponse elapsedTimeInMS */
+    elapsedTimeInMS?: (number|Long|null);
+
+    /** VehicleQueryResponse timeoutExpired */
+    timeoutExpired?: (boolean|null);
+
+    /** VehicleQueryResponse limitReached */
+    limitReached?: (boolean|null);
+
+    /** VehicleQueryResponse fromCache */
+    fromCache?: (boolean|null);
+
+    /** VehicleQueryResponse cursor */
+    cursor?: (string|null);
+
+    /** VehicleQueryResponse duplicateCount */
+    duplicateCount?: (number|Long|null);
+}
+
+/** Represents a VehicleQueryResponse. */
+export class VehicleQueryResponse implements IVehicleQueryResponse {
+
+    /**
+     * Constructs a new VehicleQueryResponse.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IVehicleQueryResponse);
+
+    /** VehicleQueryResponse processedFilesCount. */
+    public processedFilesCount: (number|Long);
+
+    /** VehicleQueryResponse processedBytes. */
+    public processedBytes: (number|Long);
+
+    /** VehicleQueryResponse processedRecordCount. */
+    public processedRecordCount: (number|Long);
+
+    /** VehicleQueryResponse selectedRecordCount. */
+    public selectedRecordCount: (number|Long);
+
+    /** VehicleQueryResponse distinctVehicleCount. */
+    public distinctVehicleCount: (number|Long);
+
+    /** VehicleQueryResponse elapsedTimeInMS. */
+    public elapsedTimeInMS: (number|Long);
+
+    /** VehicleQueryResponse timeoutExpired. */
+    public timeoutExpired: boolean;
+
+    /** VehicleQueryResponse limitReached. */
+    public limitReached: boolean;
+
+    /** VehicleQueryResponse fromCache. */
+    public fromCache: boolean;
+
+    /** VehicleQueryResponse cursor. */
+    public cursor?: (string|null);
+
+    /** VehicleQueryResponse duplicateCount. */
+    public duplicateCount: (number|Long);
+
+    /**
+     * Creates a new VehicleQueryResponse instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns VehicleQueryResponse instance
+     */
+    public static create(properties?: IVehicleQueryResponse): VehicleQueryResponse;
+
+    /**
+     * Encodes the specified VehicleQueryResponse message. Does not implicitly {@link VehicleQueryResponse.verify|verify} messages.
+     * @param message VehicleQueryResponse message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IVehicleQueryResponse, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified VehicleQueryResponse message, length delimited. Does not implicitly {@link VehicleQueryResponse.verify|verify} messages.
+     * @param message VehicleQueryResponse message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IVehicleQueryResponse, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a VehicleQueryResponse message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns VehicleQueryResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): VehicleQueryResponse;
+
+    /**
+     * Decodes a VehicleQueryResponse message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns VehicleQueryResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): VehicleQueryResponse;
+
+    /**
+     * Verifies a VehicleQueryResponse message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a VehicleQueryResponse message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns VehicleQueryResponse
+     */
+    public static fromObject(object: { [k: string]: any }): VehicleQueryResponse;
+
+    /**
+     * Creates a plain object from a VehicleQueryResponse message. Also converts values to other types if specified.
+     * @param message VehicleQueryResponse
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: VehicleQueryResponse, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this VehicleQueryResponse to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for VehicleQueryResponse
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a VehicleQueryStartedEvent. */
+export interface IVehicleQueryStartedEvent {
+
+    /** VehicleQueryStartedEvent query */
+    query?: (IRequest|null);
+}
+
+/** Represents a VehicleQueryStartedEvent. */
+export class VehicleQueryStartedEvent implements IVehicleQueryStartedEvent {
+
+    /**
+     * Constructs a new VehicleQueryStartedEvent.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IVehicleQueryStartedEvent);
+
+    /** VehicleQueryStartedEvent query. */
+    public query?: (IRequest|null);
+
+    /**
+     * Creates a new VehicleQueryStartedEvent instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns VehicleQueryStartedEvent instance
+     */
+    public static create(properties?: IVehicleQueryStartedEvent): VehicleQueryStartedEvent;
+
+    /**
+     * Encodes the specified VehicleQueryStartedEvent message. Does not implicitly {@link VehicleQueryStartedEvent.verify|verify} messages.
+     * @param message VehicleQueryStartedEvent message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IVehicleQueryStartedEvent, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified VehicleQueryStartedEvent message, length delimited. Does not implicitly {@link VehicleQueryStartedEvent.verify|verify} messages.
+     * @param message VehicleQueryStartedEvent message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IVehicleQueryStartedEvent, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a VehicleQueryStartedEvent message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns VehicleQueryStartedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): VehicleQueryStartedEvent;
+
+    /**
+     * Decodes a VehicleQueryStartedEvent message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns VehicleQueryStartedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): VehicleQueryStartedEvent;
+
+    /**
+     * Verifies a VehicleQueryStartedEvent message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a VehicleQueryStartedEvent message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns VehicleQueryStartedEvent
+     */
+    public static fromObject(object: { [k: string]: any }): VehicleQueryStartedEvent;
+
+    /**
+     * Creates a plain object from a VehicleQueryStartedEvent message. Also converts values to other types if specified.
+     * @param message VehicleQueryStartedEvent
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: VehicleQueryStartedEvent, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this VehicleQueryStartedEvent to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for VehicleQueryStartedEvent
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a VehicleQueryStoppedEvent. */
+export interface IVehicleQueryStoppedEvent {
+
+    /** VehicleQueryStoppedEvent query */
+    query?: (IRequest|null);
+
+    /** VehicleQueryStoppedEvent isSuccess */
+    isSuccess?: (boolean|null);
+
+    /** VehicleQueryStoppedEvent response */
+    response?: (IVehicleQueryResponse|null);
+
+    /** VehicleQueryStoppedEvent error */
+    error?: (string|null);
+}
+
+/** Represents a VehicleQueryStoppedEvent. */
+export class VehicleQueryStoppedEvent implements IVehicleQueryStoppedEvent {
+
+    /**
+     * Constructs a new VehicleQueryStoppedEvent.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IVehicleQueryStoppedEvent);
+
+    /** VehicleQueryStoppedEvent query. */
+    public query?: (IRequest|null);
+
+    /** VehicleQueryStoppedEvent isSuccess. */
+    public isSuccess: boolean;
+
+    /** VehicleQueryStoppedEvent response. */
+    public response?: (IVehicleQueryResponse|null);
+
+    /** VehicleQueryStoppedEvent error. */
+    public error?: (string|null);
+
+    /**
+     * Creates a new VehicleQueryStoppedEvent instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns VehicleQueryStoppedEvent instance
+     */
+    public static create(properties?: IVehicleQueryStoppedEvent): VehicleQueryStoppedEvent;
+
+    /**
+     * Encodes the specified VehicleQueryStoppedEvent message. Does not implicitly {@link VehicleQueryStoppedEvent.verify|verify} messages.
+     * @param message VehicleQueryStoppedEvent message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IVehicleQueryStoppedEvent, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified VehicleQueryStoppedEvent message, length delimited. Does not implicitly {@link VehicleQueryStoppedEvent.verify|verify} messages.
+     * @param message VehicleQueryStoppedEvent message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IVehicleQueryStoppedEvent, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a VehicleQueryStoppedEvent message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns VehicleQueryStoppedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): VehicleQueryStoppedEvent;
+
+    /**
+     * Decodes a VehicleQueryStoppedEvent message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns VehicleQueryStoppedEvent
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): VehicleQueryStoppedEvent;
+
+    /**
+     * Verifies a VehicleQueryStoppedEvent message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a VehicleQueryStoppedEvent message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns VehicleQueryStoppedEvent
+     */
+    public static fromObject(object: { [k: string]: any }): VehicleQueryStoppedEvent;
+
+    /**
+     * Creates a plain object from a VehicleQueryStoppedEvent message. Also converts values to other types if specified.
+     * @param message VehicleQueryStoppedEvent
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: VehicleQueryStoppedEvent, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this VehicleQueryStoppedEvent to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for VehicleQueryStoppedEvent
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a PingRequest. */
+export interface IPingRequest {
+
+    /** PingRequest serviceName */
+    serviceName?: (string|null);
+}
+
+/** Represents a PingRequest. */
+export class PingRequest implements IPingRequest {
+
+    /**
+     * Constructs a new PingRequest.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IPingRequest);
+
+    /** PingRequest serviceName. */
+    public serviceName?: (string|null);
+
+    /**
+     * Creates a new PingRequest instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns PingRequest instance
+     */
+    public static create(properties?: IPingRequest): PingRequest;
+
+    /**
+     * Encodes the specified PingRequest message. Does not implicitly {@link PingRequest.verify|verify} messages.
+     * @param message PingRequest message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IPingRequest, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified PingRequest message, length delimited. Does not implicitly {@link PingRequest.verify|verify} messages.
+     * @param message PingRequest message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IPingRequest, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a PingRequest message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns PingRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): PingRequest;
+
+    /**
+     * Decodes a PingRequest message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns PingRequest
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): PingRequest;
+
+    /**
+     * Verifies a PingRequest message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a PingRequest message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns PingRequest
+     */
+    public static fromObject(object: { [k: string]: any }): PingRequest;
+
+    /**
+     * Creates a plain object from a PingRequest message. Also converts values to other types if specified.
+     * @param message PingRequest
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: PingRequest, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this PingRequest to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for PingRequest
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}
+
+/** Properties of a PingResponse. */
+export interface IPingResponse {
+
+    /** PingResponse identity */
+    identity?: (IServiceIdentity|null);
+}
+
+/** Represents a PingResponse. */
+export class PingResponse implements IPingResponse {
+
+    /**
+     * Constructs a new PingResponse.
+     * @param [properties] Properties to set
+     */
+    constructor(properties?: IPingResponse);
+
+    /** PingResponse identity. */
+    public identity?: (IServiceIdentity|null);
+
+    /**
+     * Creates a new PingResponse instance using the specified properties.
+     * @param [properties] Properties to set
+     * @returns PingResponse instance
+     */
+    public static create(properties?: IPingResponse): PingResponse;
+
+    /**
+     * Encodes the specified PingResponse message. Does not implicitly {@link PingResponse.verify|verify} messages.
+     * @param message PingResponse message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encode(message: IPingResponse, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Encodes the specified PingResponse message, length delimited. Does not implicitly {@link PingResponse.verify|verify} messages.
+     * @param message PingResponse message or plain object to encode
+     * @param [writer] Writer to encode to
+     * @returns Writer
+     */
+    public static encodeDelimited(message: IPingResponse, writer?: $protobuf.Writer): $protobuf.Writer;
+
+    /**
+     * Decodes a PingResponse message from the specified reader or buffer.
+     * @param reader Reader or buffer to decode from
+     * @param [length] Message length if known beforehand
+     * @returns PingResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decode(reader: ($protobuf.Reader|Uint8Array), length?: number): PingResponse;
+
+    /**
+     * Decodes a PingResponse message from the specified reader or buffer, length delimited.
+     * @param reader Reader or buffer to decode from
+     * @returns PingResponse
+     * @throws {Error} If the payload is not a reader or valid buffer
+     * @throws {$protobuf.util.ProtocolError} If required fields are missing
+     */
+    public static decodeDelimited(reader: ($protobuf.Reader|Uint8Array)): PingResponse;
+
+    /**
+     * Verifies a PingResponse message.
+     * @param message Plain object to verify
+     * @returns `null` if valid, otherwise the reason why it is not
+     */
+    public static verify(message: { [k: string]: any }): (string|null);
+
+    /**
+     * Creates a PingResponse message from a plain object. Also converts values to their respective internal types.
+     * @param object Plain object
+     * @returns PingResponse
+     */
+    public static fromObject(object: { [k: string]: any }): PingResponse;
+
+    /**
+     * Creates a plain object from a PingResponse message. Also converts values to other types if specified.
+     * @param message PingResponse
+     * @param [options] Conversion options
+     * @returns Plain object
+     */
+    public static toObject(message: PingResponse, options?: $protobuf.IConversionOptions): { [k: string]: any };
+
+    /**
+     * Converts this PingResponse to JSON.
+     * @returns JSON object
+     */
+    public toJSON(): { [k: string]: any };
+
+    /**
+     * Gets the default type url for PingResponse
+     * @param [typeUrlPrefix] your custom typeUrlPrefix(default "type.googleapis.com")
+     * @returns The default type url
+     */
+    public static getTypeUrl(typeUrlPrefix?: string): string;
+}